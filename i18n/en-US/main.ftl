@@ -14,6 +14,56 @@ delete = Delete
 
 add-grade = Add a grade
 import-grades = Import grades
+import-preview = Import preview
+import-summary = Valid rows: {$valid}, rows with errors: {$invalid}
+import-fix-errors = Fix the errors in the file and upload it again
+import-confirm = Apply grades
+import-applied = {$count ->
+    [one] {$count} grade has been imported
+   *[other] {$count} grades have been imported
+}
+import-format = The file must be a CSV with the columns student (email or identifier), subject and grade
+line = Line
+back-to-grades = Back to grades
+bulk-entry = Bulk entry
+entry-summary = Saved grades: {$saved}, rows with errors: {$invalid}
+export-csv = Export CSV
+export-xlsx = Export XLSX
+history = History
+search-students = Search by student name
+sort-by = Sort by this column
+first-page = First page
+next-page = Next page
+grade-statistics = Statistics
+statistics-scale-note = All values are shown as percentages, regardless of the subject's grading scale
+statistics-count = Grades
+statistics-mean = Mean
+statistics-median = Median
+statistics-std-deviation = Std. deviation
+statistics-min = Min
+statistics-lower-quartile = Lower quartile
+statistics-upper-quartile = Upper quartile
+statistics-max = Max
+statistics-empty = There are no grades in this term yet
+dashboard = Progress
+dashboard-average = Your average
+dashboard-class-average = Class average
+dashboard-z-score = Z-score
+dashboard-class-mean = Class mean
+dashboard-difference = Difference
+dashboard-class-size = {$count ->
+    [one] among {$count} student
+   *[other] among {$count} students
+}
+dashboard-trend = Average by term
+dashboard-empty = You have no grades in this term yet
+grade-history = Grade history
+changed-at = Date
+changed-by = Changed by
+old-value = Old grade
+new-value = New grade
+change-reason = Reason
+no-grade-history = No changes have been recorded yet
 
 system-theme = System
 light-theme = Light
@@ -27,16 +77,142 @@ login-action = Login
 
 logout-action = Logout
 
+forgot-password = Forgot your password?
+password-reset = Password reset
+password-reset-action = Send reset link
+password-reset-sent = If an account with this email exists, a link to reset the password has been sent to it
+new-password = New password
+new-password-action = Set password
+
+password-reset-subject = Reset your password
+password-reset-greeting = Hello, {$user}!
+password-reset-instructions = {$minutes ->
+    [one] Follow the link below to set a new password. The link can be used once and expires in {$minutes} minute.
+   *[other] Follow the link below to set a new password. The link can be used once and expires in {$minutes} minutes.
+}
+password-reset-ignore = If you did not request a password reset, ignore this email.
+
+two-factor = Two-factor authentication
+two-factor-setup = Two-factor authentication
+two-factor-scan = Scan the code with an authenticator app or enter the key manually, then confirm with a generated code
+two-factor-prompt = Enter the code from your authenticator app or one of your recovery codes
+two-factor-action = Confirm
+code = Code
+recovery-codes = Recovery codes
+recovery-codes-hint = Store these codes in a safe place. Each of them can be used once instead of an authenticator code and they will not be shown again
+continue = Continue
+
+verification-sent = We have sent a verification link to your email address, follow it to activate the account
+verification-missing = Did not receive the verification email?
+verification-resend = Email verification
+verification-resend-action = Send verification link
+email-verified = Your email address has been verified
+
+email-verification-subject = Verify your email address
+email-verification-greeting = Welcome, {$user}!
+email-verification-instructions = Follow the link below to verify your email address and activate the account.
+email-verification-ignore = If you did not sign up, ignore this email.
+
+invite-teacher = Invite a teacher
+invite-action = Send invitation
+invite-sent = The invitation has been sent. It is valid for a limited time and can be used once
+
+teacher-invite-subject = You have been invited to join as a teacher
+teacher-invite-greeting = {$inviter} has invited you to join as a teacher.
+teacher-invite-instructions = {$days ->
+    [one] Follow the link below to create your account. The link can be used once and expires in {$days} day.
+   *[other] Follow the link below to create your account. The link can be used once and expires in {$days} days.
+}
+teacher-invite-ignore = If you were not expecting this invitation, ignore this email.
+
+users = Users
+search-users = Search by name or email
+role = Role
+status = Status
+teacher = Teacher
+admin = Administrator
+user-active = Active
+user-unverified = Unverified
+user-disabled = Disabled
+enable = Enable
+disable = Disable
+delete-user-confirm = Delete {$user} and all of their data?
+administration = Administration
+manage-users = Manage users
+
 welcome-user = Welcome, {$user}!
 
 general-access = Access: general
 
 student = Student
 
+student-id = Student ID
+
 grade = Grade
 
+grade-status-graded = Graded
+grade-status-ungraded = Not graded yet
+grade-status-excused = Excused
+grade-status-absent = Absent
+grade-status-retracted = Retracted
+retract = Retract
+retract-grade-confirm = Retract the grade of {$student}? A reason is required
+
 subject = Subject
 
+subjects = Subjects
+manage-subjects = Manage subjects
+subject-id = Code
+subject-title = Title
+add-subject = Add a subject
+grades = Grades
+subject-active = Active
+subject-archived = Archived
+archive = Archive
+restore = Restore
+delete-subject-confirm = Delete {$subject}?
+
+classes = Classes
+manage-classes = Manage classes
+class-id = Code
+class-name = Name
+add-class = Add a class
+students = Students
+roster = Roster
+enroll = Enroll
+unenroll = Unenroll
+assign = Assign
+unassign = Unassign
+delete-class-confirm = Delete {$class}? Grades of its students are kept.
+
+assessments = Assessments
+add-assessment = Add an assessment
+assessment-title = Title
+assessment-kind = Kind
+weight = Weight
+score = Score
+final-grade = Final grade
+kind-test = Test
+kind-homework = Homework
+kind-exam = Exam
+delete-assessment-confirm = Delete {$assessment} and all of its scores?
+
+terms = Terms
+manage-terms = Manage terms
+term = Term
+term-name = Name
+starts-on = Starts on
+ends-on = Ends on
+add-term = Add a term
+term-active = Active
+activate = Activate
+
+grading-scale = Grading scale
+grading-scale-percent = Percent (0–100)
+grading-scale-twelve-point = 12-point (1–12)
+grading-scale-letter = Letter (A–F)
+grading-scale-gpa = GPA (0–4)
+
 access-keys = Access keys
 
 error-internal = An unexpected error occurred
@@ -46,3 +222,77 @@ error-not-found = Requested resourse was not found
 error-invalid-credentials = Invalid login or password
 
 error-unauthorized = Log in to continue
+
+error-forbidden = You do not have permission to perform this action
+
+error-session-expired = Your session has expired, log in again
+
+error-csrf-failed = Your request could not be verified, reload the page and try again
+
+error-invalid-reset-token = This password reset link is invalid or has expired
+
+error-email-not-verified = Verify your email address before logging in
+
+error-invalid-verification-token = This verification link is invalid or has expired
+
+error-invalid-two-factor-code = The code is invalid or has already been used
+
+error-two-factor-expired = Your login attempt has expired, log in again
+
+error-two-factor-enabled = Two-factor authentication is already enabled
+
+error-too-many-attempts = Too many failed login attempts, try again in {$seconds} seconds
+
+error-account-disabled = Your account has been disabled
+
+error-self-modification = You cannot change your own account here
+
+error-invalid-role = Unknown role
+
+error-email-taken = An account with this email already exists
+
+error-invalid-invite = The invitation is invalid, expired or has already been used
+
+error-invite-email-mismatch = The invitation was sent to a different email address
+
+error-subject-exists = A subject with this code already exists
+
+error-subject-has-grades = The subject has grades, archive it instead
+
+error-class-exists = A class with this code already exists
+
+error-invalid-import-file = The file could not be read as CSV
+
+error-import-missing-columns = The file must have student, subject and grade columns
+
+error-unsupported-export-format = Export format must be csv or xlsx
+
+error-term-exists = A term with this name already exists
+
+error-term-invalid-dates = A term cannot end before it starts
+
+error-unknown-term = The selected term does not exist
+
+error-grade-conflict = {$status ->
+    [graded] Someone else has changed this grade to {$current} in the meantime. Reload it and try again
+    [ungraded] Someone else has cleared this grade in the meantime. Reload it and try again
+    [excused] Someone else has marked this student as excused in the meantime. Reload it and try again
+    [absent] Someone else has marked this student as absent in the meantime. Reload it and try again
+    [retracted] Someone else has retracted this grade in the meantime. Reload it and try again
+   *[other] Someone else has changed this grade in the meantime. Reload it and try again
+}
+
+error-grade-locked = {$status ->
+    [excused] This student is excused from the subject, change the grade status before entering scores
+    [absent] This student is marked as absent, change the grade status before entering scores
+   *[other] This grade has been retracted, change the grade status before entering scores
+}
+
+error-grade-exists = {$status ->
+    [graded] This student already has the grade {$current} in this subject, change it on the grades page instead
+    [excused] This student is excused from the subject, change the grade on the grades page instead
+    [absent] This student is marked as absent, change the grade on the grades page instead
+   *[other] This grade has been retracted, change it on the grades page instead
+}
+
+error-grade-computed = This grade is calculated from assessment scores, change the scores instead
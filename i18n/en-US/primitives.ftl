@@ -43,4 +43,110 @@ error-password-no-digits = {password} must contain at least one digit
 
 error-password-no-special = {password} must contain at least one special character
 
-error-grade-invalid-format = Enter a value from {$min} to {$max} with optional {$fraction-digits} digit fraction
+error-grade-invalid-format = {$scale ->
+    [letter] Enter one of the letter grades: {$letters}
+    [twelve-point] Enter a whole number from {$min} to {$max}
+   *[other] Enter a value from {$min} to {$max} with optional {$fraction-digits} digit fraction
+}
+
+error-subject-id-too-short = {$min ->
+    [one] Subject code must be at least {$min} character long
+   *[other] Subject code must be at least {$min} characters long
+}
+
+error-subject-id-too-long = {$max ->
+    [one] Subject code cannot be more than {$max} character long
+   *[other] Subject code cannot be more than {$max} characters long
+}
+
+error-subject-title-too-short = {$min ->
+    [one] Subject title must be at least {$min} character long
+   *[other] Subject title must be at least {$min} characters long
+}
+
+error-subject-title-too-long = {$max ->
+    [one] Subject title cannot be more than {$max} character long
+   *[other] Subject title cannot be more than {$max} characters long
+}
+
+error-class-id-too-short = {$min ->
+    [one] Class code must be at least {$min} character long
+   *[other] Class code must be at least {$min} characters long
+}
+
+error-class-id-too-long = {$max ->
+    [one] Class code cannot be more than {$max} character long
+   *[other] Class code cannot be more than {$max} characters long
+}
+
+error-class-name-too-short = {$min ->
+    [one] Class name must be at least {$min} character long
+   *[other] Class name must be at least {$min} characters long
+}
+
+error-class-name-too-long = {$max ->
+    [one] Class name cannot be more than {$max} character long
+   *[other] Class name cannot be more than {$max} characters long
+}
+
+error-invalid-user-id = Given value is not a valid user identifier
+
+error-unknown-student = No student with this email or identifier exists
+
+error-unknown-subject = No active subject with this code exists
+
+error-not-enrolled = The student is not enrolled in a class that studies this subject
+
+error-duplicate-import-row = This student already has a grade for the subject earlier in the file
+
+error-reason-too-short = {$min ->
+    [one] Reason must be at least {$min} character long
+   *[other] Reason must be at least {$min} characters long
+}
+
+error-reason-too-long = {$max ->
+    [one] Reason cannot be more than {$max} character long
+   *[other] Reason cannot be more than {$max} characters long
+}
+
+error-weight-invalid-format = Enter a weight greater than 0 and up to {$max} with optional {$fraction-digits} digit fraction
+
+error-assessment-title-too-short = {$min ->
+    [one] Assessment title must be at least {$min} character long
+   *[other] Assessment title must be at least {$min} characters long
+}
+
+error-assessment-title-too-long = {$max ->
+    [one] Assessment title cannot be more than {$max} character long
+   *[other] Assessment title cannot be more than {$max} characters long
+}
+
+error-invalid-assessment-kind = Assessment kind must be a test, homework or exam
+
+error-invalid-assessment-id = Given value is not a valid assessment identifier
+
+error-term-name-too-short = {$min ->
+    [one] Term name must be at least {$min} character long
+   *[other] Term name must be at least {$min} characters long
+}
+
+error-term-name-too-long = {$max ->
+    [one] Term name cannot be more than {$max} character long
+   *[other] Term name cannot be more than {$max} characters long
+}
+
+error-invalid-term-date = Enter a date in the YYYY-MM-DD format
+
+error-invalid-term-id = Given value is not a valid term identifier
+
+error-invalid-grading-scale = Grading scale must be percent, 12-point, letter or GPA
+
+error-invalid-grade-status = Grade status must be graded, not graded yet, excused or absent
+
+error-invalid-grade-version = Given value is not a valid grade version
+
+error-duplicate-grade-row = This student already has a grade for the subject in an earlier row
+
+error-invalid-grade-sort = Grades can be sorted by student, subject or grade
+
+error-invalid-sort-order = Sort order must be ascending or descending
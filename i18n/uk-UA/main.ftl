@@ -14,6 +14,54 @@ delete = Видалити
 
 add-grade = Додати оцінку
 import-grades = Імпортувати оцінки
+import-preview = Попередній перегляд імпорту
+import-summary = Коректних рядків: {$valid}, рядків з помилками: {$invalid}
+import-fix-errors = Виправте помилки у файлі та завантажте його знову
+import-confirm = Застосувати оцінки
+import-applied = Імпортовано оцінок: {$count}
+import-format = Файл має бути у форматі CSV зі стовпцями student (адреса або ідентифікатор), subject та grade
+line = Рядок
+back-to-grades = Повернутися до оцінок
+bulk-entry = Масове введення
+entry-summary = Збережено оцінок: {$saved}, рядків з помилками: {$invalid}
+export-csv = Експорт у CSV
+export-xlsx = Експорт у XLSX
+history = Історія
+search-students = Пошук за ім'ям студента
+sort-by = Сортувати за цим стовпцем
+first-page = Перша сторінка
+next-page = Наступна сторінка
+grade-statistics = Статистика
+statistics-scale-note = Усі значення подано у відсотках незалежно від шкали оцінювання предмета
+statistics-count = Оцінок
+statistics-mean = Середнє
+statistics-median = Медіана
+statistics-std-deviation = Станд. відхилення
+statistics-min = Мін.
+statistics-lower-quartile = Нижній квартиль
+statistics-upper-quartile = Верхній квартиль
+statistics-max = Макс.
+statistics-empty = У цьому семестрі ще немає оцінок
+dashboard = Успішність
+dashboard-average = Ваш середній бал
+dashboard-class-average = Середній бал класу
+dashboard-z-score = Z-оцінка
+dashboard-class-mean = Середнє класу
+dashboard-difference = Різниця
+dashboard-class-size = {$count ->
+    [one] серед {$count} студента
+    [few] серед {$count} студентів
+   *[other] серед {$count} студентів
+}
+dashboard-trend = Середній бал за семестрами
+dashboard-empty = У вас ще немає оцінок у цьому семестрі
+grade-history = Історія оцінки
+changed-at = Дата
+changed-by = Змінив
+old-value = Стара оцінка
+new-value = Нова оцінка
+change-reason = Причина
+no-grade-history = Змін ще не зафіксовано
 
 system-theme = Авто
 light-theme = Світла
@@ -27,16 +75,136 @@ login-action = Увійти
 
 logout-action = Вийти
 
+forgot-password = Забули пароль?
+password-reset = Відновлення пароля
+password-reset-action = Надіслати посилання
+password-reset-sent = Якщо обліковий запис з цією адресою існує, на неї надіслано посилання для відновлення пароля
+new-password = Новий пароль
+new-password-action = Встановити пароль
+
+password-reset-subject = Відновлення пароля
+password-reset-greeting = Вітаю, {$user}!
+password-reset-instructions = Перейдіть за посиланням нижче, щоб встановити новий пароль. Посилання можна використати один раз, воно діє {$minutes} хв.
+password-reset-ignore = Якщо ви не запитували відновлення пароля, проігноруйте цей лист.
+
+two-factor = Двофакторна автентифікація
+two-factor-setup = Двофакторна автентифікація
+two-factor-scan = Відскануйте код у застосунку-автентифікаторі або введіть ключ вручну, потім підтвердіть згенерованим кодом
+two-factor-prompt = Введіть код із застосунку-автентифікатора або один із резервних кодів
+two-factor-action = Підтвердити
+code = Код
+recovery-codes = Резервні коди
+recovery-codes-hint = Збережіть ці коди в надійному місці. Кожен з них можна використати один раз замість коду автентифікатора, і вони більше не будуть показані
+continue = Продовжити
+
+verification-sent = Ми надіслали посилання для підтвердження на вашу адресу, перейдіть за ним, щоб активувати обліковий запис
+verification-missing = Не отримали лист для підтвердження?
+verification-resend = Підтвердження пошти
+verification-resend-action = Надіслати посилання
+email-verified = Вашу адресу електронної пошти підтверджено
+
+email-verification-subject = Підтвердження адреси електронної пошти
+email-verification-greeting = Вітаю, {$user}!
+email-verification-instructions = Перейдіть за посиланням нижче, щоб підтвердити адресу та активувати обліковий запис.
+email-verification-ignore = Якщо ви не реєструвалися, проігноруйте цей лист.
+
+invite-teacher = Запросити викладача
+invite-action = Надіслати запрошення
+invite-sent = Запрошення надіслано. Воно діє обмежений час і може бути використане один раз
+
+teacher-invite-subject = Вас запрошено приєднатися як викладача
+teacher-invite-greeting = {$inviter} запрошує вас приєднатися як викладача.
+teacher-invite-instructions = Перейдіть за посиланням нижче, щоб створити обліковий запис. Посилання можна використати один раз, воно діє {$days} дн.
+teacher-invite-ignore = Якщо ви не очікували цього запрошення, проігноруйте цей лист.
+
+users = Користувачі
+search-users = Пошук за ім'ям або поштою
+role = Роль
+status = Статус
+teacher = Викладач
+admin = Адміністратор
+user-active = Активний
+user-unverified = Не підтверджений
+user-disabled = Заблокований
+enable = Розблокувати
+disable = Заблокувати
+delete-user-confirm = Видалити {$user} та всі пов'язані дані?
+administration = Адміністрування
+manage-users = Керування користувачами
+
 welcome-user = Вітаю, {$user}!
 
 general-access = Доступ: загальний
 
 student = Студент
 
+student-id = ID студента
+
 grade = Оцінка
 
+grade-status-graded = Оцінено
+grade-status-ungraded = Ще не оцінено
+grade-status-excused = Звільнено
+grade-status-absent = Відсутній
+grade-status-retracted = Відкликано
+retract = Відкликати
+retract-grade-confirm = Відкликати оцінку студента {$student}? Потрібно вказати причину
+
 subject = Дисципліна
 
+subjects = Предмети
+manage-subjects = Керування предметами
+subject-id = Код
+subject-title = Назва
+add-subject = Додати предмет
+grades = Оцінки
+subject-active = Активний
+subject-archived = В архіві
+archive = Архівувати
+restore = Відновити
+delete-subject-confirm = Видалити {$subject}?
+
+classes = Класи
+manage-classes = Керування класами
+class-id = Код
+class-name = Назва
+add-class = Додати клас
+students = Студенти
+roster = Склад
+enroll = Зарахувати
+unenroll = Відрахувати
+assign = Призначити
+unassign = Зняти
+delete-class-confirm = Видалити {$class}? Оцінки студентів буде збережено.
+
+assessments = Оцінювання
+add-assessment = Додати оцінювання
+assessment-title = Назва
+assessment-kind = Тип
+weight = Вага
+score = Бал
+final-grade = Підсумкова оцінка
+kind-test = Тест
+kind-homework = Домашнє завдання
+kind-exam = Іспит
+delete-assessment-confirm = Видалити {$assessment} разом з усіма балами?
+
+terms = Семестри
+manage-terms = Керування семестрами
+term = Семестр
+term-name = Назва
+starts-on = Початок
+ends-on = Кінець
+add-term = Додати семестр
+term-active = Поточний
+activate = Зробити поточним
+
+grading-scale = Шкала оцінювання
+grading-scale-percent = Відсоткова (0–100)
+grading-scale-twelve-point = 12-бальна (1–12)
+grading-scale-letter = Літерна (A–F)
+grading-scale-gpa = GPA (0–4)
+
 access-keys = Ключі доступу
 
 error-internal = Щось пішло не так
@@ -46,3 +214,77 @@ error-not-found = Запитаний ресурс не знайдено
 error-invalid-credentials = Невірний логін або пароль
 
 error-unauthorized = Увійдіть щоб продовжити
+
+error-forbidden = У вас немає дозволу на цю дію
+
+error-session-expired = Ваш сеанс завершився, увійдіть знову
+
+error-csrf-failed = Не вдалося перевірити запит, оновіть сторінку та спробуйте знову
+
+error-invalid-reset-token = Посилання для відновлення пароля недійсне або застаріле
+
+error-email-not-verified = Підтвердіть адресу електронної пошти перед входом
+
+error-invalid-verification-token = Посилання для підтвердження недійсне або застаріле
+
+error-invalid-two-factor-code = Код недійсний або вже використаний
+
+error-two-factor-expired = Час на вхід вичерпано, увійдіть знову
+
+error-two-factor-enabled = Двофакторну автентифікацію вже увімкнено
+
+error-too-many-attempts = Забагато невдалих спроб входу, спробуйте знову через {$seconds} с
+
+error-account-disabled = Ваш обліковий запис заблоковано
+
+error-self-modification = Тут не можна змінювати власний обліковий запис
+
+error-invalid-role = Невідома роль
+
+error-email-taken = Обліковий запис з цією адресою вже існує
+
+error-invalid-invite = Запрошення недійсне, прострочене або вже використане
+
+error-invite-email-mismatch = Запрошення було надіслано на іншу адресу
+
+error-subject-exists = Предмет з таким кодом уже існує
+
+error-subject-has-grades = Предмет має оцінки, заархівуйте його замість видалення
+
+error-class-exists = Клас з таким кодом уже існує
+
+error-invalid-import-file = Не вдалося прочитати файл як CSV
+
+error-import-missing-columns = Файл повинен містити стовпці student, subject та grade
+
+error-unsupported-export-format = Формат експорту має бути csv або xlsx
+
+error-term-exists = Семестр з такою назвою вже існує
+
+error-term-invalid-dates = Семестр не може закінчуватися раніше, ніж починається
+
+error-unknown-term = Обраного семестру не існує
+
+error-grade-conflict = {$status ->
+    [graded] Тим часом хтось інший змінив цю оцінку на {$current}. Оновіть її та спробуйте ще раз
+    [ungraded] Тим часом хтось інший прибрав цю оцінку. Оновіть її та спробуйте ще раз
+    [excused] Тим часом хтось інший позначив студента звільненим. Оновіть оцінку та спробуйте ще раз
+    [absent] Тим часом хтось інший позначив студента відсутнім. Оновіть оцінку та спробуйте ще раз
+    [retracted] Тим часом хтось інший відкликав цю оцінку. Оновіть її та спробуйте ще раз
+   *[other] Тим часом хтось інший змінив цю оцінку. Оновіть її та спробуйте ще раз
+}
+
+error-grade-locked = {$status ->
+    [excused] Студента звільнено від предмета, змініть статус оцінки, перш ніж вносити бали
+    [absent] Студента позначено відсутнім, змініть статус оцінки, перш ніж вносити бали
+   *[other] Цю оцінку відкликано, змініть статус оцінки, перш ніж вносити бали
+}
+
+error-grade-exists = {$status ->
+    [graded] Цей студент уже має оцінку {$current} з цього предмета, змініть її на сторінці оцінок
+    [excused] Студента звільнено від предмета, змініть оцінку на сторінці оцінок
+    [absent] Студента позначено відсутнім, змініть оцінку на сторінці оцінок
+   *[other] Цю оцінку відкликано, змініть її на сторінці оцінок
+}
+
+error-grade-computed = Ця оцінка обчислюється з балів за оцінювання, змініть бали
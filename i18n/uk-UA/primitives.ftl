@@ -48,4 +48,124 @@ error-password-no-digits = {password} повинен містити принай
 
 error-password-no-special = {password} повинен містити щонайменше один спеціальний символ
 
-error-grade-invalid-format = Введіть значення від {$min} до {$max} з можливими {$fraction-digits} цифрами після коми
+error-grade-invalid-format = {$scale ->
+    [letter] Введіть одну з літерних оцінок: {$letters}
+    [twelve-point] Введіть ціле число від {$min} до {$max}
+   *[other] Введіть значення від {$min} до {$max} з можливими {$fraction-digits} цифрами після коми
+}
+
+error-subject-id-too-short = {$min ->
+    [one] Код предмета повинен містити щонайменше {$min} символ
+    [few] Код предмета повинен містити щонайменше {$min} символи
+   *[other] Код предмета повинен містити щонайменше {$min} символів
+}
+
+error-subject-id-too-long = {$max ->
+    [one] Код предмета не може містити більше ніж {$max} символ
+    [few] Код предмета не може містити більше ніж {$max} символи
+   *[other] Код предмета не може містити більше ніж {$max} символів
+}
+
+error-subject-title-too-short = {$min ->
+    [one] Назва предмета повинна містити щонайменше {$min} символ
+    [few] Назва предмета повинна містити щонайменше {$min} символи
+   *[other] Назва предмета повинна містити щонайменше {$min} символів
+}
+
+error-subject-title-too-long = {$max ->
+    [one] Назва предмета не може містити більше ніж {$max} символ
+    [few] Назва предмета не може містити більше ніж {$max} символи
+   *[other] Назва предмета не може містити більше ніж {$max} символів
+}
+
+error-class-id-too-short = {$min ->
+    [one] Код класу повинен містити щонайменше {$min} символ
+    [few] Код класу повинен містити щонайменше {$min} символи
+   *[other] Код класу повинен містити щонайменше {$min} символів
+}
+
+error-class-id-too-long = {$max ->
+    [one] Код класу не може містити більше ніж {$max} символ
+    [few] Код класу не може містити більше ніж {$max} символи
+   *[other] Код класу не може містити більше ніж {$max} символів
+}
+
+error-class-name-too-short = {$min ->
+    [one] Назва класу повинна містити щонайменше {$min} символ
+    [few] Назва класу повинна містити щонайменше {$min} символи
+   *[other] Назва класу повинна містити щонайменше {$min} символів
+}
+
+error-class-name-too-long = {$max ->
+    [one] Назва класу не може містити більше ніж {$max} символ
+    [few] Назва класу не може містити більше ніж {$max} символи
+   *[other] Назва класу не може містити більше ніж {$max} символів
+}
+
+error-invalid-user-id = Значення не є коректним ідентифікатором користувача
+
+error-unknown-student = Студента з такою адресою або ідентифікатором не існує
+
+error-unknown-subject = Активного предмета з таким кодом не існує
+
+error-not-enrolled = Студент не навчається в класі, який вивчає цей предмет
+
+error-duplicate-import-row = Цей студент уже має оцінку з предмета вище у файлі
+
+error-reason-too-short = {$min ->
+    [one] Причина повинна містити щонайменше {$min} символ
+    [few] Причина повинна містити щонайменше {$min} символи
+   *[other] Причина повинна містити щонайменше {$min} символів
+}
+
+error-reason-too-long = {$max ->
+    [one] Причина не може містити більше ніж {$max} символ
+    [few] Причина не може містити більше ніж {$max} символи
+   *[other] Причина не може містити більше ніж {$max} символів
+}
+
+error-weight-invalid-format = Введіть вагу більшу за 0 і не більшу ніж {$max} з можливими {$fraction-digits} цифрами після коми
+
+error-assessment-title-too-short = {$min ->
+    [one] Назва оцінювання повинна містити щонайменше {$min} символ
+    [few] Назва оцінювання повинна містити щонайменше {$min} символи
+   *[other] Назва оцінювання повинна містити щонайменше {$min} символів
+}
+
+error-assessment-title-too-long = {$max ->
+    [one] Назва оцінювання не може містити більше ніж {$max} символ
+    [few] Назва оцінювання не може містити більше ніж {$max} символи
+   *[other] Назва оцінювання не може містити більше ніж {$max} символів
+}
+
+error-invalid-assessment-kind = Тип оцінювання має бути тестом, домашнім завданням або іспитом
+
+error-invalid-assessment-id = Вказане значення не є коректним ідентифікатором оцінювання
+
+error-term-name-too-short = {$min ->
+    [one] Назва семестру повинна містити щонайменше {$min} символ
+    [few] Назва семестру повинна містити щонайменше {$min} символи
+   *[other] Назва семестру повинна містити щонайменше {$min} символів
+}
+
+error-term-name-too-long = {$max ->
+    [one] Назва семестру не може містити більше ніж {$max} символ
+    [few] Назва семестру не може містити більше ніж {$max} символи
+   *[other] Назва семестру не може містити більше ніж {$max} символів
+}
+
+error-invalid-term-date = Введіть дату у форматі РРРР-ММ-ДД
+
+error-invalid-term-id = Вказане значення не є коректним ідентифікатором семестру
+
+error-invalid-grading-scale = Шкала оцінювання має бути відсотковою, 12-бальною, літерною або GPA
+
+error-invalid-grade-status = Статус оцінки має бути «оцінено», «ще не оцінено», «звільнено» або «відсутній»
+
+error-invalid-grade-version = Вказане значення не є коректною версією оцінки
+
+error-duplicate-grade-row = Цей студент уже має оцінку з предмета в одному з попередніх рядків
+
+error-invalid-grade-sort = Оцінки можна сортувати за студентом, предметом або оцінкою
+
+error-invalid-sort-order = Порядок сортування має бути за зростанням або за спаданням
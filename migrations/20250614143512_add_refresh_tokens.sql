@@ -0,0 +1,19 @@
+-- Create "refresh_tokens" table
+CREATE TABLE "public"."refresh_tokens" (
+  "hash" text NOT NULL,
+  "user_id" bigint NOT NULL,
+  "session_id" uuid NOT NULL,
+  "expires_at" timestamptz NOT NULL,
+  "used_at" timestamptz NULL,
+  "revoked_at" timestamptz NULL,
+  PRIMARY KEY ("hash"),
+  CONSTRAINT "refresh_tokens_user_id_fkey" FOREIGN KEY ("user_id") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+);
+-- Create index "refresh_tokens_session_id_idx" to table: "refresh_tokens"
+CREATE INDEX "refresh_tokens_session_id_idx" ON "public"."refresh_tokens" ("session_id");
+-- Create "revoked_tokens" table
+CREATE TABLE "public"."revoked_tokens" (
+  "id" uuid NOT NULL,
+  "expires_at" timestamptz NOT NULL,
+  PRIMARY KEY ("id")
+);
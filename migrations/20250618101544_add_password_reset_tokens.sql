@@ -0,0 +1,9 @@
+-- Create "password_reset_tokens" table
+CREATE TABLE "public"."password_reset_tokens" (
+  "hash" text NOT NULL,
+  "user_id" bigint NOT NULL,
+  "expires_at" timestamptz NOT NULL,
+  "used_at" timestamptz NULL,
+  PRIMARY KEY ("hash"),
+  CONSTRAINT "password_reset_tokens_user_id_fkey" FOREIGN KEY ("user_id") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+);
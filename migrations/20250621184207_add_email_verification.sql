@@ -0,0 +1,4 @@
+-- Modify "users" table
+ALTER TABLE "public"."users" ADD COLUMN "verified_at" timestamptz NULL;
+
+UPDATE "public"."users" SET "verified_at" = now();
@@ -0,0 +1,20 @@
+-- Create "totp_credentials" table
+CREATE TABLE "public"."totp_credentials" (
+  "user_id" bigint NOT NULL,
+  "secret" text NOT NULL,
+  "enabled_at" timestamptz NULL,
+  "last_used_step" bigint NULL,
+  PRIMARY KEY ("user_id"),
+  CONSTRAINT "totp_credentials_user_id_fkey" FOREIGN KEY ("user_id") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+);
+-- Create "recovery_codes" table
+CREATE TABLE "public"."recovery_codes" (
+  "id" bigserial NOT NULL,
+  "user_id" bigint NOT NULL,
+  "hash" text NOT NULL,
+  "used_at" timestamptz NULL,
+  PRIMARY KEY ("id"),
+  CONSTRAINT "recovery_codes_user_id_fkey" FOREIGN KEY ("user_id") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+);
+-- Create index "recovery_codes_user_id_idx" to table: "recovery_codes"
+CREATE INDEX "recovery_codes_user_id_idx" ON "public"."recovery_codes" ("user_id");
@@ -0,0 +1,14 @@
+-- Create "login_attempts" table
+CREATE TABLE "public"."login_attempts" (
+  "id" bigserial NOT NULL,
+  "email" text NOT NULL,
+  "ip" text NOT NULL,
+  "outcome" text NOT NULL,
+  "attempted_at" timestamptz NOT NULL DEFAULT now(),
+  PRIMARY KEY ("id"),
+  CONSTRAINT "login_attempts_outcome_check" CHECK (outcome = ANY (ARRAY['succeeded'::text, 'failed'::text, 'throttled'::text]))
+);
+-- Create index "login_attempts_email_attempted_at_idx" to table: "login_attempts"
+CREATE INDEX "login_attempts_email_attempted_at_idx" ON "public"."login_attempts" ("email", "attempted_at");
+-- Create index "login_attempts_ip_attempted_at_idx" to table: "login_attempts"
+CREATE INDEX "login_attempts_ip_attempted_at_idx" ON "public"."login_attempts" ("ip", "attempted_at");
@@ -0,0 +1,2 @@
+-- Modify "users" table
+ALTER TABLE "public"."users" DROP CONSTRAINT "users_role_check", ADD CONSTRAINT "users_role_check" CHECK (role = ANY (ARRAY['teacher'::text, 'student'::text, 'admin'::text])), ADD COLUMN "disabled_at" timestamptz NULL;
@@ -0,0 +1,10 @@
+-- Create "invites" table
+CREATE TABLE "public"."invites" (
+  "hash" text NOT NULL,
+  "email" text NOT NULL,
+  "invited_by" bigint NULL,
+  "expires_at" timestamptz NOT NULL,
+  "used_at" timestamptz NULL,
+  PRIMARY KEY ("hash"),
+  CONSTRAINT "invites_invited_by_fkey" FOREIGN KEY ("invited_by") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE SET NULL
+);
@@ -0,0 +1,4 @@
+-- Modify "subjects" table
+ALTER TABLE "public"."subjects" ADD COLUMN "archived_at" timestamptz NULL;
+-- Modify "grades" table
+ALTER TABLE "public"."grades" DROP CONSTRAINT "grades_subject_id_fkey", ADD CONSTRAINT "grades_subject_id_fkey" FOREIGN KEY ("subject_id") REFERENCES "public"."subjects" ("id") ON UPDATE NO ACTION ON DELETE RESTRICT;
@@ -0,0 +1,26 @@
+-- Create "classes" table
+CREATE TABLE "public"."classes" (
+  "id" text NOT NULL,
+  "name" text NOT NULL,
+  PRIMARY KEY ("id")
+);
+-- Create "class_students" table
+CREATE TABLE "public"."class_students" (
+  "class_id" text NOT NULL,
+  "user_id" bigint NOT NULL,
+  PRIMARY KEY ("class_id", "user_id"),
+  CONSTRAINT "class_students_class_id_fkey" FOREIGN KEY ("class_id") REFERENCES "public"."classes" ("id") ON UPDATE NO ACTION ON DELETE CASCADE,
+  CONSTRAINT "class_students_user_id_fkey" FOREIGN KEY ("user_id") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+);
+-- Create index "class_students_user_id_idx" to table: "class_students"
+CREATE INDEX "class_students_user_id_idx" ON "public"."class_students" ("user_id");
+-- Create "class_subjects" table
+CREATE TABLE "public"."class_subjects" (
+  "class_id" text NOT NULL,
+  "subject_id" text NOT NULL,
+  PRIMARY KEY ("class_id", "subject_id"),
+  CONSTRAINT "class_subjects_class_id_fkey" FOREIGN KEY ("class_id") REFERENCES "public"."classes" ("id") ON UPDATE NO ACTION ON DELETE CASCADE,
+  CONSTRAINT "class_subjects_subject_id_fkey" FOREIGN KEY ("subject_id") REFERENCES "public"."subjects" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+);
+-- Create index "class_subjects_subject_id_idx" to table: "class_subjects"
+CREATE INDEX "class_subjects_subject_id_idx" ON "public"."class_subjects" ("subject_id");
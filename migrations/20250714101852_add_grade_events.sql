@@ -0,0 +1,17 @@
+-- Create "grade_events" table
+CREATE TABLE "public"."grade_events" (
+  "id" bigserial NOT NULL,
+  "student_id" bigint NOT NULL,
+  "subject_id" text NOT NULL,
+  "actor_id" bigint NULL,
+  "old_value" numeric(5,2) NULL,
+  "new_value" numeric(5,2) NULL,
+  "reason" text NULL,
+  "created_at" timestamptz NOT NULL DEFAULT now(),
+  PRIMARY KEY ("id"),
+  CONSTRAINT "grade_events_actor_id_fkey" FOREIGN KEY ("actor_id") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE SET NULL,
+  CONSTRAINT "grade_events_student_id_fkey" FOREIGN KEY ("student_id") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE CASCADE,
+  CONSTRAINT "grade_events_subject_id_fkey" FOREIGN KEY ("subject_id") REFERENCES "public"."subjects" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+);
+-- Create index "grade_events_student_id_subject_id_idx" to table: "grade_events"
+CREATE INDEX "grade_events_student_id_subject_id_idx" ON "public"."grade_events" ("student_id", "subject_id");
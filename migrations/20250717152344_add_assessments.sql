@@ -0,0 +1,25 @@
+-- Create "assessments" table
+CREATE TABLE "public"."assessments" (
+  "id" bigserial NOT NULL,
+  "subject_id" text NOT NULL,
+  "title" text NOT NULL,
+  "kind" text NOT NULL,
+  "weight" numeric(5,2) NOT NULL,
+  PRIMARY KEY ("id"),
+  CONSTRAINT "assessments_subject_id_fkey" FOREIGN KEY ("subject_id") REFERENCES "public"."subjects" ("id") ON UPDATE NO ACTION ON DELETE CASCADE,
+  CONSTRAINT "assessments_kind_check" CHECK (kind = ANY (ARRAY['test'::text, 'homework'::text, 'exam'::text])),
+  CONSTRAINT "assessments_weight_check" CHECK (weight > (0)::numeric)
+);
+-- Create index "assessments_subject_id_idx" to table: "assessments"
+CREATE INDEX "assessments_subject_id_idx" ON "public"."assessments" ("subject_id");
+-- Create "assessment_scores" table
+CREATE TABLE "public"."assessment_scores" (
+  "assessment_id" bigint NOT NULL,
+  "user_id" bigint NOT NULL,
+  "value" numeric(5,2) NOT NULL,
+  PRIMARY KEY ("assessment_id", "user_id"),
+  CONSTRAINT "assessment_scores_assessment_id_fkey" FOREIGN KEY ("assessment_id") REFERENCES "public"."assessments" ("id") ON UPDATE NO ACTION ON DELETE CASCADE,
+  CONSTRAINT "assessment_scores_user_id_fkey" FOREIGN KEY ("user_id") REFERENCES "public"."users" ("id") ON UPDATE NO ACTION ON DELETE CASCADE
+);
+-- Create index "assessment_scores_user_id_idx" to table: "assessment_scores"
+CREATE INDEX "assessment_scores_user_id_idx" ON "public"."assessment_scores" ("user_id");
@@ -0,0 +1,39 @@
+-- Create "terms" table
+CREATE TABLE "public"."terms" (
+  "id" bigserial NOT NULL,
+  "name" text NOT NULL,
+  "starts_on" date NOT NULL,
+  "ends_on" date NOT NULL,
+  "is_active" boolean NOT NULL DEFAULT false,
+  PRIMARY KEY ("id"),
+  CONSTRAINT "terms_name_key" UNIQUE ("name"),
+  CONSTRAINT "terms_check" CHECK (ends_on >= starts_on)
+);
+-- Create index "terms_is_active_idx" to table: "terms"
+CREATE UNIQUE INDEX "terms_is_active_idx" ON "public"."terms" ("is_active") WHERE is_active;
+
+INSERT INTO "public"."terms" ("name", "starts_on", "ends_on", "is_active") VALUES ('Initial term', CURRENT_DATE, CURRENT_DATE + 364, true);
+
+-- Modify "grades" table
+ALTER TABLE "public"."grades" ADD COLUMN "term_id" bigint NULL;
+-- Modify "grade_events" table
+ALTER TABLE "public"."grade_events" ADD COLUMN "term_id" bigint NULL;
+-- Modify "assessments" table
+ALTER TABLE "public"."assessments" ADD COLUMN "term_id" bigint NULL;
+
+UPDATE "public"."grades" SET "term_id" = (SELECT "id" FROM "public"."terms" WHERE "is_active");
+UPDATE "public"."grade_events" SET "term_id" = (SELECT "id" FROM "public"."terms" WHERE "is_active");
+UPDATE "public"."assessments" SET "term_id" = (SELECT "id" FROM "public"."terms" WHERE "is_active");
+
+-- Modify "grades" table
+ALTER TABLE "public"."grades" ALTER COLUMN "term_id" SET NOT NULL, DROP CONSTRAINT "grades_pkey", ADD PRIMARY KEY ("user_id", "subject_id", "term_id"), ADD CONSTRAINT "grades_term_id_fkey" FOREIGN KEY ("term_id") REFERENCES "public"."terms" ("id") ON UPDATE NO ACTION ON DELETE RESTRICT;
+-- Create index "grades_term_id_idx" to table: "grades"
+CREATE INDEX "grades_term_id_idx" ON "public"."grades" ("term_id");
+-- Modify "grade_events" table
+ALTER TABLE "public"."grade_events" ALTER COLUMN "term_id" SET NOT NULL, ADD CONSTRAINT "grade_events_term_id_fkey" FOREIGN KEY ("term_id") REFERENCES "public"."terms" ("id") ON UPDATE NO ACTION ON DELETE CASCADE;
+-- Modify "assessments" table
+ALTER TABLE "public"."assessments" ALTER COLUMN "term_id" SET NOT NULL, ADD CONSTRAINT "assessments_term_id_fkey" FOREIGN KEY ("term_id") REFERENCES "public"."terms" ("id") ON UPDATE NO ACTION ON DELETE CASCADE;
+-- Drop index "assessments_subject_id_idx" from table: "assessments"
+DROP INDEX "public"."assessments_subject_id_idx";
+-- Create index "assessments_subject_id_term_id_idx" to table: "assessments"
+CREATE INDEX "assessments_subject_id_term_id_idx" ON "public"."assessments" ("subject_id", "term_id");
@@ -0,0 +1,6 @@
+-- Modify "subjects" table
+ALTER TABLE "public"."subjects" ADD CONSTRAINT "subjects_grading_scale_check" CHECK (grading_scale = ANY (ARRAY['percent'::text, 'twelve-point'::text, 'letter'::text, 'gpa'::text])), ADD COLUMN "grading_scale" text NOT NULL DEFAULT 'percent';
+-- Modify "grades" table
+ALTER TABLE "public"."grades" ADD COLUMN "scaled_value" text NULL, ADD COLUMN "gpa" numeric(3,2) NULL;
+
+UPDATE "public"."grades" SET "scaled_value" = "value"::text, "gpa" = CASE WHEN "value" >= 90 THEN 4 WHEN "value" >= 80 THEN 3 WHEN "value" >= 70 THEN 2 WHEN "value" >= 60 THEN 1 ELSE 0 END WHERE "value" IS NOT NULL;
@@ -0,0 +1,7 @@
+-- Modify "grades" table
+ALTER TABLE "public"."grades" ADD CONSTRAINT "grades_status_check" CHECK (status = ANY (ARRAY['graded'::text, 'ungraded'::text, 'excused'::text, 'absent'::text])), ADD COLUMN "status" text NOT NULL DEFAULT 'graded';
+
+UPDATE "public"."grades" SET "status" = 'ungraded', "scaled_value" = NULL, "gpa" = NULL WHERE "value" IS NULL;
+
+-- Modify "grades" table
+ALTER TABLE "public"."grades" ADD CONSTRAINT "grades_check" CHECK ((status = 'graded'::text) = (value IS NOT NULL));
@@ -0,0 +1,2 @@
+-- Modify "grades" table
+ALTER TABLE "public"."grades" ADD COLUMN "version" bigint NOT NULL DEFAULT 1;
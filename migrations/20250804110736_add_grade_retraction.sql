@@ -0,0 +1,2 @@
+-- Modify "grades" table
+ALTER TABLE "public"."grades" DROP CONSTRAINT "grades_status_check", ADD CONSTRAINT "grades_status_check" CHECK (status = ANY (ARRAY['graded'::text, 'ungraded'::text, 'excused'::text, 'absent'::text, 'retracted'::text]));
@@ -0,0 +1,18 @@
+-- Modify "grade_events" table
+ALTER TABLE "public"."grade_events" ADD CONSTRAINT "grade_events_status_check" CHECK (status = ANY (ARRAY['graded'::text, 'ungraded'::text, 'excused'::text, 'absent'::text, 'retracted'::text])), ADD COLUMN "status" text NOT NULL DEFAULT 'graded';
+
+UPDATE "public"."grade_events" SET "status" = CASE WHEN "reason" IS NOT NULL THEN 'retracted' ELSE 'ungraded' END WHERE "new_value" IS NULL;
+
+UPDATE "public"."grade_events" SET "status" = "grades"."status"
+FROM "public"."grades"
+WHERE "grade_events"."new_value" IS NULL
+  AND "grades"."user_id" = "grade_events"."student_id"
+  AND "grades"."subject_id" = "grade_events"."subject_id"
+  AND "grades"."term_id" = "grade_events"."term_id"
+  AND "grade_events"."id" = (
+    SELECT max("latest"."id")
+    FROM "public"."grade_events" AS "latest"
+    WHERE "latest"."student_id" = "grade_events"."student_id"
+      AND "latest"."subject_id" = "grade_events"."subject_id"
+      AND "latest"."term_id" = "grade_events"."term_id"
+  );
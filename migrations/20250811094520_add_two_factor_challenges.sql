@@ -0,0 +1,7 @@
+-- Create "two_factor_challenges" table
+CREATE TABLE "public"."two_factor_challenges" (
+  "id" uuid NOT NULL,
+  "attempts" integer NOT NULL DEFAULT 1,
+  "expires_at" timestamptz NOT NULL,
+  PRIMARY KEY ("id")
+);
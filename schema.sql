@@ -3,22 +3,166 @@ create table users (
     email text not null unique,
     name text not null,
     password_hash text not null,
-    role text not null check (role in ('teacher', 'student'))
+    role text not null check (role in ('teacher', 'student', 'admin')),
+    verified_at timestamptz,
+    disabled_at timestamptz
 );
 
 create table subjects (
     id text primary key,
-    title text not null
+    title text not null,
+    archived_at timestamptz,
+    grading_scale text not null default 'percent' check (grading_scale in ('percent', 'twelve-point', 'letter', 'gpa'))
 );
 
+create table terms (
+    id bigserial primary key,
+    name text not null unique,
+    starts_on date not null,
+    ends_on date not null,
+    is_active boolean not null default false,
+    check (ends_on >= starts_on)
+);
+
+create unique index terms_is_active_idx on terms (is_active) where is_active;
+
 create table grades (
     user_id bigint not null references users (id) on delete cascade,
-    subject_id text not null references subjects (id) on delete cascade,
+    subject_id text not null references subjects (id) on delete restrict,
+    term_id bigint not null references terms (id) on delete restrict,
     value numeric(5, 2),
-    primary key (user_id, subject_id)
+    scaled_value text,
+    gpa numeric(3, 2),
+    status text not null default 'graded' check (status in ('graded', 'ungraded', 'excused', 'absent', 'retracted')),
+    version bigint not null default 1,
+    primary key (user_id, subject_id, term_id),
+    check ((status = 'graded') = (value is not null))
 );
 
+create index grades_term_id_idx on grades (term_id);
+
 create table keys (
     value uuid primary key,
     user_id bigint not null references users (id) on delete cascade
 );
+
+create table refresh_tokens (
+    hash text primary key,
+    user_id bigint not null references users (id) on delete cascade,
+    session_id uuid not null,
+    expires_at timestamptz not null,
+    used_at timestamptz,
+    revoked_at timestamptz
+);
+
+create index refresh_tokens_session_id_idx on refresh_tokens (session_id);
+
+create table revoked_tokens (
+    id uuid primary key,
+    expires_at timestamptz not null
+);
+
+create table password_reset_tokens (
+    hash text primary key,
+    user_id bigint not null references users (id) on delete cascade,
+    expires_at timestamptz not null,
+    used_at timestamptz
+);
+
+create table totp_credentials (
+    user_id bigint primary key references users (id) on delete cascade,
+    secret text not null,
+    enabled_at timestamptz,
+    last_used_step bigint
+);
+
+create table recovery_codes (
+    id bigserial primary key,
+    user_id bigint not null references users (id) on delete cascade,
+    hash text not null,
+    used_at timestamptz
+);
+
+create index recovery_codes_user_id_idx on recovery_codes (user_id);
+
+create table two_factor_challenges (
+    id uuid primary key,
+    attempts integer not null default 1,
+    expires_at timestamptz not null
+);
+
+create table login_attempts (
+    id bigserial primary key,
+    email text not null,
+    ip text not null,
+    outcome text not null check (outcome in ('succeeded', 'failed', 'throttled')),
+    attempted_at timestamptz not null default now()
+);
+
+create index login_attempts_email_attempted_at_idx on login_attempts (email, attempted_at);
+
+create index login_attempts_ip_attempted_at_idx on login_attempts (ip, attempted_at);
+
+create table invites (
+    hash text primary key,
+    email text not null,
+    invited_by bigint references users (id) on delete set null,
+    expires_at timestamptz not null,
+    used_at timestamptz
+);
+
+create table classes (
+    id text primary key,
+    name text not null
+);
+
+create table class_students (
+    class_id text not null references classes (id) on delete cascade,
+    user_id bigint not null references users (id) on delete cascade,
+    primary key (class_id, user_id)
+);
+
+create index class_students_user_id_idx on class_students (user_id);
+
+create table class_subjects (
+    class_id text not null references classes (id) on delete cascade,
+    subject_id text not null references subjects (id) on delete cascade,
+    primary key (class_id, subject_id)
+);
+
+create index class_subjects_subject_id_idx on class_subjects (subject_id);
+
+create table grade_events (
+    id bigserial primary key,
+    student_id bigint not null references users (id) on delete cascade,
+    subject_id text not null references subjects (id) on delete cascade,
+    term_id bigint not null references terms (id) on delete cascade,
+    actor_id bigint references users (id) on delete set null,
+    old_value numeric(5, 2),
+    new_value numeric(5, 2),
+    status text not null default 'graded' check (status in ('graded', 'ungraded', 'excused', 'absent', 'retracted')),
+    reason text,
+    created_at timestamptz not null default now()
+);
+
+create index grade_events_student_id_subject_id_idx on grade_events (student_id, subject_id);
+
+create table assessments (
+    id bigserial primary key,
+    subject_id text not null references subjects (id) on delete cascade,
+    term_id bigint not null references terms (id) on delete cascade,
+    title text not null,
+    kind text not null check (kind in ('test', 'homework', 'exam')),
+    weight numeric(5, 2) not null check (weight > 0)
+);
+
+create index assessments_subject_id_term_id_idx on assessments (subject_id, term_id);
+
+create table assessment_scores (
+    assessment_id bigint not null references assessments (id) on delete cascade,
+    user_id bigint not null references users (id) on delete cascade,
+    value numeric(5, 2) not null,
+    primary key (assessment_id, user_id)
+);
+
+create index assessment_scores_user_id_idx on assessment_scores (user_id);
@@ -0,0 +1,225 @@
+use crate::{
+    app::AppError,
+    domain::{
+        admin::*,
+        auth::{DecodeUserId, EncodeUserId, RevokeUserSessions},
+        role::Role,
+        user_id::{DbUserId, UserId},
+    },
+    services::database::admin::{
+        get_managed_users, remove_user, update_user_disabled, update_user_role,
+    },
+};
+
+use super::{validation::ValidationErrors, AppContext};
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn list_users(
+    ctx: &AppContext,
+    search: Option<String>,
+) -> crate::Result<Vec<ManagedUser>> {
+    let search = search
+        .map(|search| search.trim().to_owned())
+        .filter(|search| !search.is_empty());
+    ctx.list_users(search).await
+}
+
+async fn list_users_with(
+    storage: &impl GetManagedUsers,
+    encoder: &impl EncodeUserId,
+    search: Option<String>,
+) -> crate::Result<Vec<ManagedUser>> {
+    storage
+        .get_managed_users(search)
+        .await?
+        .into_iter()
+        .map(|user| {
+            let DbManagedUser {
+                id,
+                email,
+                name,
+                role,
+                is_verified,
+                is_disabled,
+            } = user;
+            let id = encoder.encode_user_id(id)?;
+            Ok(ManagedUser {
+                id,
+                email,
+                name,
+                role,
+                is_verified,
+                is_disabled,
+            })
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn change_user_role<T>(
+    ctx: &AppContext,
+    admin_id: UserId,
+    form: T,
+) -> crate::Result<(), AppError<ManageUserError>>
+where
+    T: core::fmt::Debug + TryInto<RoleChange, Error = ValidationErrors>,
+{
+    let change = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.change_user_role(admin_id, change)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+async fn change_user_role_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl UpdateUserRole,
+    admin_id: UserId,
+    change: RoleChange,
+) -> crate::Result<(), ManageUserError> {
+    let user_id = decode_target(decoder, admin_id, change.user)?;
+    storage
+        .update_user_role(user_id, change.role)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn set_user_disabled(
+    ctx: &AppContext,
+    admin_id: UserId,
+    user_id: String,
+    is_disabled: bool,
+) -> crate::Result<(), ManageUserError> {
+    let user_id = UserId::try_from(user_id)
+        .map_err(|_| crate::Error::expected(ManageUserError::UnknownUser))?;
+    ctx.set_user_disabled(admin_id, user_id, is_disabled).await
+}
+
+async fn set_user_disabled_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl UpdateUserDisabled,
+    sessions: &impl RevokeUserSessions,
+    admin_id: UserId,
+    user_id: UserId,
+    is_disabled: bool,
+) -> crate::Result<(), ManageUserError> {
+    let user_id = decode_target(decoder, admin_id, user_id)?;
+    storage
+        .update_user_disabled(user_id, is_disabled)
+        .await
+        .map_err(crate::Error::cast)?;
+    if is_disabled {
+        sessions
+            .revoke_user_sessions(user_id)
+            .await
+            .map_err(crate::Error::from_internal)?;
+    }
+    Ok(())
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn delete_user(
+    ctx: &AppContext,
+    admin_id: UserId,
+    user_id: String,
+) -> crate::Result<(), ManageUserError> {
+    let user_id = UserId::try_from(user_id)
+        .map_err(|_| crate::Error::expected(ManageUserError::UnknownUser))?;
+    ctx.delete_user(admin_id, user_id).await
+}
+
+async fn delete_user_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl RemoveUser,
+    admin_id: UserId,
+    user_id: UserId,
+) -> crate::Result<(), ManageUserError> {
+    let user_id = decode_target(decoder, admin_id, user_id)?;
+    storage
+        .remove_user(user_id)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+fn decode_target(
+    decoder: &impl DecodeUserId,
+    admin_id: UserId,
+    user_id: UserId,
+) -> crate::Result<DbUserId, ManageUserError> {
+    if admin_id == user_id {
+        return Err(crate::Error::expected(ManageUserError::SelfModification));
+    }
+    decoder.decode_user_id(user_id).map_err(crate::Error::cast)
+}
+
+impl ListUsers for AppContext {
+    async fn list_users(&self, search: Option<String>) -> crate::Result<Vec<ManagedUser>> {
+        list_users_with(self, self, search).await
+    }
+}
+
+impl ChangeUserRole for AppContext {
+    async fn change_user_role(
+        &self,
+        admin_id: UserId,
+        change: RoleChange,
+    ) -> crate::Result<(), ManageUserError> {
+        change_user_role_with(self, self, admin_id, change).await
+    }
+}
+
+impl SetUserDisabled for AppContext {
+    async fn set_user_disabled(
+        &self,
+        admin_id: UserId,
+        user_id: UserId,
+        is_disabled: bool,
+    ) -> crate::Result<(), ManageUserError> {
+        set_user_disabled_with(self, self, self, admin_id, user_id, is_disabled).await
+    }
+}
+
+impl DeleteUser for AppContext {
+    async fn delete_user(
+        &self,
+        admin_id: UserId,
+        user_id: UserId,
+    ) -> crate::Result<(), ManageUserError> {
+        delete_user_with(self, self, admin_id, user_id).await
+    }
+}
+
+impl GetManagedUsers for AppContext {
+    async fn get_managed_users(&self, search: Option<String>) -> crate::Result<Vec<DbManagedUser>> {
+        get_managed_users(&self.database, search).await
+    }
+}
+
+impl UpdateUserRole for AppContext {
+    async fn update_user_role(
+        &self,
+        user_id: DbUserId,
+        role: Role,
+    ) -> crate::Result<(), UpdateUserError> {
+        update_user_role(&self.database, user_id, role).await
+    }
+}
+
+impl UpdateUserDisabled for AppContext {
+    async fn update_user_disabled(
+        &self,
+        user_id: DbUserId,
+        is_disabled: bool,
+    ) -> crate::Result<(), UpdateUserError> {
+        update_user_disabled(&self.database, user_id, is_disabled).await
+    }
+}
+
+impl RemoveUser for AppContext {
+    async fn remove_user(&self, user_id: DbUserId) -> crate::Result<(), UpdateUserError> {
+        remove_user(&self.database, user_id).await
+    }
+}
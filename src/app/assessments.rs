@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+use crate::{
+    app::AppError,
+    domain::{
+        assessment_id::AssessmentId,
+        auth::{DecodeUserId, EncodeUserId, User},
+        grades::*,
+        role::Role,
+        subject_id::SubjectId,
+        user_id::{DbUserId, UserId},
+    },
+    services::database::assessments::{
+        get_assessments, get_db_assessment_scores, get_db_gradebook_students, get_db_subject,
+        insert_assessment, remove_assessment, save_db_assessment_score,
+    },
+};
+
+use super::{validation::ValidationErrors, AppContext};
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_gradebook(
+    ctx: &AppContext,
+    subject_id: String,
+) -> crate::Result<Gradebook, AssessmentError> {
+    ctx.get_gradebook(parse_subject_id(subject_id)?).await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn create_assessment<T>(
+    ctx: &AppContext,
+    user: User,
+    form: T,
+) -> crate::Result<(), AppError<AssessmentError>>
+where
+    T: core::fmt::Debug + TryInto<NewAssessment, Error = ValidationErrors>,
+{
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(AssessmentError::Forbidden.into()));
+    }
+    let assessment = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.create_assessment(assessment)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn delete_assessment(
+    ctx: &AppContext,
+    user: User,
+    subject_id: String,
+    assessment_id: String,
+) -> crate::Result<(), AssessmentError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(AssessmentError::Forbidden));
+    }
+    let subject_id = parse_subject_id(subject_id)?;
+    let assessment_id = parse_assessment_id(assessment_id)?;
+    ctx.delete_assessment(user.id, subject_id, assessment_id)
+        .await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_assessment_score<T>(
+    ctx: &AppContext,
+    user: User,
+    form: T,
+) -> crate::Result<(), AppError<AssessmentError>>
+where
+    T: core::fmt::Debug + TryInto<AssessmentScore, Error = ValidationErrors>,
+{
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(AssessmentError::Forbidden.into()));
+    }
+    let score = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.save_assessment_score(user.id, score)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+fn parse_subject_id(subject_id: String) -> crate::Result<SubjectId, AssessmentError> {
+    SubjectId::new(subject_id).map_err(|_| crate::Error::expected(AssessmentError::NotFound))
+}
+
+fn parse_assessment_id(assessment_id: String) -> crate::Result<AssessmentId, AssessmentError> {
+    assessment_id
+        .parse()
+        .map_err(|_| crate::Error::expected(AssessmentError::NotFound))
+}
+
+async fn get_gradebook_with(
+    subjects: &impl GetDbSubject,
+    assessments: &impl GetAssessments,
+    students: &impl GetDbGradebookStudents,
+    scores: &impl GetDbAssessmentScores,
+    encoder: &impl EncodeUserId,
+    subject_id: SubjectId,
+) -> crate::Result<Gradebook, AssessmentError> {
+    let subject = subjects.get_db_subject(&subject_id).await?;
+    let assessments = assessments
+        .get_assessments(&subject_id)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    let scores = scores
+        .get_db_assessment_scores(&subject_id)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|score| ((score.student_id, score.assessment_id), score.score))
+        .collect::<HashMap<_, _>>();
+    let students = students
+        .get_db_gradebook_students(&subject_id)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|student| {
+            let DbGradebookStudent {
+                id,
+                name,
+                final_grade,
+            } = student;
+            let scores = assessments
+                .iter()
+                .map(|assessment| scores.get(&(id, assessment.id)).copied())
+                .collect();
+            let id = encoder.encode_user_id(id)?;
+            Ok(GradebookStudent {
+                id,
+                name,
+                scores,
+                final_grade,
+            })
+        })
+        .collect::<crate::Result<Vec<_>>>()
+        .map_err(crate::Error::from_internal)?;
+    Ok(Gradebook {
+        subject,
+        assessments,
+        students,
+    })
+}
+
+async fn delete_assessment_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl RemoveAssessment,
+    actor: UserId,
+    subject_id: SubjectId,
+    assessment_id: AssessmentId,
+) -> crate::Result<(), AssessmentError> {
+    let actor = decode_user(decoder, actor)?;
+    storage
+        .remove_assessment(actor, subject_id, assessment_id)
+        .await
+}
+
+async fn save_assessment_score_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl SaveDbAssessmentScore,
+    actor: UserId,
+    score: AssessmentScore,
+) -> crate::Result<(), AssessmentError> {
+    let AssessmentScore {
+        subject_id,
+        assessment_id,
+        student_id,
+        score,
+    } = score;
+    let actor = decode_user(decoder, actor)?;
+    let student_id = decode_user(decoder, student_id)?;
+    let score = DbAssessmentScore {
+        subject_id,
+        assessment_id,
+        student_id,
+        score,
+    };
+    storage.save_db_assessment_score(actor, score).await
+}
+
+fn decode_user(
+    decoder: &impl DecodeUserId,
+    user_id: UserId,
+) -> crate::Result<DbUserId, AssessmentError> {
+    decoder
+        .decode_user_id(user_id)
+        .map_err(|_| crate::Error::expected(AssessmentError::NotFound))
+}
+
+impl GetGradebook for AppContext {
+    async fn get_gradebook(
+        &self,
+        subject_id: SubjectId,
+    ) -> crate::Result<Gradebook, AssessmentError> {
+        get_gradebook_with(self, self, self, self, self, subject_id).await
+    }
+}
+
+impl CreateAssessment for AppContext {
+    async fn create_assessment(
+        &self,
+        assessment: NewAssessment,
+    ) -> crate::Result<(), AssessmentError> {
+        insert_assessment(&self.database, assessment).await
+    }
+}
+
+impl DeleteAssessment for AppContext {
+    async fn delete_assessment(
+        &self,
+        actor: UserId,
+        subject_id: SubjectId,
+        assessment_id: AssessmentId,
+    ) -> crate::Result<(), AssessmentError> {
+        delete_assessment_with(self, self, actor, subject_id, assessment_id).await
+    }
+}
+
+impl SaveAssessmentScore for AppContext {
+    async fn save_assessment_score(
+        &self,
+        actor: UserId,
+        score: AssessmentScore,
+    ) -> crate::Result<(), AssessmentError> {
+        save_assessment_score_with(self, self, actor, score).await
+    }
+}
+
+impl GetDbSubject for AppContext {
+    async fn get_db_subject(
+        &self,
+        subject_id: &SubjectId,
+    ) -> crate::Result<Subject, AssessmentError> {
+        get_db_subject(&self.database, subject_id).await
+    }
+}
+
+impl GetAssessments for AppContext {
+    async fn get_assessments(&self, subject_id: &SubjectId) -> crate::Result<Vec<Assessment>> {
+        get_assessments(&self.database, subject_id).await
+    }
+}
+
+impl GetDbGradebookStudents for AppContext {
+    async fn get_db_gradebook_students(
+        &self,
+        subject_id: &SubjectId,
+    ) -> crate::Result<Vec<DbGradebookStudent>> {
+        get_db_gradebook_students(&self.database, subject_id).await
+    }
+}
+
+impl GetDbAssessmentScores for AppContext {
+    async fn get_db_assessment_scores(
+        &self,
+        subject_id: &SubjectId,
+    ) -> crate::Result<Vec<DbAssessmentScore>> {
+        get_db_assessment_scores(&self.database, subject_id).await
+    }
+}
+
+impl RemoveAssessment for AppContext {
+    async fn remove_assessment(
+        &self,
+        actor: DbUserId,
+        subject_id: SubjectId,
+        assessment_id: AssessmentId,
+    ) -> crate::Result<(), AssessmentError> {
+        remove_assessment(&self.database, actor, subject_id, assessment_id).await
+    }
+}
+
+impl SaveDbAssessmentScore for AppContext {
+    async fn save_db_assessment_score(
+        &self,
+        actor: DbUserId,
+        score: DbAssessmentScore,
+    ) -> crate::Result<(), AssessmentError> {
+        save_db_assessment_score(&self.database, actor, score).await
+    }
+}
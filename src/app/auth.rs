@@ -1,25 +1,53 @@
+use std::net::IpAddr;
+
+use serde::Serialize;
+use unic_langid::LanguageIdentifier;
+
 use crate::{
     app::AppError,
     domain::{
         auth::*,
-        email::MaybeEmail,
+        email::{Email, MaybeEmail},
+        mail::{Mail, MailContent, MailTemplate, RenderMail, SendMail},
+        name::Name,
         password::{MaybePassword, Password, PasswordHash},
         role::Role,
-        token::AuthToken,
+        throttle::{
+            CheckLoginThrottle, LoginAttempt, LoginAttemptOutcome, LoginSource, RecordLoginAttempt,
+        },
+        token::{
+            AuthToken, RefreshToken, RefreshTokenHash, ResetToken, ResetTokenHash, SessionId,
+            TokenId, VerificationToken,
+        },
+        two_factor::CheckTwoFactor,
         user_id::{DbUserId, UserId},
     },
+    error::ErrorKind,
     services::{
-        database::auth::{find_user, get_user, save_new_user},
+        database::auth::{
+            find_user, get_user, is_token_revoked, mark_email_verified, revoke_session,
+            revoke_token, revoke_user_sessions, save_new_user, save_refresh_token,
+            save_reset_token, update_password, use_refresh_token, use_reset_token,
+        },
         hasher::{hash_password, verify_password},
         id_encoder::{decode_user_id, encode_user_id},
-        token_issuer::{issue_token, parse_token},
+        mailer::{render_mail, send_mail},
+        token_issuer::{
+            hash_refresh_token, hash_reset_token, issue_refresh_token, issue_reset_token,
+            issue_token, issue_verification_token, parse_token, parse_verification_token,
+            rotate_refresh_token,
+        },
     },
 };
 
 use super::{validation::ValidationErrors, AppContext};
 
 #[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
-pub async fn signup<T>(ctx: &AppContext, form: T) -> crate::Result<(), AppError<SignupError>>
+pub async fn signup<T>(
+    ctx: &AppContext,
+    form: T,
+    locale: LanguageIdentifier,
+) -> crate::Result<(), AppError<SignupError>>
 where
     T: core::fmt::Debug + TryInto<SignupData, Error = ValidationErrors>,
 {
@@ -27,13 +55,19 @@ where
         .try_into()
         .map_err(AppError::Validation)
         .map_err(crate::Error::expected)?;
-    ctx.signup(signup_data).await.map_err(crate::Error::cast)
+    ctx.signup(signup_data, locale)
+        .await
+        .map_err(crate::Error::cast)
 }
 
 async fn signup_with(
     hasher: &impl HashPassword,
     storage: &impl SaveNewUser,
+    issuer: &impl IssueVerificationToken,
+    renderer: &impl RenderMail,
+    mailer: &impl SendMail,
     signup_data: SignupData,
+    locale: LanguageIdentifier,
 ) -> crate::Result<(), SignupError> {
     let SignupData {
         email,
@@ -43,6 +77,8 @@ async fn signup_with(
     let password_hash = hasher
         .hash_password(&password)
         .map_err(crate::Error::from_internal)?;
+    let mail = verification_mail(issuer, renderer, email.clone(), name.clone(), &locale)
+        .map_err(crate::Error::from_internal)?;
     let role = Role::Student;
     let new_user = NewUser {
         email,
@@ -53,11 +89,90 @@ async fn signup_with(
     storage
         .save_new_user(new_user)
         .await
+        .map_err(crate::Error::cast)?;
+    mailer
+        .send_mail(mail)
+        .await
+        .map_err(crate::Error::from_internal)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn resend_verification(
+    ctx: &AppContext,
+    email: String,
+    locale: LanguageIdentifier,
+) -> crate::Result<()> {
+    let request = VerificationRequest {
+        email: email.into(),
+        locale,
+    };
+    ctx.resend_verification(request).await
+}
+
+async fn resend_verification_with(
+    storage: &impl FindUser,
+    issuer: &impl IssueVerificationToken,
+    renderer: &impl RenderMail,
+    mailer: &impl SendMail,
+    request: VerificationRequest,
+) -> crate::Result<()> {
+    let VerificationRequest { email, locale } = request;
+    let user = match storage.find_user(&email).await {
+        Ok(user) => user,
+        Err(error) => match error.kind {
+            ErrorKind::Expected(FindUserError::NotFound) => {
+                tracing::info!("verification requested for unknown email");
+                return Ok(());
+            }
+            ErrorKind::Internal(error) => return Err(crate::Error::internal(error)),
+        },
+    };
+    if user.is_verified || user.is_disabled {
+        tracing::info!("verification requested for an active account");
+        return Ok(());
+    }
+    let mail = verification_mail(issuer, renderer, user.email, user.name, &locale)?;
+    mailer.send_mail(mail).await
+}
+
+fn verification_mail(
+    issuer: &impl IssueVerificationToken,
+    renderer: &impl RenderMail,
+    email: Email,
+    name: Name,
+    locale: &LanguageIdentifier,
+) -> crate::Result<Mail> {
+    let token = issuer.issue_verification_token(&email)?;
+    let data = EmailVerificationMail { name, token };
+    let content = renderer.render_mail(MailTemplate::EmailVerification, data, locale)?;
+    Ok(Mail { to: email, content })
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn verify_email(ctx: &AppContext, token: String) -> crate::Result<(), VerifyEmailError> {
+    ctx.verify_email(VerificationToken::new(token)).await
+}
+
+async fn verify_email_with(
+    parser: &impl ParseVerificationToken,
+    storage: &impl MarkEmailVerified,
+    token: VerificationToken,
+) -> crate::Result<(), VerifyEmailError> {
+    let email = parser
+        .parse_verification_token(token)
+        .map_err(crate::Error::cast)?;
+    storage
+        .mark_email_verified(&email)
+        .await
         .map_err(crate::Error::cast)
 }
 
 #[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
-pub async fn login<T>(ctx: &AppContext, form: T) -> crate::Result<AuthToken, AppError<LoginError>>
+pub async fn login<T>(
+    ctx: &AppContext,
+    form: T,
+    ip: IpAddr,
+) -> crate::Result<LoginOutcome, AppError<LoginError>>
 where
     T: core::fmt::Debug + TryInto<LoginData, Error = ValidationErrors>,
 {
@@ -65,28 +180,284 @@ where
         .try_into()
         .map_err(AppError::Validation)
         .map_err(crate::Error::expected)?;
-    ctx.login(data).await.map_err(crate::Error::cast)
+    ctx.login(LoginRequest { data, ip })
+        .await
+        .map_err(crate::Error::cast)
 }
 
 async fn login_with(
+    throttle: &impl CheckLoginThrottle,
+    audit: &impl RecordLoginAttempt,
     storage: &impl FindUser,
     verifier: &impl VerifyPassword,
+    two_factor: &impl CheckTwoFactor,
+    sessions: &impl StartSession,
+    request: LoginRequest,
+) -> crate::Result<LoginOutcome, LoginError> {
+    let LoginRequest {
+        data: login_data,
+        ip,
+    } = request;
+    let source = LoginSource {
+        email: login_data.email.clone(),
+        ip,
+    };
+    throttle
+        .check_login_throttle(&source)
+        .await
+        .map_err(crate::Error::cast)?;
+    let credentials = match storage.find_user(&login_data.email).await {
+        Ok(user) => verifier
+            .verify_password(login_data.password, user.password_hash.clone())
+            .map(|_| user)
+            .map_err(crate::Error::cast),
+        Err(error) => Err(error.cast()),
+    };
+    let user = match credentials {
+        Err(error) if matches!(error.kind, ErrorKind::Expected(_)) => {
+            audit
+                .record_login_attempt(LoginAttempt {
+                    source,
+                    outcome: LoginAttemptOutcome::Failed,
+                })
+                .await
+                .map_err(crate::Error::from_internal)?;
+            return Err(error);
+        }
+        credentials => credentials?,
+    };
+    if user.is_disabled {
+        return Err(crate::Error::expected(LoginError::AccountDisabled));
+    }
+    if !user.is_verified {
+        return Err(crate::Error::expected(LoginError::EmailNotVerified));
+    }
+    if let Some(prompt) = two_factor
+        .check_two_factor(&user, &login_data.email)
+        .await
+        .map_err(crate::Error::from_internal)?
+    {
+        return Ok(LoginOutcome::TwoFactorRequired(prompt));
+    }
+    audit
+        .record_login_attempt(LoginAttempt {
+            source,
+            outcome: LoginAttemptOutcome::Succeeded,
+        })
+        .await
+        .map_err(crate::Error::from_internal)?;
+    sessions
+        .start_session(user.id, SessionId::new())
+        .await
+        .map(LoginOutcome::Authenticated)
+        .map_err(crate::Error::from_internal)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn refresh(
+    ctx: &AppContext,
+    refresh_token: String,
+) -> crate::Result<AuthTokens, RefreshError> {
+    ctx.refresh(RefreshToken::new(refresh_token)).await
+}
+
+async fn refresh_with(
+    hasher: &impl HashRefreshToken,
+    rotator: &impl RotateRefreshToken,
+    storage: &impl UseRefreshToken,
+    revoker: &impl RevokeSession,
+    sessions: &impl ResumeSession,
+    token: RefreshToken,
+) -> crate::Result<AuthTokens, RefreshError> {
+    let hash = hasher.hash_refresh_token(&token);
+    let DbRefreshToken {
+        user_id,
+        session_id,
+    } = match storage.use_refresh_token(hash).await {
+        Ok(token) => token,
+        Err(error) => match error.kind {
+            ErrorKind::Expected(UseRefreshTokenError::NotFound) => {
+                return Err(crate::Error::expected(RefreshError::SessionExpired))
+            }
+            ErrorKind::Expected(UseRefreshTokenError::Reused(session_id)) => {
+                tracing::warn!(?session_id, "refresh token reuse detected");
+                revoker
+                    .revoke_session(session_id)
+                    .await
+                    .map_err(crate::Error::from_internal)?;
+                return Err(crate::Error::expected(RefreshError::SessionExpired));
+            }
+            ErrorKind::Internal(error) => return Err(crate::Error::internal(error)),
+        },
+    };
+    let successor = rotator.rotate_refresh_token(&token);
+    sessions
+        .resume_session(user_id, session_id, successor)
+        .await
+        .map_err(crate::Error::from_internal)
+}
+
+async fn start_session_with(
+    refresh_issuer: &impl IssueRefreshToken,
+    sessions: &impl ResumeSession,
+    user_id: DbUserId,
+    session_id: SessionId,
+) -> crate::Result<AuthTokens> {
+    let refresh_token = refresh_issuer.issue_refresh_token()?;
+    sessions
+        .resume_session(user_id, session_id, refresh_token)
+        .await
+}
+
+async fn resume_session_with(
     encoder: &impl EncodeUserId,
     issuer: &impl IssueToken,
-    login_data: LoginData,
-) -> crate::Result<AuthToken, LoginError> {
-    let user = storage
-        .find_user(&login_data.email)
+    storage: &impl SaveRefreshToken,
+    user_id: DbUserId,
+    session_id: SessionId,
+    refresh_token: IssuedRefreshToken,
+) -> crate::Result<AuthTokens> {
+    let IssuedRefreshToken {
+        token: refresh_token,
+        hash,
+        ttl,
+    } = refresh_token;
+    let new_token = NewRefreshToken {
+        hash,
+        user_id,
+        session_id,
+        ttl,
+    };
+    storage.save_refresh_token(new_token).await?;
+    let user_id = encoder.encode_user_id(user_id)?;
+    let access_token = issuer.issue_token(user_id, session_id)?;
+    Ok(AuthTokens {
+        access_token,
+        refresh_token,
+    })
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn logout(ctx: &AppContext, token: AuthToken) -> crate::Result<(), AuthError> {
+    ctx.logout(token).await
+}
+
+async fn logout_with(
+    parser: &impl ParseToken,
+    token_revoker: &impl RevokeToken,
+    session_revoker: &impl RevokeSession,
+    token: AuthToken,
+) -> crate::Result<(), AuthError> {
+    let AuthClaims {
+        token_id,
+        session_id,
+        user_id: _,
+        expires_at,
+    } = parser.parse_token(token).map_err(crate::Error::cast)?;
+    token_revoker
+        .revoke_token(token_id, expires_at)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    session_revoker
+        .revoke_session(session_id)
+        .await
+        .map_err(crate::Error::from_internal)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn request_password_reset(
+    ctx: &AppContext,
+    email: String,
+    locale: LanguageIdentifier,
+) -> crate::Result<()> {
+    let request = PasswordResetRequest {
+        email: email.into(),
+        locale,
+    };
+    ctx.request_password_reset(request).await
+}
+
+async fn request_password_reset_with(
+    storage: &impl FindUser,
+    issuer: &impl IssueResetToken,
+    tokens: &impl SaveResetToken,
+    renderer: &impl RenderMail,
+    mailer: &impl SendMail,
+    request: PasswordResetRequest,
+) -> crate::Result<()> {
+    let PasswordResetRequest { email, locale } = request;
+    let user = match storage.find_user(&email).await {
+        Ok(user) => user,
+        Err(error) => match error.kind {
+            ErrorKind::Expected(FindUserError::NotFound) => {
+                tracing::info!("password reset requested for unknown email");
+                return Ok(());
+            }
+            ErrorKind::Internal(error) => return Err(crate::Error::internal(error)),
+        },
+    };
+    let IssuedResetToken { token, hash, ttl } = issuer.issue_reset_token()?;
+    let new_token = NewResetToken {
+        hash,
+        user_id: user.id,
+        ttl,
+    };
+    tokens.save_reset_token(new_token).await?;
+    let data = PasswordResetMail {
+        name: user.name,
+        token,
+        ttl_minutes: ttl.as_secs() / 60,
+    };
+    let content = renderer.render_mail(MailTemplate::PasswordReset, data, &locale)?;
+    let mail = Mail {
+        to: user.email,
+        content,
+    };
+    mailer.send_mail(mail).await
+}
+
+#[tracing::instrument(skip(ctx, token), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn reset_password<T>(
+    ctx: &AppContext,
+    token: String,
+    form: T,
+) -> crate::Result<(), AppError<ResetPasswordError>>
+where
+    T: core::fmt::Debug + TryInto<NewPassword, Error = ValidationErrors>,
+{
+    let NewPassword { password } = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.reset_password(ResetToken::new(token), password)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+async fn reset_password_with(
+    hasher: &impl HashPassword,
+    token_hasher: &impl HashResetToken,
+    storage: &impl UseResetToken,
+    passwords: &impl UpdatePassword,
+    sessions: &impl RevokeUserSessions,
+    token: ResetToken,
+    password: Password,
+) -> crate::Result<(), ResetPasswordError> {
+    let password_hash = hasher
+        .hash_password(&password)
+        .map_err(crate::Error::from_internal)?;
+    let hash = token_hasher.hash_reset_token(&token);
+    let user_id = storage
+        .use_reset_token(hash)
         .await
         .map_err(crate::Error::cast)?;
-    verifier
-        .verify_password(login_data.password, user.password_hash)
-        .map_err(crate::Error::cast)?;
-    let user_id = encoder
-        .encode_user_id(user.id)
+    passwords
+        .update_password(user_id, password_hash)
+        .await
         .map_err(crate::Error::from_internal)?;
-    issuer
-        .issue_token(user_id)
+    sessions
+        .revoke_user_sessions(user_id)
+        .await
         .map_err(crate::Error::from_internal)
 }
 
@@ -97,12 +468,22 @@ pub async fn authenticate(ctx: &AppContext, token: AuthToken) -> crate::Result<U
 
 async fn authenticate_with(
     parser: &impl ParseToken,
+    revocations: &impl IsTokenRevoked,
     encoder: &impl EncodeUserId,
     decoder: &impl DecodeUserId,
     storage: &impl GetUser,
     token: AuthToken,
 ) -> crate::Result<User, AuthError> {
-    let user_id = parser.parse_token(token).map_err(crate::Error::cast)?;
+    let AuthClaims {
+        token_id, user_id, ..
+    } = parser.parse_token(token).map_err(crate::Error::cast)?;
+    if revocations
+        .is_token_revoked(token_id)
+        .await
+        .map_err(crate::Error::from_internal)?
+    {
+        return Err(crate::Error::expected(AuthError::Unauthenticated));
+    }
     let db_id = decoder
         .decode_user_id(user_id)
         .map_err(crate::Error::cast)?;
@@ -112,7 +493,12 @@ async fn authenticate_with(
         name,
         password_hash: _,
         role,
+        is_verified: _,
+        is_disabled,
     } = storage.get_user(&db_id).await.map_err(crate::Error::cast)?;
+    if is_disabled {
+        return Err(crate::Error::expected(AuthError::Unauthenticated));
+    }
     let id = encoder
         .encode_user_id(id)
         .map_err(crate::Error::from_internal)?;
@@ -125,20 +511,85 @@ async fn authenticate_with(
 }
 
 impl Signup for AppContext {
-    async fn signup(&self, signup_data: SignupData) -> crate::Result<(), SignupError> {
-        signup_with(self, self, signup_data).await
+    async fn signup(
+        &self,
+        signup_data: SignupData,
+        locale: LanguageIdentifier,
+    ) -> crate::Result<(), SignupError> {
+        signup_with(self, self, self, self, self, signup_data, locale).await
+    }
+}
+
+impl VerifyEmail for AppContext {
+    async fn verify_email(&self, token: VerificationToken) -> crate::Result<(), VerifyEmailError> {
+        verify_email_with(self, self, token).await
+    }
+}
+
+impl ResendVerification for AppContext {
+    async fn resend_verification(&self, request: VerificationRequest) -> crate::Result<()> {
+        resend_verification_with(self, self, self, self, request).await
     }
 }
 
 impl Login for AppContext {
-    async fn login(&self, login_data: LoginData) -> crate::Result<AuthToken, LoginError> {
-        login_with(self, self, self, self, login_data).await
+    async fn login(&self, request: LoginRequest) -> crate::Result<LoginOutcome, LoginError> {
+        login_with(self, self, self, self, self, self, request).await
+    }
+}
+
+impl Refresh for AppContext {
+    async fn refresh(&self, token: RefreshToken) -> crate::Result<AuthTokens, RefreshError> {
+        refresh_with(self, self, self, self, self, token).await
+    }
+}
+
+impl Logout for AppContext {
+    async fn logout(&self, token: AuthToken) -> crate::Result<(), AuthError> {
+        logout_with(self, self, self, token).await
+    }
+}
+
+impl StartSession for AppContext {
+    async fn start_session(
+        &self,
+        user_id: DbUserId,
+        session_id: SessionId,
+    ) -> crate::Result<AuthTokens> {
+        start_session_with(self, self, user_id, session_id).await
+    }
+}
+
+impl ResumeSession for AppContext {
+    async fn resume_session(
+        &self,
+        user_id: DbUserId,
+        session_id: SessionId,
+        refresh_token: IssuedRefreshToken,
+    ) -> crate::Result<AuthTokens> {
+        resume_session_with(self, self, self, user_id, session_id, refresh_token).await
+    }
+}
+
+impl RequestPasswordReset for AppContext {
+    async fn request_password_reset(&self, request: PasswordResetRequest) -> crate::Result<()> {
+        request_password_reset_with(self, self, self, self, self, request).await
+    }
+}
+
+impl ResetPassword for AppContext {
+    async fn reset_password(
+        &self,
+        token: ResetToken,
+        password: Password,
+    ) -> crate::Result<(), ResetPasswordError> {
+        reset_password_with(self, self, self, self, self, token, password).await
     }
 }
 
 impl Authenticate for AppContext {
     async fn authenticate(&self, token: AuthToken) -> crate::Result<User, AuthError> {
-        authenticate_with(self, self, self, self, token).await
+        authenticate_with(self, self, self, self, self, token).await
     }
 }
 
@@ -177,17 +628,162 @@ impl VerifyPassword for AppContext {
 }
 
 impl IssueToken for AppContext {
-    fn issue_token(&self, user_id: UserId) -> crate::Result<AuthToken> {
-        issue_token(&self.token_issuer, user_id)
+    fn issue_token(&self, user_id: UserId, session_id: SessionId) -> crate::Result<AuthToken> {
+        issue_token(&self.token_issuer, user_id, session_id)
     }
 }
 
 impl ParseToken for AppContext {
-    fn parse_token(&self, token: AuthToken) -> crate::Result<UserId, ParseTokenError> {
+    fn parse_token(&self, token: AuthToken) -> crate::Result<AuthClaims, ParseTokenError> {
         parse_token(&self.token_issuer, token)
     }
 }
 
+impl IssueRefreshToken for AppContext {
+    fn issue_refresh_token(&self) -> crate::Result<IssuedRefreshToken> {
+        issue_refresh_token(&self.token_issuer)
+    }
+}
+
+impl RotateRefreshToken for AppContext {
+    fn rotate_refresh_token(&self, token: &RefreshToken) -> IssuedRefreshToken {
+        rotate_refresh_token(&self.token_issuer, token)
+    }
+}
+
+impl HashRefreshToken for AppContext {
+    fn hash_refresh_token(&self, token: &RefreshToken) -> RefreshTokenHash {
+        hash_refresh_token(token)
+    }
+}
+
+impl SaveRefreshToken for AppContext {
+    async fn save_refresh_token(&self, token: NewRefreshToken) -> crate::Result<()> {
+        save_refresh_token(&self.database, token).await
+    }
+}
+
+impl UseRefreshToken for AppContext {
+    async fn use_refresh_token(
+        &self,
+        hash: RefreshTokenHash,
+    ) -> crate::Result<DbRefreshToken, UseRefreshTokenError> {
+        use_refresh_token(&self.database, hash).await
+    }
+}
+
+impl RevokeSession for AppContext {
+    async fn revoke_session(&self, session_id: SessionId) -> crate::Result<()> {
+        revoke_session(&self.database, session_id).await
+    }
+}
+
+impl RevokeToken for AppContext {
+    async fn revoke_token(&self, token_id: TokenId, expires_at: u64) -> crate::Result<()> {
+        revoke_token(&self.database, token_id, expires_at).await
+    }
+}
+
+impl IsTokenRevoked for AppContext {
+    async fn is_token_revoked(&self, token_id: TokenId) -> crate::Result<bool> {
+        is_token_revoked(&self.database, token_id).await
+    }
+}
+
+impl IssueVerificationToken for AppContext {
+    fn issue_verification_token(&self, email: &Email) -> crate::Result<VerificationToken> {
+        issue_verification_token(&self.token_issuer, email)
+    }
+}
+
+impl ParseVerificationToken for AppContext {
+    fn parse_verification_token(
+        &self,
+        token: VerificationToken,
+    ) -> crate::Result<MaybeEmail, ParseTokenError> {
+        parse_verification_token(&self.token_issuer, token)
+    }
+}
+
+impl MarkEmailVerified for AppContext {
+    async fn mark_email_verified(
+        &self,
+        email: &MaybeEmail,
+    ) -> crate::Result<(), MarkEmailVerifiedError> {
+        mark_email_verified(&self.database, email).await
+    }
+}
+
+impl IssueResetToken for AppContext {
+    fn issue_reset_token(&self) -> crate::Result<IssuedResetToken> {
+        issue_reset_token(&self.token_issuer)
+    }
+}
+
+impl HashResetToken for AppContext {
+    fn hash_reset_token(&self, token: &ResetToken) -> ResetTokenHash {
+        hash_reset_token(token)
+    }
+}
+
+impl SaveResetToken for AppContext {
+    async fn save_reset_token(&self, token: NewResetToken) -> crate::Result<()> {
+        save_reset_token(&self.database, token).await
+    }
+}
+
+impl UseResetToken for AppContext {
+    async fn use_reset_token(
+        &self,
+        hash: ResetTokenHash,
+    ) -> crate::Result<DbUserId, UseResetTokenError> {
+        use_reset_token(&self.database, hash).await
+    }
+}
+
+impl UpdatePassword for AppContext {
+    async fn update_password(
+        &self,
+        user_id: DbUserId,
+        password_hash: PasswordHash,
+    ) -> crate::Result<()> {
+        update_password(&self.database, user_id, password_hash).await
+    }
+}
+
+impl RevokeUserSessions for AppContext {
+    async fn revoke_user_sessions(&self, user_id: DbUserId) -> crate::Result<()> {
+        revoke_user_sessions(&self.database, user_id).await
+    }
+}
+
+impl RenderMail for AppContext {
+    fn render_mail<T>(
+        &self,
+        template: MailTemplate,
+        data: T,
+        locale: &LanguageIdentifier,
+    ) -> crate::Result<MailContent>
+    where
+        T: Serialize,
+    {
+        render_mail(
+            &self.mailer,
+            self.templating_engine.as_ref(),
+            &self.localizer,
+            template,
+            data,
+            locale,
+        )
+    }
+}
+
+impl SendMail for AppContext {
+    async fn send_mail(&self, mail: Mail) -> crate::Result<()> {
+        send_mail(&self.mailer, mail).await
+    }
+}
+
 impl EncodeUserId for AppContext {
     fn encode_user_id(&self, raw_id: DbUserId) -> crate::Result<UserId> {
         encode_user_id(&self.id_encoder, raw_id)
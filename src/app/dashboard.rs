@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use rust_decimal::Decimal;
+
+use crate::{
+    domain::{
+        auth::{DecodeUserId, User},
+        dashboard::*,
+        enrollment::GetDbClassmates,
+        grades::GetDbGrades,
+        role::Role,
+        term_id::TermId,
+        user_id::{DbUserId, UserId},
+    },
+    error::ErrorKind,
+    services::database::dashboard::get_db_term_averages,
+};
+
+use super::{statistics, AppContext};
+
+const DASHBOARD_DIGITS: u32 = 2;
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_student_dashboard(
+    ctx: &AppContext,
+    user: User,
+    term: Option<TermId>,
+) -> crate::Result<StudentDashboard, DashboardError> {
+    if user.role != Role::Student {
+        return Err(crate::Error::expected(DashboardError::Forbidden));
+    }
+    ctx.get_student_dashboard(user.id, term)
+        .await
+        .map_err(crate::Error::from_internal)
+}
+
+async fn get_student_dashboard_with(
+    decoder: &impl DecodeUserId,
+    grade_storage: &impl GetDbGrades,
+    roster: &impl GetDbClassmates,
+    trend_storage: &impl GetDbTermAverages,
+    student_id: UserId,
+    term: Option<TermId>,
+) -> crate::Result<StudentDashboard> {
+    let student_id = match decoder.decode_user_id(student_id) {
+        Ok(id) => id,
+        Err(error) => match error.kind {
+            ErrorKind::Expected(_) => return Ok(StudentDashboard::default()),
+            ErrorKind::Internal(error) => return Err(crate::Error::internal(error)),
+        },
+    };
+    let classmates = roster
+        .get_db_classmates(student_id)
+        .await?
+        .into_iter()
+        .map(|enrollment| (enrollment.student_id, enrollment.subject_id))
+        .collect::<HashSet<_>>();
+    let grades = grade_storage.get_db_grades(None, term).await?;
+    let graded = statistics::graded_values(&grades)
+        .into_iter()
+        .filter(|(record, _)| {
+            record.student_id == student_id
+                || classmates.contains(&(record.student_id, record.subject_id.clone()))
+        })
+        .collect::<Vec<_>>();
+    let subject_grades = statistics::group_by_subject(&graded);
+    let mut comparisons = graded
+        .iter()
+        .filter(|(record, _)| record.student_id == student_id)
+        .filter_map(|(record, value)| {
+            let class_grades = subject_grades.get(&record.subject_id)?;
+            let moments = statistics::moments(class_grades)?;
+            let z_score = moments.z_score(*value);
+            let comparison = SubjectComparison {
+                subject_id: record.subject_id.clone(),
+                subject_title: record.subject_title.clone(),
+                grade: record.grade?,
+                scaled_value: record.scaled_value.clone(),
+                class_mean: moments.mean.round_dp(DASHBOARD_DIGITS),
+                difference: (value - moments.mean).round_dp(DASHBOARD_DIGITS),
+                z_score: z_score.round_dp(DASHBOARD_DIGITS),
+                class_size: class_grades.len(),
+            };
+            Some((comparison, *value, z_score))
+        })
+        .collect::<Vec<_>>();
+    comparisons.sort_by_key(|(comparison, _, _)| {
+        let title: String = comparison.subject_title.clone().into();
+        title.to_lowercase()
+    });
+    let own_grades = comparisons
+        .iter()
+        .map(|(_, value, _)| *value)
+        .collect::<Vec<_>>();
+    let z_scores = comparisons
+        .iter()
+        .map(|(_, _, z_score)| *z_score)
+        .collect::<Vec<_>>();
+    let class_grades = graded.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+    let trend = trend_storage.get_db_term_averages(student_id).await?;
+    let round = |value: Decimal| value.round_dp(DASHBOARD_DIGITS);
+    Ok(StudentDashboard {
+        average: statistics::mean(&own_grades).map(round),
+        class_average: statistics::mean(&class_grades).map(round),
+        average_z_score: statistics::mean(&z_scores).map(round),
+        subjects: comparisons
+            .into_iter()
+            .map(|(comparison, _, _)| comparison)
+            .collect(),
+        trend,
+    })
+}
+
+impl GetStudentDashboard for AppContext {
+    async fn get_student_dashboard(
+        &self,
+        student_id: UserId,
+        term: Option<TermId>,
+    ) -> crate::Result<StudentDashboard> {
+        get_student_dashboard_with(self, self, self, self, student_id, term).await
+    }
+}
+
+impl GetDbTermAverages for AppContext {
+    async fn get_db_term_averages(&self, student_id: DbUserId) -> crate::Result<Vec<TermAverage>> {
+        get_db_term_averages(&self.database, student_id).await
+    }
+}
@@ -0,0 +1,373 @@
+use crate::{
+    app::AppError,
+    domain::{
+        auth::{DecodeUserId, EncodeUserId, User},
+        class_id::ClassId,
+        enrollment::*,
+        role::Role,
+        subject_id::SubjectId,
+        term_id::TermId,
+        user_id::{DbUserId, UserId},
+    },
+    services::database::enrollment::{
+        get_class_subjects, get_classes, get_db_class, get_db_class_students, get_db_classmates,
+        get_db_enrollments, get_db_roster_grades, insert_class, remove_class, remove_class_student,
+        remove_class_subject, save_class_student, save_class_subject,
+    },
+};
+
+use super::{validation::ValidationErrors, AppContext};
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn list_classes(ctx: &AppContext) -> crate::Result<Vec<ClassSummary>> {
+    ctx.list_classes().await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn create_class<T>(
+    ctx: &AppContext,
+    user: User,
+    form: T,
+) -> crate::Result<(), AppError<EnrollmentError>>
+where
+    T: core::fmt::Debug + TryInto<Class, Error = ValidationErrors>,
+{
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(EnrollmentError::Forbidden.into()));
+    }
+    let class = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.create_class(class).await.map_err(crate::Error::cast)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn delete_class(
+    ctx: &AppContext,
+    user: User,
+    class_id: String,
+) -> crate::Result<(), EnrollmentError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(EnrollmentError::Forbidden));
+    }
+    ctx.delete_class(parse_class_id(class_id)?).await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_class_roster(
+    ctx: &AppContext,
+    class_id: String,
+) -> crate::Result<ClassRoster, EnrollmentError> {
+    ctx.get_class_roster(parse_class_id(class_id)?).await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn set_student_enrolled(
+    ctx: &AppContext,
+    user: User,
+    class_id: String,
+    student_id: String,
+    is_enrolled: bool,
+) -> crate::Result<(), EnrollmentError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(EnrollmentError::Forbidden));
+    }
+    let class_id = parse_class_id(class_id)?;
+    let student_id = UserId::try_from(student_id)
+        .map_err(|_| crate::Error::expected(EnrollmentError::NotFound))?;
+    match is_enrolled {
+        true => ctx.enroll_student(class_id, student_id).await,
+        false => ctx.unenroll_student(class_id, student_id).await,
+    }
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn set_subject_assigned(
+    ctx: &AppContext,
+    user: User,
+    class_id: String,
+    subject_id: String,
+    is_assigned: bool,
+) -> crate::Result<(), EnrollmentError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(EnrollmentError::Forbidden));
+    }
+    let class_id = parse_class_id(class_id)?;
+    let subject_id = SubjectId::new(subject_id)
+        .map_err(|_| crate::Error::expected(EnrollmentError::NotFound))?;
+    match is_assigned {
+        true => ctx.assign_subject(class_id, subject_id).await,
+        false => ctx.unassign_subject(class_id, subject_id).await,
+    }
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_roster_grades(
+    ctx: &AppContext,
+    subject: Option<String>,
+    term: Option<TermId>,
+) -> crate::Result<Vec<RosterGrade>> {
+    let subject = subject.and_then(|subject| SubjectId::new(subject).ok());
+    ctx.get_roster_grades(subject, term).await
+}
+
+fn parse_class_id(class_id: String) -> crate::Result<ClassId, EnrollmentError> {
+    ClassId::new(class_id).map_err(|_| crate::Error::expected(EnrollmentError::NotFound))
+}
+
+async fn get_class_roster_with(
+    classes: &impl GetDbClass,
+    students: &impl GetDbClassStudents,
+    subjects: &impl GetClassSubjects,
+    encoder: &impl EncodeUserId,
+    class_id: ClassId,
+) -> crate::Result<ClassRoster, EnrollmentError> {
+    let class = classes.get_db_class(&class_id).await?;
+    let students = students
+        .get_db_class_students(&class_id)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|student| {
+            let DbClassStudent {
+                id,
+                name,
+                email,
+                is_enrolled,
+            } = student;
+            let id = encoder.encode_user_id(id)?;
+            Ok(ClassStudent {
+                id,
+                name,
+                email,
+                is_enrolled,
+            })
+        })
+        .collect::<crate::Result<Vec<_>>>()
+        .map_err(crate::Error::from_internal)?;
+    let subjects = subjects
+        .get_class_subjects(&class_id)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    Ok(ClassRoster {
+        class,
+        students,
+        subjects,
+    })
+}
+
+async fn enroll_student_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl SaveClassStudent,
+    class_id: ClassId,
+    student_id: UserId,
+) -> crate::Result<(), EnrollmentError> {
+    let student_id = decode_student(decoder, student_id)?;
+    storage.save_class_student(class_id, student_id).await
+}
+
+async fn unenroll_student_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl RemoveClassStudent,
+    class_id: ClassId,
+    student_id: UserId,
+) -> crate::Result<(), EnrollmentError> {
+    let student_id = decode_student(decoder, student_id)?;
+    storage.remove_class_student(class_id, student_id).await
+}
+
+fn decode_student(
+    decoder: &impl DecodeUserId,
+    student_id: UserId,
+) -> crate::Result<DbUserId, EnrollmentError> {
+    decoder
+        .decode_user_id(student_id)
+        .map_err(|_| crate::Error::expected(EnrollmentError::NotFound))
+}
+
+async fn get_roster_grades_with(
+    storage: &impl GetDbRosterGrades,
+    encoder: &impl EncodeUserId,
+    subject: Option<SubjectId>,
+    term: Option<TermId>,
+) -> crate::Result<Vec<RosterGrade>> {
+    storage
+        .get_db_roster_grades(subject, term)
+        .await?
+        .into_iter()
+        .map(|record| encode_roster_grade(encoder, record))
+        .collect()
+}
+
+pub(super) fn encode_roster_grade(
+    encoder: &impl EncodeUserId,
+    record: DbRosterGrade,
+) -> crate::Result<RosterGrade> {
+    let DbRosterGrade {
+        student_id,
+        student_name,
+        grade,
+        scaled_value,
+        gpa,
+        status,
+        version,
+        subject_id,
+        subject_title,
+    } = record;
+    let student_id = encoder.encode_user_id(student_id)?;
+    Ok(RosterGrade {
+        student_id,
+        student_name,
+        grade,
+        scaled_value,
+        gpa,
+        status,
+        version,
+        subject_id,
+        subject_title,
+    })
+}
+
+impl ListClasses for AppContext {
+    async fn list_classes(&self) -> crate::Result<Vec<ClassSummary>> {
+        get_classes(&self.database).await
+    }
+}
+
+impl CreateClass for AppContext {
+    async fn create_class(&self, class: Class) -> crate::Result<(), EnrollmentError> {
+        insert_class(&self.database, class).await
+    }
+}
+
+impl DeleteClass for AppContext {
+    async fn delete_class(&self, class_id: ClassId) -> crate::Result<(), EnrollmentError> {
+        remove_class(&self.database, class_id).await
+    }
+}
+
+impl GetClassRoster for AppContext {
+    async fn get_class_roster(
+        &self,
+        class_id: ClassId,
+    ) -> crate::Result<ClassRoster, EnrollmentError> {
+        get_class_roster_with(self, self, self, self, class_id).await
+    }
+}
+
+impl EnrollStudent for AppContext {
+    async fn enroll_student(
+        &self,
+        class_id: ClassId,
+        student_id: UserId,
+    ) -> crate::Result<(), EnrollmentError> {
+        enroll_student_with(self, self, class_id, student_id).await
+    }
+}
+
+impl UnenrollStudent for AppContext {
+    async fn unenroll_student(
+        &self,
+        class_id: ClassId,
+        student_id: UserId,
+    ) -> crate::Result<(), EnrollmentError> {
+        unenroll_student_with(self, self, class_id, student_id).await
+    }
+}
+
+impl AssignSubject for AppContext {
+    async fn assign_subject(
+        &self,
+        class_id: ClassId,
+        subject_id: SubjectId,
+    ) -> crate::Result<(), EnrollmentError> {
+        save_class_subject(&self.database, class_id, subject_id).await
+    }
+}
+
+impl UnassignSubject for AppContext {
+    async fn unassign_subject(
+        &self,
+        class_id: ClassId,
+        subject_id: SubjectId,
+    ) -> crate::Result<(), EnrollmentError> {
+        remove_class_subject(&self.database, class_id, subject_id).await
+    }
+}
+
+impl GetRosterGrades for AppContext {
+    async fn get_roster_grades(
+        &self,
+        subject: Option<SubjectId>,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<RosterGrade>> {
+        get_roster_grades_with(self, self, subject, term).await
+    }
+}
+
+impl GetDbClass for AppContext {
+    async fn get_db_class(&self, class_id: &ClassId) -> crate::Result<Class, EnrollmentError> {
+        get_db_class(&self.database, class_id).await
+    }
+}
+
+impl GetDbClassStudents for AppContext {
+    async fn get_db_class_students(
+        &self,
+        class_id: &ClassId,
+    ) -> crate::Result<Vec<DbClassStudent>> {
+        get_db_class_students(&self.database, class_id).await
+    }
+}
+
+impl GetClassSubjects for AppContext {
+    async fn get_class_subjects(&self, class_id: &ClassId) -> crate::Result<Vec<ClassSubject>> {
+        get_class_subjects(&self.database, class_id).await
+    }
+}
+
+impl SaveClassStudent for AppContext {
+    async fn save_class_student(
+        &self,
+        class_id: ClassId,
+        student_id: DbUserId,
+    ) -> crate::Result<(), EnrollmentError> {
+        save_class_student(&self.database, class_id, student_id).await
+    }
+}
+
+impl RemoveClassStudent for AppContext {
+    async fn remove_class_student(
+        &self,
+        class_id: ClassId,
+        student_id: DbUserId,
+    ) -> crate::Result<(), EnrollmentError> {
+        remove_class_student(&self.database, class_id, student_id).await
+    }
+}
+
+impl GetDbRosterGrades for AppContext {
+    async fn get_db_roster_grades(
+        &self,
+        subject: Option<SubjectId>,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<DbRosterGrade>> {
+        get_db_roster_grades(&self.database, subject, term).await
+    }
+}
+
+impl GetDbEnrollments for AppContext {
+    async fn get_db_enrollments(
+        &self,
+        students: Vec<DbUserId>,
+    ) -> crate::Result<Vec<DbEnrollment>> {
+        get_db_enrollments(&self.database, students).await
+    }
+}
+
+impl GetDbClassmates for AppContext {
+    async fn get_db_classmates(&self, student: DbUserId) -> crate::Result<Vec<DbEnrollment>> {
+        get_db_classmates(&self.database, student).await
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    domain::{
+        auth::{DecodeUserId, User},
+        enrollment::GetDbEnrollments,
+        grade::RawGrade,
+        grade_entry::*,
+        grade_status::GradeStatus,
+        grade_version::OptionalGradeVersion,
+        grades::{DbGradeChange, GetGradingScales, UpdateGradeError},
+        grading_scale::GradingScale,
+        role::Role,
+        subject_id::SubjectId,
+        user_id::{DbUserId, UserId},
+    },
+    services::database::grade_entry::save_db_grades,
+};
+
+use super::{
+    localization::LocalizedError,
+    validation::{try_convert, ValidationErrors},
+    AppContext,
+};
+
+const STUDENT_FIELD: &str = "student";
+const SUBJECT_FIELD: &str = "subject";
+const GRADE_FIELD: &str = "grade";
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn enter_grades(
+    ctx: &AppContext,
+    user: User,
+    rows: Vec<GradeEntryRow>,
+) -> crate::Result<GradeEntryOutcome, GradeEntryError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(GradeEntryError::Forbidden));
+    }
+    let entry = GradeEntry {
+        actor: user.id,
+        rows,
+    };
+    ctx.enter_grades(entry).await
+}
+
+struct ParsedEntry {
+    student: UserId,
+    subject: SubjectId,
+    grade: RawGrade,
+    status: GradeStatus,
+    version: OptionalGradeVersion,
+}
+
+impl TryFrom<GradeEntryRow> for ParsedEntry {
+    type Error = ValidationErrors;
+
+    fn try_from(value: GradeEntryRow) -> Result<Self, Self::Error> {
+        try_convert!(GradeEntryRow value => ParsedEntry {
+            student,
+            subject,
+            grade,
+            status,
+            version,
+        })
+    }
+}
+
+async fn enter_grades_with(
+    decoder: &impl DecodeUserId,
+    scales: &impl GetGradingScales,
+    roster: &impl GetDbEnrollments,
+    storage: &impl SaveDbGrades,
+    entry: GradeEntry,
+) -> crate::Result<GradeEntryOutcome, GradeEntryError> {
+    let GradeEntry { actor, rows } = entry;
+    let actor = decoder
+        .decode_user_id(actor)
+        .map_err(|_| crate::Error::expected(GradeEntryError::Forbidden))?;
+    let scales = scales
+        .get_grading_scales()
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|subject| (subject.id, subject.grading_scale))
+        .collect::<HashMap<_, _>>();
+    let students = rows
+        .iter()
+        .filter_map(|row| UserId::try_from(row.student.clone()).ok())
+        .filter_map(|student| decoder.decode_user_id(student).ok())
+        .collect::<HashSet<_>>();
+    let enrollments = roster
+        .get_db_enrollments(students.into_iter().collect())
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|enrollment| (enrollment.student_id, enrollment.subject_id))
+        .collect::<HashSet<_>>();
+    let mut seen = HashSet::new();
+    let mut changes = Vec::with_capacity(rows.len());
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        if row.grade.trim().is_empty() && row.status == GradeStatus::Graded.as_str() {
+            continue;
+        }
+        let errors = match ParsedEntry::try_from(row.clone()) {
+            Ok(parsed) => match parse_entry(decoder, &scales, &enrollments, &mut seen, parsed) {
+                Ok(change) => {
+                    changes.push((results.len(), change));
+                    ValidationErrors::default()
+                }
+                Err(errors) => errors,
+            },
+            Err(errors) => errors,
+        };
+        results.push(GradeEntryResult { row, errors });
+    }
+    let (indices, changes): (Vec<_>, Vec<_>) = changes.into_iter().unzip();
+    let saved = match changes.is_empty() {
+        true => Vec::new(),
+        false => storage
+            .save_db_grades(actor, changes)
+            .await
+            .map_err(crate::Error::from_internal)?,
+    };
+    let mut saved_count = 0;
+    for (index, saved) in indices.into_iter().zip(saved) {
+        match saved {
+            SavedGrade::Saved => saved_count += 1,
+            SavedGrade::Unchanged => {}
+            SavedGrade::Conflict { status, current } => results[index].errors.add(
+                GRADE_FIELD,
+                vec![UpdateGradeError::Conflict { status, current }.into()],
+            ),
+        }
+    }
+    let invalid_count = results
+        .iter()
+        .filter(|result| !result.errors.is_empty())
+        .count();
+    Ok(GradeEntryOutcome {
+        rows: results,
+        saved_count,
+        invalid_count,
+    })
+}
+
+fn parse_entry(
+    decoder: &impl DecodeUserId,
+    scales: &HashMap<SubjectId, GradingScale>,
+    enrollments: &HashSet<(DbUserId, SubjectId)>,
+    seen: &mut HashSet<(DbUserId, SubjectId)>,
+    parsed: ParsedEntry,
+) -> Result<DbGradeChange, ValidationErrors> {
+    let ParsedEntry {
+        student,
+        subject,
+        grade,
+        status,
+        version,
+    } = parsed;
+    let mut errors = ValidationErrors::default();
+    let student = decoder
+        .decode_user_id(student)
+        .map_err(|_| errors.add(STUDENT_FIELD, vec![LocalizedError::new("INVALID_USER_ID")]))
+        .ok();
+    let grade = match (scales.get(&subject), status) {
+        (Some(grading_scale), GradeStatus::Graded) => grading_scale
+            .grade(grade.into())
+            .map(Some)
+            .map_err(|failure| errors.add(GRADE_FIELD, failure.errors))
+            .ok(),
+        (Some(_), _) => Some(None),
+        (None, _) => {
+            errors.add(SUBJECT_FIELD, vec![LocalizedError::new("UNKNOWN_SUBJECT")]);
+            None
+        }
+    };
+    let is_enrolled = |student| enrollments.contains(&(student, subject.clone()));
+    if scales.contains_key(&subject) && student.is_some_and(|student| !is_enrolled(student)) {
+        errors.add(STUDENT_FIELD, vec![LocalizedError::new("NOT_ENROLLED")]);
+    }
+    if student.is_some_and(|student| !seen.insert((student, subject.clone()))) {
+        errors.add(
+            SUBJECT_FIELD,
+            vec![LocalizedError::new("DUPLICATE_GRADE_ROW")],
+        );
+    }
+    match (student, grade) {
+        (Some(student), Some(grade)) if errors.is_empty() => Ok(DbGradeChange {
+            subject,
+            student,
+            grade,
+            status,
+            version: version.into(),
+            reason: None,
+        }),
+        _ => Err(errors),
+    }
+}
+
+impl EnterGrades for AppContext {
+    async fn enter_grades(
+        &self,
+        entry: GradeEntry,
+    ) -> crate::Result<GradeEntryOutcome, GradeEntryError> {
+        enter_grades_with(self, self, self, self, entry).await
+    }
+}
+
+impl SaveDbGrades for AppContext {
+    async fn save_db_grades(
+        &self,
+        actor: DbUserId,
+        changes: Vec<DbGradeChange>,
+    ) -> crate::Result<Vec<SavedGrade>> {
+        save_db_grades(&self.database, actor, changes).await
+    }
+}
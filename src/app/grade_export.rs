@@ -0,0 +1,129 @@
+use anyhow::Context;
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    domain::{
+        auth::User, grade_export::*, grades::GetGrades, role::Role, subject_id::SubjectId,
+        term_id::TermId,
+    },
+    services::spreadsheet::{write_csv, write_xlsx},
+};
+
+use super::AppContext;
+
+const COLUMNS: [&str; 6] = [
+    "student-id",
+    "student",
+    "subject-id",
+    "subject",
+    "grade",
+    "status",
+];
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn export_grades(
+    ctx: &AppContext,
+    user: User,
+    subject: Option<String>,
+    term: Option<String>,
+    format: Option<String>,
+    locale: LanguageIdentifier,
+) -> crate::Result<GradeExport, GradeExportError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(GradeExportError::Forbidden));
+    }
+    let format = match format {
+        Some(format) => format.parse().map_err(crate::Error::expected)?,
+        None => ExportFormat::Csv,
+    };
+    let subject = subject.and_then(|subject| SubjectId::new(subject).ok());
+    let term = term.and_then(|term| term.parse().ok());
+    ctx.export_grades(subject, term, format, locale)
+        .await
+        .map_err(crate::Error::from_internal)
+}
+
+#[tracing::instrument(skip(ctx, rows), err(Debug, level = "debug"))]
+pub fn grades_csv<T>(
+    ctx: &AppContext,
+    rows: Vec<T>,
+    locale: &LanguageIdentifier,
+) -> crate::Result<Vec<u8>>
+where
+    T: Into<GradeSheetRow>,
+{
+    let rows = rows.into_iter().map(Into::into).collect();
+    let sheet = grade_sheet_with(ctx, rows, locale)?;
+    ctx.write_spreadsheet(&sheet, ExportFormat::Csv)
+}
+
+async fn export_grades_with(
+    grades: &impl GetGrades,
+    localizer: &impl LocalizeText,
+    writer: &impl WriteSpreadsheet,
+    subject: Option<SubjectId>,
+    term: Option<TermId>,
+    format: ExportFormat,
+    locale: LanguageIdentifier,
+) -> crate::Result<GradeExport> {
+    let rows = grades
+        .get_grades(subject.clone(), term)
+        .await?
+        .into_iter()
+        .map(GradeSheetRow::from)
+        .collect();
+    let sheet = grade_sheet_with(localizer, rows, &locale)?;
+    let content = writer.write_spreadsheet(&sheet, format)?;
+    Ok(GradeExport {
+        subject,
+        format,
+        content,
+    })
+}
+
+fn grade_sheet_with(
+    localizer: &impl LocalizeText,
+    rows: Vec<GradeSheetRow>,
+    locale: &LanguageIdentifier,
+) -> crate::Result<GradeSheet> {
+    let columns = COLUMNS
+        .into_iter()
+        .map(|key| localizer.localize_text(locale, key))
+        .collect::<crate::Result<_>>()?;
+    Ok(GradeSheet { columns, rows })
+}
+
+impl ExportGrades for AppContext {
+    async fn export_grades(
+        &self,
+        subject: Option<SubjectId>,
+        term: Option<TermId>,
+        format: ExportFormat,
+        locale: LanguageIdentifier,
+    ) -> crate::Result<GradeExport> {
+        export_grades_with(self, self, self, subject, term, format, locale).await
+    }
+}
+
+impl LocalizeText for AppContext {
+    fn localize_text(&self, locale: &LanguageIdentifier, key: &str) -> crate::Result<String> {
+        let text = self
+            .localizer
+            .localize(locale, key)
+            .with_context(|| format!("localize text `{key}`"))?;
+        Ok(text)
+    }
+}
+
+impl WriteSpreadsheet for AppContext {
+    fn write_spreadsheet(
+        &self,
+        sheet: &GradeSheet,
+        format: ExportFormat,
+    ) -> crate::Result<Vec<u8>> {
+        match format {
+            ExportFormat::Csv => write_csv(sheet),
+            ExportFormat::Xlsx => write_xlsx(sheet),
+        }
+    }
+}
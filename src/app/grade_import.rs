@@ -0,0 +1,320 @@
+use std::collections::{HashMap, HashSet};
+
+use csv::{ReaderBuilder, Trim};
+use rust_decimal::Decimal;
+
+use crate::{
+    domain::{
+        auth::{DecodeUserId, User},
+        enrollment::{GetDbEnrollments, GetDbRosterGrades},
+        grade_entry::{SaveDbGrades, SavedGrade},
+        grade_import::*,
+        grade_status::GradeStatus,
+        grades::{DbGradeChange, GetGradingScales, UpdateGradeError},
+        role::Role,
+        subject_id::SubjectId,
+        user_id::{DbUserId, UserId},
+    },
+    services::database::grade_import::get_import_students,
+};
+
+use super::{localization::LocalizedError, validation::ValidationErrors, AppContext};
+
+const STUDENT_COLUMN: &str = "student";
+const SUBJECT_COLUMN: &str = "subject";
+const GRADE_COLUMN: &str = "grade";
+
+#[tracing::instrument(skip(ctx, content), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn import_grades(
+    ctx: &AppContext,
+    user: User,
+    content: String,
+    confirm: bool,
+) -> crate::Result<GradeImportOutcome, GradeImportError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(GradeImportError::Forbidden));
+    }
+    let rows = parse_rows(&content)?;
+    let import = GradeImport {
+        actor: user.id,
+        rows,
+        content,
+        confirm,
+    };
+    ctx.import_grades(import).await
+}
+
+fn parse_rows(content: &str) -> crate::Result<Vec<GradeImportRow>, GradeImportError> {
+    let mut reader = ReaderBuilder::new()
+        .flexible(true)
+        .trim(Trim::All)
+        .from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|_| crate::Error::expected(GradeImportError::InvalidFile))?
+        .clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+    };
+    let (Some(student), Some(subject), Some(grade)) = (
+        column(STUDENT_COLUMN),
+        column(SUBJECT_COLUMN),
+        column(GRADE_COLUMN),
+    ) else {
+        return Err(crate::Error::expected(GradeImportError::MissingColumns));
+    };
+    reader
+        .records()
+        .map(|record| {
+            let record =
+                record.map_err(|_| crate::Error::expected(GradeImportError::InvalidFile))?;
+            let line = record
+                .position()
+                .map(|position| position.line())
+                .unwrap_or_default();
+            let field = |index| record.get(index).unwrap_or_default().to_owned();
+            Ok(GradeImportRow {
+                line,
+                student: field(student),
+                subject: field(subject),
+                grade: field(grade),
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum StudentRef {
+    Email(String),
+    Id(DbUserId),
+}
+
+struct ParsedRow {
+    student: StudentRef,
+    subject: SubjectId,
+    grade: String,
+}
+
+async fn import_grades_with(
+    decoder: &impl DecodeUserId,
+    students: &impl GetImportStudents,
+    subjects: &impl GetGradingScales,
+    roster: &impl GetDbEnrollments,
+    grades: &impl GetDbRosterGrades,
+    storage: &impl SaveDbGrades,
+    import: GradeImport,
+) -> crate::Result<GradeImportOutcome, GradeImportError> {
+    let GradeImport {
+        actor,
+        rows,
+        content,
+        confirm,
+    } = import;
+    let actor = decoder
+        .decode_user_id(actor)
+        .map_err(|_| crate::Error::expected(GradeImportError::Forbidden))?;
+    let parsed = rows
+        .iter()
+        .map(|row| parse_row(decoder, row))
+        .collect::<Vec<_>>();
+    let (emails, ids) =
+        parsed
+            .iter()
+            .flatten()
+            .fold((Vec::new(), Vec::new()), |(mut emails, mut ids), row| {
+                match &row.student {
+                    StudentRef::Email(email) => emails.push(email.clone()),
+                    StudentRef::Id(id) => ids.push(*id),
+                }
+                (emails, ids)
+            });
+    let known_students = students
+        .get_import_students(emails, ids)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .flat_map(|student| {
+            let email: String = student.email.into();
+            let email = email.to_lowercase();
+            [
+                (StudentRef::Email(email), student.id),
+                (StudentRef::Id(student.id), student.id),
+            ]
+        })
+        .collect::<HashMap<_, _>>();
+    let student_ids = known_students.values().copied().collect::<HashSet<_>>();
+    let enrollments = roster
+        .get_db_enrollments(student_ids.into_iter().collect())
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|enrollment| (enrollment.student_id, enrollment.subject_id))
+        .collect::<HashSet<_>>();
+    let known_subjects = subjects
+        .get_grading_scales()
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|subject| (subject.id, subject.grading_scale))
+        .collect::<HashMap<_, _>>();
+    let current_grades = grades
+        .get_db_roster_grades(None, None)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|grade| ((grade.student_id, grade.subject_id.clone()), grade))
+        .collect::<HashMap<_, _>>();
+    let mut seen = HashSet::new();
+    let mut changes = Vec::with_capacity(rows.len());
+    let mut preview = Vec::with_capacity(rows.len());
+    for (row, parsed) in rows.into_iter().zip(parsed) {
+        let mut errors = ValidationErrors::default();
+        match parsed {
+            Ok(ParsedRow {
+                student,
+                subject,
+                grade,
+            }) => {
+                let student = known_students.get(&student).copied();
+                if student.is_none() {
+                    errors.add(STUDENT_COLUMN, vec![LocalizedError::new("UNKNOWN_STUDENT")]);
+                }
+                let grade = match known_subjects.get(&subject) {
+                    Some(grading_scale) => grading_scale
+                        .grade(grade)
+                        .map_err(|failure| errors.add(GRADE_COLUMN, failure.errors))
+                        .ok(),
+                    None => {
+                        errors.add(SUBJECT_COLUMN, vec![LocalizedError::new("UNKNOWN_SUBJECT")]);
+                        None
+                    }
+                };
+                if let Some(student) = student {
+                    let is_enrolled = enrollments.contains(&(student, subject.clone()));
+                    if known_subjects.contains_key(&subject) && !is_enrolled {
+                        errors.add(STUDENT_COLUMN, vec![LocalizedError::new("NOT_ENROLLED")]);
+                    }
+                    if !seen.insert((student, subject.clone())) {
+                        errors.add(
+                            SUBJECT_COLUMN,
+                            vec![LocalizedError::new("DUPLICATE_IMPORT_ROW")],
+                        );
+                    }
+                    if let (true, Some(grade)) = (errors.is_empty(), grade) {
+                        let current = current_grades.get(&(student, subject.clone()));
+                        let is_same = current.is_some_and(|current| {
+                            current.status == GradeStatus::Graded
+                                && current.grade.map(Decimal::from)
+                                    == Some(Decimal::from(grade.grade))
+                        });
+                        match current {
+                            Some(current)
+                                if current.status != GradeStatus::Ungraded && !is_same =>
+                            {
+                                let error = LocalizedError::new("GRADE_EXISTS")
+                                    .with_text("status", current.status.as_str());
+                                let error = match current.scaled_value.clone() {
+                                    Some(value) => error.with_text("current", value),
+                                    None => error,
+                                };
+                                errors.add(GRADE_COLUMN, vec![error]);
+                            }
+                            _ => changes.push((
+                                preview.len(),
+                                DbGradeChange {
+                                    subject,
+                                    student,
+                                    grade: Some(grade),
+                                    status: GradeStatus::Graded,
+                                    version: current.and_then(|current| current.version),
+                                    reason: None,
+                                },
+                            )),
+                        }
+                    }
+                }
+            }
+            Err(row_errors) => errors = row_errors,
+        }
+        preview.push(GradeImportPreviewRow { row, errors });
+    }
+    let is_valid = preview.iter().all(|row| row.errors.is_empty());
+    if confirm && is_valid && !changes.is_empty() {
+        let (indices, changes): (Vec<_>, Vec<_>) = changes.into_iter().unzip();
+        let saved = storage
+            .save_db_grades(actor, changes)
+            .await
+            .map_err(crate::Error::from_internal)?;
+        let mut count = 0;
+        for (index, saved) in indices.into_iter().zip(saved) {
+            match saved {
+                SavedGrade::Saved => count += 1,
+                SavedGrade::Unchanged => {}
+                SavedGrade::Conflict { status, current } => preview[index].errors.add(
+                    GRADE_COLUMN,
+                    vec![UpdateGradeError::Conflict { status, current }.into()],
+                ),
+            }
+        }
+        if preview.iter().all(|row| row.errors.is_empty()) {
+            return Ok(GradeImportOutcome::Applied { count });
+        }
+    }
+    let invalid_count = preview.iter().filter(|row| !row.errors.is_empty()).count();
+    Ok(GradeImportOutcome::Preview(GradeImportPreview {
+        valid_count: preview.len() - invalid_count,
+        invalid_count,
+        rows: preview,
+        content,
+    }))
+}
+
+fn parse_row(
+    decoder: &impl DecodeUserId,
+    row: &GradeImportRow,
+) -> Result<ParsedRow, ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+    let student = if row.student.contains('@') {
+        Some(StudentRef::Email(row.student.to_lowercase()))
+    } else {
+        UserId::try_from(row.student.clone())
+            .ok()
+            .and_then(|id| decoder.decode_user_id(id).ok())
+            .map(StudentRef::Id)
+    };
+    if student.is_none() {
+        errors.add(STUDENT_COLUMN, vec![LocalizedError::new("INVALID_USER_ID")]);
+    }
+    let subject = SubjectId::new(row.subject.clone())
+        .map_err(|failure| errors.add(SUBJECT_COLUMN, failure.errors))
+        .ok();
+    match (student, subject) {
+        (Some(student), Some(subject)) => Ok(ParsedRow {
+            student,
+            subject,
+            grade: row.grade.clone(),
+        }),
+        _ => Err(errors),
+    }
+}
+
+impl ImportGrades for AppContext {
+    async fn import_grades(
+        &self,
+        import: GradeImport,
+    ) -> crate::Result<GradeImportOutcome, GradeImportError> {
+        import_grades_with(self, self, self, self, self, self, import).await
+    }
+}
+
+impl GetImportStudents for AppContext {
+    async fn get_import_students(
+        &self,
+        emails: Vec<String>,
+        ids: Vec<DbUserId>,
+    ) -> crate::Result<Vec<ImportStudent>> {
+        get_import_students(&self.database, emails, ids).await
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use crate::domain::{
+    grade_stats::*,
+    grades::{DbGradeRecord, GetDbGrades},
+    term_id::TermId,
+};
+
+use super::{statistics, AppContext};
+
+const HISTOGRAM_BINS: usize = 10;
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_grade_statistics(
+    ctx: &AppContext,
+    term: Option<TermId>,
+) -> crate::Result<Vec<SubjectStatistics>> {
+    ctx.get_grade_statistics(term).await
+}
+
+async fn get_grade_statistics_with(
+    storage: &impl GetDbGrades,
+    term: Option<TermId>,
+) -> crate::Result<Vec<SubjectStatistics>> {
+    let subjects = storage
+        .get_db_grades(None, term)
+        .await?
+        .into_iter()
+        .filter_map(|record| record.grade.map(|grade| (Decimal::from(grade), record)))
+        .fold(
+            HashMap::<_, (DbGradeRecord, Vec<_>)>::new(),
+            |mut map, (grade, record)| {
+                map.entry(record.subject_id.clone())
+                    .or_insert_with(|| (record, Vec::new()))
+                    .1
+                    .push(grade);
+                map
+            },
+        );
+    let mut subjects = subjects
+        .into_values()
+        .filter_map(|(record, grades)| {
+            let summary = statistics::summarize(&grades)?;
+            let histogram =
+                statistics::histogram(&grades, Decimal::ZERO, Decimal::ONE_HUNDRED, HISTOGRAM_BINS);
+            Some(SubjectStatistics {
+                subject_id: record.subject_id,
+                subject_title: record.subject_title,
+                summary,
+                histogram,
+            })
+        })
+        .collect::<Vec<_>>();
+    subjects.sort_by_key(|subject| {
+        let title: String = subject.subject_title.clone().into();
+        title.to_lowercase()
+    });
+    Ok(subjects)
+}
+
+impl GetGradeStatistics for AppContext {
+    async fn get_grade_statistics(
+        &self,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<SubjectStatistics>> {
+        get_grade_statistics_with(self, term).await
+    }
+}
@@ -1,22 +1,42 @@
-use std::{convert::Infallible, str::FromStr};
+use std::{collections::HashMap, str::FromStr};
 
 use squint::Id;
 
 use crate::{
     domain::{
-        auth::{DecodeUserId, EncodeUserId},
-        grade::Grade,
+        auth::{DecodeUserId, EncodeUserId, User},
+        enrollment::{GetDbEnrollments, RosterGrade},
+        grade::RawGrade,
+        grade_sort::{GradeSort, SortOrder},
+        grade_status::GradeStatus,
+        grade_version::OptionalGradeVersion,
         grades::*,
+        grading_scale::GradingScale,
+        reason::{OptionalReason, Reason},
+        role::Role,
         subject_id::SubjectId,
+        term_id::TermId,
         user_id::{DbUserId, UserId},
     },
     error::ErrorKind,
-    services::database::grades::{
-        get_db_grade, get_db_grades, get_db_student_grades, get_subjects, update_db_grade,
+    services::database::{
+        assessments::get_db_student_assessments,
+        grades::{
+            get_db_grade, get_db_grade_events, get_db_grade_page, get_db_grades,
+            get_db_student_grades, get_grading_scales, get_managed_subjects,
+            get_subject_grading_scale, get_subjects, insert_subject, remove_db_grade,
+            remove_subject, update_db_grade, update_subject_archived, update_subject_grading_scale,
+            update_subject_title,
+        },
     },
 };
 
-use super::{validation::ValidationErrors, AppContext, AppError};
+use super::{
+    enrollment::encode_roster_grade, localization::LocalizedError, validation::ValidationErrors,
+    AppContext, AppError,
+};
+
+const PAGE_SIZE: usize = 50;
 
 impl GetSubjects for AppContext {
     #[tracing::instrument(skip(self), ret(level = "debug") err(Debug, level = "debug"))]
@@ -25,6 +45,103 @@ impl GetSubjects for AppContext {
     }
 }
 
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn list_subjects(ctx: &AppContext) -> crate::Result<Vec<ManagedSubject>> {
+    ctx.get_managed_subjects().await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn create_subject<T>(
+    ctx: &AppContext,
+    user: User,
+    form: T,
+) -> crate::Result<(), AppError<ManageSubjectError>>
+where
+    T: core::fmt::Debug + TryInto<NewSubject, Error = ValidationErrors>,
+{
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(ManageSubjectError::Forbidden.into()));
+    }
+    let subject = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.create_subject(subject)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn rename_subject<T>(
+    ctx: &AppContext,
+    user: User,
+    form: T,
+) -> crate::Result<(), AppError<ManageSubjectError>>
+where
+    T: core::fmt::Debug + TryInto<Subject, Error = ValidationErrors>,
+{
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(ManageSubjectError::Forbidden.into()));
+    }
+    let subject = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.rename_subject(subject)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn set_grading_scale<T>(
+    ctx: &AppContext,
+    user: User,
+    form: T,
+) -> crate::Result<(), AppError<ManageSubjectError>>
+where
+    T: core::fmt::Debug + TryInto<SubjectScale, Error = ValidationErrors>,
+{
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(ManageSubjectError::Forbidden.into()));
+    }
+    let subject = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.set_grading_scale(subject)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn set_subject_archived(
+    ctx: &AppContext,
+    user: User,
+    subject_id: String,
+    is_archived: bool,
+) -> crate::Result<(), ManageSubjectError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(ManageSubjectError::Forbidden));
+    }
+    let subject_id = SubjectId::new(subject_id)
+        .map_err(|_| crate::Error::expected(ManageSubjectError::NotFound))?;
+    ctx.set_subject_archived(subject_id, is_archived).await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn delete_subject(
+    ctx: &AppContext,
+    user: User,
+    subject_id: String,
+) -> crate::Result<(), ManageSubjectError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(ManageSubjectError::Forbidden));
+    }
+    let subject_id = SubjectId::new(subject_id)
+        .map_err(|_| crate::Error::expected(ManageSubjectError::NotFound))?;
+    ctx.delete_subject(subject_id).await
+}
+
 #[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
 pub async fn get_grade(
     ctx: &AppContext,
@@ -43,34 +160,194 @@ pub async fn get_grade(
 pub async fn get_grades(
     ctx: &AppContext,
     subject: Option<String>,
+    term: Option<String>,
 ) -> crate::Result<Vec<GradeRecord>> {
     let subject = subject.and_then(|subject| SubjectId::new(subject).ok());
-    ctx.get_grades(subject).await
+    let term = term.and_then(|term| term.parse().ok());
+    ctx.get_grades(subject, term).await
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GradePageRequest {
+    pub subject: Option<String>,
+    pub search: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub after_grade: Option<String>,
+    pub after_primary: Option<String>,
+    pub after_secondary: Option<String>,
+    pub after_student: Option<String>,
+    pub after_subject: Option<String>,
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_grade_page(
+    ctx: &AppContext,
+    term: Option<TermId>,
+    req: GradePageRequest,
+) -> crate::Result<GradePage> {
+    ctx.get_grade_page(grade_query(term, req)).await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_filtered_grades(
+    ctx: &AppContext,
+    term: Option<TermId>,
+    req: GradePageRequest,
+) -> crate::Result<Vec<RosterGrade>> {
+    let query = GradeQuery {
+        after: None,
+        ..grade_query(term, req)
+    };
+    ctx.get_filtered_grades(query).await
+}
+
+fn grade_cursor(
+    grade_key: Option<String>,
+    primary_key: Option<String>,
+    secondary_key: Option<String>,
+    student: Option<String>,
+    subject: Option<String>,
+) -> Option<GradeCursor> {
+    Some(GradeCursor {
+        grade_key: grade_key?.parse().ok()?,
+        primary_key: primary_key?,
+        secondary_key: secondary_key?,
+        student: UserId::try_from(student?).ok()?,
+        subject: SubjectId::new(subject?).ok()?,
+    })
+}
+
+fn grade_query(term: Option<TermId>, req: GradePageRequest) -> GradeQuery {
+    let after = grade_cursor(
+        req.after_grade,
+        req.after_primary,
+        req.after_secondary,
+        req.after_student,
+        req.after_subject,
+    );
+    GradeQuery {
+        subject: req.subject.and_then(|subject| SubjectId::new(subject).ok()),
+        term,
+        search: req
+            .search
+            .map(|search| search.trim().to_owned())
+            .filter(|search| !search.is_empty()),
+        sort: req
+            .sort
+            .and_then(|sort| GradeSort::try_from(sort).ok())
+            .unwrap_or_default(),
+        order: req
+            .order
+            .and_then(|order| SortOrder::try_from(order).ok())
+            .unwrap_or_default(),
+        after,
+    }
 }
 
 pub struct UpdateGradeRequest {
     pub subject: SubjectId,
     pub student: UserId,
-    pub grade: Grade,
+    pub grade: RawGrade,
+    pub status: GradeStatus,
+    pub version: OptionalGradeVersion,
+    pub reason: OptionalReason,
 }
 
 #[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
 pub async fn update_grade<T>(
     ctx: &AppContext,
+    user: User,
     req: T,
-) -> crate::Result<GradeRecord, AppError<Infallible>>
+) -> crate::Result<GradeRecord, AppError<UpdateGradeError>>
 where
     T: core::fmt::Debug + TryInto<UpdateGradeRequest, Error = ValidationErrors>,
 {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(UpdateGradeError::Forbidden.into()));
+    }
     let req = req
         .try_into()
         .map_err(AppError::Validation)
         .map_err(crate::Error::expected)?;
-    ctx.update_grade(req.subject, req.student, req.grade)
+    let grading_scale = ctx
+        .get_grading_scale(&req.subject)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    let grade = match (grading_scale, req.status) {
+        (Some(grading_scale), GradeStatus::Graded) => grading_scale
+            .grade(req.grade.into())
+            .map(Some)
+            .map_err(|failure| ("grade", failure.errors)),
+        (Some(_), _) => Ok(None),
+        (None, _) => Err(("subject", vec![LocalizedError::new("UNKNOWN_SUBJECT")])),
+    }
+    .map_err(|(field, field_errors)| {
+        let mut errors = ValidationErrors::default();
+        errors.add(field, field_errors);
+        crate::Error::expected(AppError::Validation(errors))
+    })?;
+    let change = GradeChange {
+        subject: req.subject,
+        student: req.student,
+        grade,
+        status: req.status,
+        version: req.version.into(),
+        reason: req.reason.into(),
+    };
+    ctx.update_grade(user.id, change)
         .await
         .map_err(crate::Error::cast)
 }
 
+pub struct RemoveGradeRequest {
+    pub subject: SubjectId,
+    pub student: UserId,
+    pub version: OptionalGradeVersion,
+    pub reason: Reason,
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn remove_grade<T>(
+    ctx: &AppContext,
+    user: User,
+    req: T,
+) -> crate::Result<GradeRecord, AppError<RemoveGradeError>>
+where
+    T: core::fmt::Debug + TryInto<RemoveGradeRequest, Error = ValidationErrors>,
+{
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(RemoveGradeError::Forbidden.into()));
+    }
+    let req = req
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    let removal = GradeRemoval {
+        subject: req.subject,
+        student: req.student,
+        version: req.version.into(),
+        reason: req.reason,
+    };
+    ctx.remove_grade(user.id, removal)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_grade_history(
+    ctx: &AppContext,
+    subject_id: String,
+    student_id: String,
+) -> crate::Result<GradeHistory, GetGradeError> {
+    let subject_id =
+        SubjectId::new(subject_id).map_err(|_| crate::Error::expected(GetGradeError::NotFound))?;
+    let student_id = Id::from_str(&student_id)
+        .map(UserId::new)
+        .map_err(|_| crate::Error::expected(GetGradeError::NotFound))?;
+    ctx.get_grade_history(subject_id, student_id).await
+}
+
 impl GetGrade for AppContext {
     async fn get_grade(
         &self,
@@ -82,26 +359,65 @@ impl GetGrade for AppContext {
 }
 
 impl GetGrades for AppContext {
-    async fn get_grades(&self, subject_id: Option<SubjectId>) -> crate::Result<Vec<GradeRecord>> {
-        get_grades_with(self, self, subject_id).await
+    async fn get_grades(
+        &self,
+        subject_id: Option<SubjectId>,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<GradeRecord>> {
+        get_grades_with(self, self, subject_id, term).await
+    }
+}
+
+impl GetGradePage for AppContext {
+    async fn get_grade_page(&self, query: GradeQuery) -> crate::Result<GradePage> {
+        get_grade_page_with(self, self, self, query).await
+    }
+}
+
+impl GetFilteredGrades for AppContext {
+    async fn get_filtered_grades(&self, query: GradeQuery) -> crate::Result<Vec<RosterGrade>> {
+        get_filtered_grades_with(self, self, self, query).await
     }
 }
 
 impl GetStudentGrades for AppContext {
     #[tracing::instrument(skip(self), ret(level = "debug") err(Debug, level = "debug"))]
-    async fn get_student_grades(&self, student_id: UserId) -> crate::Result<Vec<StudentGrade>> {
-        get_student_grades_with(self, self, student_id).await
+    async fn get_student_grades(
+        &self,
+        student_id: UserId,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<StudentGrade>> {
+        get_student_grades_with(self, self, self, student_id, term).await
     }
 }
 
 impl UpdateGrade for AppContext {
     async fn update_grade(
         &self,
-        subject: SubjectId,
-        student: UserId,
-        grade: Grade,
-    ) -> crate::Result<GradeRecord> {
-        update_grade_with(self, self, self, subject, student, grade).await
+        actor: UserId,
+        change: GradeChange,
+    ) -> crate::Result<GradeRecord, UpdateGradeError> {
+        update_grade_with(self, self, self, self, actor, change).await
+    }
+}
+
+impl RemoveGrade for AppContext {
+    async fn remove_grade(
+        &self,
+        actor: UserId,
+        removal: GradeRemoval,
+    ) -> crate::Result<GradeRecord, RemoveGradeError> {
+        remove_grade_with(self, self, self, actor, removal).await
+    }
+}
+
+impl GetGradeHistory for AppContext {
+    async fn get_grade_history(
+        &self,
+        subject_id: SubjectId,
+        student_id: UserId,
+    ) -> crate::Result<GradeHistory, GetGradeError> {
+        get_grade_history_with(self, self, self, subject_id, student_id).await
     }
 }
 
@@ -120,6 +436,10 @@ async fn get_grade_with(
         student_id,
         student_name,
         grade,
+        scaled_value,
+        gpa,
+        status,
+        version,
         subject_id,
         subject_title,
     } = grade;
@@ -130,6 +450,10 @@ async fn get_grade_with(
         student_id,
         student_name,
         grade,
+        scaled_value,
+        gpa,
+        status,
+        version,
         subject_id,
         subject_title,
     };
@@ -140,14 +464,19 @@ async fn get_grades_with(
     storage: &impl GetDbGrades,
     encoder: &impl EncodeUserId,
     subject_id: Option<SubjectId>,
+    term: Option<TermId>,
 ) -> crate::Result<Vec<GradeRecord>> {
-    let db_grades = storage.get_db_grades(subject_id).await?;
+    let db_grades = storage.get_db_grades(subject_id, term).await?;
     let mut grades = Vec::with_capacity(db_grades.len());
     for grade in db_grades {
         let DbGradeRecord {
             student_id,
             student_name,
             grade,
+            scaled_value,
+            gpa,
+            status,
+            version,
             subject_id,
             subject_title,
         } = grade;
@@ -156,6 +485,10 @@ async fn get_grades_with(
             student_id,
             student_name,
             grade,
+            scaled_value,
+            gpa,
+            status,
+            version,
             subject_id,
             subject_title,
         };
@@ -164,10 +497,94 @@ async fn get_grades_with(
     Ok(grades)
 }
 
+async fn get_grade_page_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl GetDbGradePage,
+    encoder: &impl EncodeUserId,
+    query: GradeQuery,
+) -> crate::Result<GradePage> {
+    let query = db_grade_query(decoder, query);
+    let mut records = storage
+        .get_db_grade_page(query, Some(PAGE_SIZE as i64 + 1))
+        .await?;
+    let has_more = records.len() > PAGE_SIZE;
+    records.truncate(PAGE_SIZE);
+    let next = match (has_more, records.last()) {
+        (true, Some(last)) => Some(GradeCursor {
+            grade_key: last.grade_key,
+            primary_key: last.primary_key.clone(),
+            secondary_key: last.secondary_key.clone(),
+            student: encoder.encode_user_id(last.grade.student_id)?,
+            subject: last.grade.subject_id.clone(),
+        }),
+        _ => None,
+    };
+    let grades = records
+        .into_iter()
+        .map(|record| encode_roster_grade(encoder, record.grade))
+        .collect::<crate::Result<Vec<_>>>()?;
+    Ok(GradePage { grades, next })
+}
+
+fn db_grade_query(decoder: &impl DecodeUserId, query: GradeQuery) -> DbGradeQuery {
+    let GradeQuery {
+        subject,
+        term,
+        search,
+        sort,
+        order,
+        after,
+    } = query;
+    let after = after.and_then(|cursor| {
+        let GradeCursor {
+            grade_key,
+            primary_key,
+            secondary_key,
+            student,
+            subject,
+        } = cursor;
+        decoder
+            .decode_user_id(student)
+            .ok()
+            .map(|student| DbGradeCursor {
+                grade_key,
+                primary_key,
+                secondary_key,
+                student,
+                subject,
+            })
+    });
+    DbGradeQuery {
+        subject,
+        term,
+        search,
+        sort,
+        order,
+        after,
+    }
+}
+
+async fn get_filtered_grades_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl GetDbGradePage,
+    encoder: &impl EncodeUserId,
+    query: GradeQuery,
+) -> crate::Result<Vec<RosterGrade>> {
+    let query = db_grade_query(decoder, query);
+    storage
+        .get_db_grade_page(query, None)
+        .await?
+        .into_iter()
+        .map(|record| encode_roster_grade(encoder, record.grade))
+        .collect()
+}
+
 async fn get_student_grades_with(
     decoder: &impl DecodeUserId,
     storage: &impl GetDbStudentGrades,
+    assessments: &impl GetDbStudentAssessments,
     student_id: UserId,
+    term: Option<TermId>,
 ) -> crate::Result<Vec<StudentGrade>> {
     let student_id = match decoder.decode_user_id(student_id) {
         Ok(id) => id,
@@ -176,28 +593,188 @@ async fn get_student_grades_with(
             ErrorKind::Internal(error) => return Err(crate::Error::internal(error)),
         },
     };
-    storage.get_db_student_grades(student_id).await
+    let mut assessments = assessments
+        .get_db_student_assessments(student_id, term)
+        .await?
+        .into_iter()
+        .fold(HashMap::<_, Vec<_>>::new(), |mut map, assessment| {
+            map.entry(assessment.subject_id.clone())
+                .or_default()
+                .push(assessment);
+            map
+        });
+    let grades = storage
+        .get_db_student_grades(student_id, term)
+        .await?
+        .into_iter()
+        .map(|grade| StudentGrade {
+            assessments: assessments.remove(&grade.subject_id).unwrap_or_default(),
+            ..grade
+        })
+        .collect();
+    Ok(grades)
 }
 
 async fn update_grade_with(
     decoder: &impl DecodeUserId,
+    roster: &impl GetDbEnrollments,
     setter: &impl UpdateDbGrade,
     getter: &impl GetGrades,
-    subject: SubjectId,
-    student: UserId,
-    grade: Grade,
-) -> crate::Result<GradeRecord> {
-    let student_id = decoder.decode_user_id(student.clone()).unwrap();
-    setter
-        .update_db_grade(subject.clone(), student_id, grade)
-        .await?;
-    let grade = getter
-        .get_grades(Some(subject))
-        .await?
+    actor: UserId,
+    change: GradeChange,
+) -> crate::Result<GradeRecord, UpdateGradeError> {
+    let GradeChange {
+        subject,
+        student,
+        grade,
+        status,
+        version,
+        reason,
+    } = change;
+    let actor = decoder
+        .decode_user_id(actor)
+        .map_err(|_| crate::Error::expected(UpdateGradeError::Forbidden))?;
+    let student_id = decoder
+        .decode_user_id(student.clone())
+        .map_err(|_| crate::Error::expected(UpdateGradeError::NotFound))?;
+    let is_enrolled = roster
+        .get_db_enrollments(vec![student_id])
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .any(|enrollment| enrollment.subject_id == subject);
+    if !is_enrolled {
+        return Err(crate::Error::expected(UpdateGradeError::NotEnrolled));
+    }
+    let change = DbGradeChange {
+        subject: subject.clone(),
+        student: student_id,
+        grade,
+        status,
+        version,
+        reason,
+    };
+    setter.update_db_grade(actor, change).await?;
+    getter
+        .get_grades(Some(subject), None)
+        .await
+        .map_err(crate::Error::from_internal)?
         .into_iter()
         .find(|grade| grade.student_id == student)
-        .unwrap();
-    Ok(grade)
+        .ok_or(crate::Error::expected(UpdateGradeError::NotFound))
+}
+
+async fn remove_grade_with(
+    decoder: &impl DecodeUserId,
+    remover: &impl RemoveDbGrade,
+    getter: &impl GetGrades,
+    actor: UserId,
+    removal: GradeRemoval,
+) -> crate::Result<GradeRecord, RemoveGradeError> {
+    let GradeRemoval {
+        subject,
+        student,
+        version,
+        reason,
+    } = removal;
+    let actor = decoder
+        .decode_user_id(actor)
+        .map_err(|_| crate::Error::expected(RemoveGradeError::Forbidden))?;
+    let student_id = decoder
+        .decode_user_id(student.clone())
+        .map_err(|_| crate::Error::expected(RemoveGradeError::NotFound))?;
+    let removal = DbGradeRemoval {
+        subject: subject.clone(),
+        student: student_id,
+        version,
+        reason,
+    };
+    remover.remove_db_grade(actor, removal).await?;
+    getter
+        .get_grades(Some(subject), None)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .find(|grade| grade.student_id == student)
+        .ok_or(crate::Error::expected(RemoveGradeError::NotFound))
+}
+
+async fn get_grade_history_with(
+    decoder: &impl DecodeUserId,
+    grades: &impl GetGrade,
+    events: &impl GetDbGradeEvents,
+    subject_id: SubjectId,
+    student_id: UserId,
+) -> crate::Result<GradeHistory, GetGradeError> {
+    let grade = grades
+        .get_grade(subject_id.clone(), student_id.clone())
+        .await?;
+    let student_id = decoder
+        .decode_user_id(student_id)
+        .map_err(|_| crate::Error::expected(GetGradeError::NotFound))?;
+    let events = events
+        .get_db_grade_events(subject_id, student_id)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    Ok(GradeHistory { grade, events })
+}
+
+impl GetManagedSubjects for AppContext {
+    async fn get_managed_subjects(&self) -> crate::Result<Vec<ManagedSubject>> {
+        get_managed_subjects(&self.database).await
+    }
+}
+
+impl CreateSubject for AppContext {
+    async fn create_subject(&self, subject: NewSubject) -> crate::Result<(), ManageSubjectError> {
+        insert_subject(&self.database, subject).await
+    }
+}
+
+impl RenameSubject for AppContext {
+    async fn rename_subject(&self, subject: Subject) -> crate::Result<(), ManageSubjectError> {
+        update_subject_title(&self.database, subject).await
+    }
+}
+
+impl SetSubjectArchived for AppContext {
+    async fn set_subject_archived(
+        &self,
+        subject_id: SubjectId,
+        is_archived: bool,
+    ) -> crate::Result<(), ManageSubjectError> {
+        update_subject_archived(&self.database, subject_id, is_archived).await
+    }
+}
+
+impl SetGradingScale for AppContext {
+    async fn set_grading_scale(
+        &self,
+        subject: SubjectScale,
+    ) -> crate::Result<(), ManageSubjectError> {
+        update_subject_grading_scale(&self.database, subject).await
+    }
+}
+
+impl GetGradingScale for AppContext {
+    async fn get_grading_scale(
+        &self,
+        subject_id: &SubjectId,
+    ) -> crate::Result<Option<GradingScale>> {
+        get_subject_grading_scale(&self.database, subject_id).await
+    }
+}
+
+impl GetGradingScales for AppContext {
+    async fn get_grading_scales(&self) -> crate::Result<Vec<SubjectScale>> {
+        get_grading_scales(&self.database).await
+    }
+}
+
+impl DeleteSubject for AppContext {
+    async fn delete_subject(&self, subject_id: SubjectId) -> crate::Result<(), ManageSubjectError> {
+        remove_subject(&self.database, subject_id).await
+    }
 }
 
 impl GetDbGrade for AppContext {
@@ -214,8 +791,9 @@ impl GetDbGrades for AppContext {
     async fn get_db_grades(
         &self,
         subject_id: Option<SubjectId>,
+        term: Option<TermId>,
     ) -> crate::Result<Vec<DbGradeRecord>> {
-        get_db_grades(&self.database, subject_id).await
+        get_db_grades(&self.database, subject_id, term).await
     }
 }
 
@@ -223,18 +801,58 @@ impl GetDbStudentGrades for AppContext {
     async fn get_db_student_grades(
         &self,
         student_id: DbUserId,
+        term: Option<TermId>,
     ) -> crate::Result<Vec<StudentGrade>> {
-        get_db_student_grades(&self.database, student_id).await
+        get_db_student_grades(&self.database, student_id, term).await
+    }
+}
+
+impl GetDbStudentAssessments for AppContext {
+    async fn get_db_student_assessments(
+        &self,
+        student_id: DbUserId,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<StudentAssessment>> {
+        get_db_student_assessments(&self.database, student_id, term).await
+    }
+}
+
+impl GetDbGradePage for AppContext {
+    async fn get_db_grade_page(
+        &self,
+        query: DbGradeQuery,
+        limit: Option<i64>,
+    ) -> crate::Result<Vec<DbGradePageRow>> {
+        get_db_grade_page(&self.database, query, limit).await
     }
 }
 
 impl UpdateDbGrade for AppContext {
     async fn update_db_grade(
         &self,
-        subject: SubjectId,
-        student: DbUserId,
-        grade: crate::domain::grade::Grade,
-    ) -> crate::Result<()> {
-        update_db_grade(&self.database, subject, student, grade).await
+        actor: DbUserId,
+        change: DbGradeChange,
+    ) -> crate::Result<(), UpdateGradeError> {
+        update_db_grade(&self.database, actor, change).await
+    }
+}
+
+impl RemoveDbGrade for AppContext {
+    async fn remove_db_grade(
+        &self,
+        actor: DbUserId,
+        removal: DbGradeRemoval,
+    ) -> crate::Result<(), RemoveGradeError> {
+        remove_db_grade(&self.database, actor, removal).await
+    }
+}
+
+impl GetDbGradeEvents for AppContext {
+    async fn get_db_grade_events(
+        &self,
+        subject_id: SubjectId,
+        student_id: DbUserId,
+    ) -> crate::Result<Vec<GradeEvent>> {
+        get_db_grade_events(&self.database, subject_id, student_id).await
     }
 }
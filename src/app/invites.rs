@@ -0,0 +1,193 @@
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    app::AppError,
+    domain::{
+        auth::{DecodeUserId, FindUser, FindUserError, HashPassword, NewUser, SignupData, User},
+        email::MaybeEmail,
+        invites::*,
+        mail::{Mail, MailTemplate, RenderMail, SendMail},
+        role::Role,
+        token::{InviteToken, InviteTokenHash},
+    },
+    error::ErrorKind,
+    services::{
+        database::invites::{save_invite, save_invited_user},
+        token_issuer::{hash_invite_token, issue_invite_token},
+    },
+};
+
+use super::{validation::ValidationErrors, AppContext};
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn invite_teacher<T>(
+    ctx: &AppContext,
+    inviter: User,
+    form: T,
+    locale: LanguageIdentifier,
+) -> crate::Result<(), AppError<InviteError>>
+where
+    T: core::fmt::Debug + TryInto<InviteData, Error = ValidationErrors>,
+{
+    if inviter.role == Role::Student {
+        return Err(crate::Error::expected(AppError::Logical(
+            InviteError::Forbidden,
+        )));
+    }
+    let InviteData { email } = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    let request = InviteRequest {
+        inviter_id: inviter.id,
+        inviter_name: inviter.name,
+        email,
+        locale,
+    };
+    ctx.invite_teacher(request)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+async fn invite_teacher_with(
+    decoder: &impl DecodeUserId,
+    users: &impl FindUser,
+    issuer: &impl IssueInviteToken,
+    storage: &impl SaveInvite,
+    renderer: &impl RenderMail,
+    mailer: &impl SendMail,
+    request: InviteRequest,
+) -> crate::Result<(), InviteError> {
+    let InviteRequest {
+        inviter_id,
+        inviter_name,
+        email,
+        locale,
+    } = request;
+    let inviter_id = decoder
+        .decode_user_id(inviter_id)
+        .map_err(|_| crate::Error::expected(InviteError::Forbidden))?;
+    match users
+        .find_user(&MaybeEmail::new(email.clone().into()))
+        .await
+    {
+        Ok(_) => return Err(crate::Error::expected(InviteError::EmailTaken)),
+        Err(error) => match error.kind {
+            ErrorKind::Expected(FindUserError::NotFound) => {}
+            ErrorKind::Internal(error) => return Err(crate::Error::internal(error)),
+        },
+    }
+    let IssuedInviteToken { token, hash, ttl } = issuer
+        .issue_invite_token()
+        .map_err(crate::Error::from_internal)?;
+    let invite = NewInvite {
+        hash,
+        email: email.clone(),
+        invited_by: inviter_id,
+        ttl,
+    };
+    storage
+        .save_invite(invite)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    let data = TeacherInviteMail {
+        inviter: inviter_name,
+        token,
+        ttl_days: ttl.as_secs() / (24 * 60 * 60),
+    };
+    let content = renderer
+        .render_mail(MailTemplate::TeacherInvite, data, &locale)
+        .map_err(crate::Error::from_internal)?;
+    let mail = Mail { to: email, content };
+    mailer
+        .send_mail(mail)
+        .await
+        .map_err(crate::Error::from_internal)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn signup_with_invite<T>(
+    ctx: &AppContext,
+    form: T,
+) -> crate::Result<(), AppError<InvitedSignupError>>
+where
+    T: core::fmt::Debug + TryInto<InvitedSignupData, Error = ValidationErrors>,
+{
+    let signup_data = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.signup_with_invite(signup_data)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+async fn signup_with_invite_with(
+    hasher: &impl HashPassword,
+    invites: &impl HashInviteToken,
+    storage: &impl SaveInvitedUser,
+    signup_data: InvitedSignupData,
+) -> crate::Result<(), InvitedSignupError> {
+    let InvitedSignupData {
+        data: SignupData {
+            email,
+            password,
+            name,
+        },
+        invite,
+    } = signup_data;
+    let password_hash = hasher
+        .hash_password(&password)
+        .map_err(crate::Error::from_internal)?;
+    let new_user = NewUser {
+        email,
+        name,
+        password_hash,
+        role: Role::Teacher,
+    };
+    let hash = invites.hash_invite_token(&invite);
+    storage.save_invited_user(new_user, hash).await
+}
+
+impl InviteTeacher for AppContext {
+    async fn invite_teacher(&self, request: InviteRequest) -> crate::Result<(), InviteError> {
+        invite_teacher_with(self, self, self, self, self, self, request).await
+    }
+}
+
+impl SignupWithInvite for AppContext {
+    async fn signup_with_invite(
+        &self,
+        signup_data: InvitedSignupData,
+    ) -> crate::Result<(), InvitedSignupError> {
+        signup_with_invite_with(self, self, self, signup_data).await
+    }
+}
+
+impl IssueInviteToken for AppContext {
+    fn issue_invite_token(&self) -> crate::Result<IssuedInviteToken> {
+        issue_invite_token(&self.token_issuer)
+    }
+}
+
+impl HashInviteToken for AppContext {
+    fn hash_invite_token(&self, token: &InviteToken) -> InviteTokenHash {
+        hash_invite_token(token)
+    }
+}
+
+impl SaveInvite for AppContext {
+    async fn save_invite(&self, invite: NewInvite) -> crate::Result<()> {
+        save_invite(&self.database, invite).await
+    }
+}
+
+impl SaveInvitedUser for AppContext {
+    async fn save_invited_user(
+        &self,
+        user: NewUser,
+        invite: InviteTokenHash,
+    ) -> crate::Result<(), InvitedSignupError> {
+        save_invited_user(&self.database, user, invite).await
+    }
+}
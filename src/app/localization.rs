@@ -15,6 +15,7 @@ pub struct LocalizedError {
 #[serde(untagged)]
 pub enum Argument {
     Number(f64),
+    Text(Cow<'static, str>),
 }
 
 impl LocalizedError {
@@ -41,6 +42,10 @@ impl LocalizedError {
         self.with_arg(key, Argument::Number(value.into()))
     }
 
+    pub fn with_text(self, key: &'static str, value: impl Into<Cow<'static, str>>) -> Self {
+        self.with_arg(key, Argument::Text(value.into()))
+    }
+
     fn with_arg(self, key: &'static str, arg: Argument) -> Self {
         let mut args = self.args.unwrap_or_default();
         args.insert(Cow::Borrowed(key), arg);
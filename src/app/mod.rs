@@ -4,21 +4,37 @@ use serde::Deserialize;
 use validation::ValidationErrors;
 
 use crate::services::{
+    authenticator::{Authenticator, TwoFactorConfig},
     database::{Database, DatabaseConfig},
     hasher::{Hasher, HasherConfig},
     id_encoder::{IdConfig, IdEncoder},
     localizer::{I18nConfig, Localizer},
+    mailer::{MailService, MailerConfig},
     signer::{SignatureConfig, Signer},
     templating_engine::{TemplateConfig, TemplatingEngine},
+    throttler::{ThrottleConfig, Throttler},
     token_issuer::{JwtConfig, TokenIssuer},
 };
 
+pub mod admin;
+pub mod assessments;
 pub mod auth;
+pub mod dashboard;
+pub mod enrollment;
+pub mod grade_entry;
+pub mod grade_export;
+pub mod grade_import;
+pub mod grade_stats;
 pub mod grades;
+pub mod invites;
 pub mod keys;
 pub mod performance;
+pub mod terms;
+pub mod throttle;
+pub mod two_factor;
 
 pub mod localization;
+pub mod statistics;
 pub mod validation;
 
 #[derive(Clone, Debug, Deserialize)]
@@ -30,6 +46,9 @@ pub struct AppConfig {
     pub hasher: HasherConfig,
     pub jwt: JwtConfig,
     pub templates: TemplateConfig,
+    pub mailer: MailerConfig,
+    pub two_factor: TwoFactorConfig,
+    pub login_throttle: ThrottleConfig,
 }
 
 #[derive(Clone)]
@@ -41,6 +60,9 @@ pub struct AppContext {
     pub hasher: Hasher,
     pub token_issuer: TokenIssuer,
     pub templating_engine: Arc<TemplatingEngine<Arc<Localizer>>>,
+    pub mailer: MailService,
+    pub authenticator: Authenticator,
+    pub throttler: Throttler,
 }
 
 #[derive(Debug)]
@@ -59,6 +81,9 @@ impl AppContext {
         let database = Database::new(config.database);
         let id_encoder = Arc::new(IdEncoder::new(config.id));
         let token_issuer = TokenIssuer::new(config.jwt);
+        let mailer = MailService::new(config.mailer)?;
+        let authenticator = Authenticator::new(config.two_factor)?;
+        let throttler = Throttler::new(config.login_throttle);
         Ok(Self {
             localizer,
             database,
@@ -67,6 +92,9 @@ impl AppContext {
             hasher,
             token_issuer,
             templating_engine,
+            mailer,
+            authenticator,
+            throttler,
         })
     }
 }
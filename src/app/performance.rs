@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 
-use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal::Decimal;
 
 use crate::{
     domain::{
         grades::GetDbGrades, key::Key, name::Name, percentile::Percentile, performance::*,
-        signature::Signature, user_id::DbUserId, verifying_key::VerifyingKey,
+        signature::Signature, term_id::TermId, terms::GetTerm, user_id::DbUserId,
+        verifying_key::VerifyingKey,
     },
     services::{
         database::performance::lookup_key,
@@ -13,23 +14,29 @@ use crate::{
     },
 };
 
-use super::AppContext;
+use super::{statistics, AppContext};
 
 #[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
 pub async fn get_signature(
     ctx: &AppContext,
     key: String,
+    term: Option<String>,
 ) -> crate::Result<SignedEvaluation, KeyLookupError> {
     let key = Key::try_from(key).map_err(|_| crate::Error::expected(KeyLookupError::UnknownKey))?;
-    ctx.get_signature(key).await
+    let term = term
+        .map(|term| term.parse())
+        .transpose()
+        .map_err(|_| crate::Error::expected(KeyLookupError::UnknownTerm))?;
+    ctx.get_signature(key, term).await
 }
 
 async fn get_signature_with(
     evaluator: &impl GetPerformanceEvaluation,
     signer: &impl SignEvaluation,
     key: Key,
+    term: Option<TermId>,
 ) -> crate::Result<SignedEvaluation, KeyLookupError> {
-    let claim = evaluator.get_performance_evaluation(key).await?;
+    let claim = evaluator.get_performance_evaluation(key, term).await?;
     let signature = signer
         .sign_evaluation(&claim)
         .map_err(crate::Error::from_internal)?;
@@ -38,70 +45,39 @@ async fn get_signature_with(
 
 async fn get_performance_evaluation_with(
     key_storage: &impl LookupKey,
+    term_storage: &impl GetTerm,
     grade_storage: &impl GetDbGrades,
     key: Key,
+    term: Option<TermId>,
 ) -> crate::Result<PerformanceEvaluation, KeyLookupError> {
     let (student_id, student_name) = key_storage.lookup_key(key).await?;
+    let term = term_storage
+        .get_term(term)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .ok_or(crate::Error::expected(KeyLookupError::UnknownTerm))?;
     let grades = grade_storage
-        .get_db_grades(None)
+        .get_db_grades(None, Some(term.id))
         .await
         .map_err(crate::Error::from_internal)?;
-    let subject_grades = grades
-        .iter()
-        .fold(HashMap::<_, Vec<_>>::new(), |mut map, record| {
-            map.entry(record.subject_id.clone())
-                .or_default()
-                .push(Decimal::from(record.grade));
-            map
-        });
-    let means = subject_grades
-        .iter()
-        .map(|(subject, grades)| {
-            let sum = grades.iter().copied().sum::<Decimal>();
-            let n = Decimal::new(grades.len() as i64, 0);
-            let mean = sum / n;
-            (subject, mean)
-        })
-        .collect::<HashMap<_, _>>();
-    let std_deviations = subject_grades
+    let graded = statistics::graded_values(&grades);
+    let subject_grades = statistics::group_by_subject(&graded);
+    let moments = subject_grades
         .iter()
-        .map(|(subject, grades)| {
-            let deviation_sum = grades
-                .iter()
-                .map(|grade| (grade - means.get(subject).unwrap()).powu(2))
-                .sum::<Decimal>();
-            let variance = deviation_sum
-                .checked_div(Decimal::new((grades.len() - 1) as i64, 0))
-                .unwrap_or(Decimal::ZERO);
-            let std_deviation = variance.sqrt().unwrap();
-            (subject, std_deviation)
-        })
+        .filter_map(|(subject, grades)| Some((subject, statistics::moments(grades)?)))
         .collect::<HashMap<_, _>>();
-    let z_scores = grades
+    let z_scores = graded
         .iter()
-        .map(|record| {
+        .map(|(record, grade)| {
             let student = record.student_id;
-            let subject = record.subject_id.clone();
-            let grade = Decimal::from(record.grade);
-            let mean = means.get(&subject).unwrap();
-            let std_deviation = std_deviations.get(&subject).unwrap();
-            let z_score = (grade - mean)
-                .checked_div(*std_deviation)
-                .unwrap_or(Decimal::ZERO);
+            let z_score = moments.get(&record.subject_id).unwrap().z_score(*grade);
             (student, z_score)
         })
         .collect::<Vec<_>>();
-    let avg_z_scores = z_scores
-        .into_iter()
-        .fold(HashMap::<_, Vec<_>>::new(), |mut map, (student, score)| {
-            map.entry(student).or_default().push(score);
-            map
-        })
+    let avg_z_scores = statistics::group_by(z_scores)
         .into_iter()
         .map(|(student, z_scores)| {
-            let n = Decimal::new(z_scores.len() as i64, 0);
-            let sum = z_scores.into_iter().sum::<Decimal>();
-            let avg = sum / n;
+            let avg = statistics::mean(&z_scores).unwrap_or(Decimal::ZERO);
             (student, avg)
         })
         .collect::<HashMap<_, _>>();
@@ -120,14 +96,19 @@ async fn get_performance_evaluation_with(
     let percentile = Percentile::new(percentile)?;
     let evaluation = PerformanceEvaluation {
         student: student_name,
+        term: term.name,
         percentile,
     };
     Ok(evaluation)
 }
 
 impl GetSignature for AppContext {
-    async fn get_signature(&self, key: Key) -> crate::Result<SignedEvaluation, KeyLookupError> {
-        get_signature_with(self, self, key).await
+    async fn get_signature(
+        &self,
+        key: Key,
+        term: Option<TermId>,
+    ) -> crate::Result<SignedEvaluation, KeyLookupError> {
+        get_signature_with(self, self, key, term).await
     }
 }
 
@@ -142,8 +123,9 @@ impl GetPerformanceEvaluation for AppContext {
     async fn get_performance_evaluation(
         &self,
         key: Key,
+        term: Option<TermId>,
     ) -> crate::Result<PerformanceEvaluation, KeyLookupError> {
-        get_performance_evaluation_with(self, self, key).await
+        get_performance_evaluation_with(self, self, self, key, term).await
     }
 }
 
@@ -0,0 +1,130 @@
+use std::{collections::HashMap, hash::Hash};
+
+use rust_decimal::{prelude::ToPrimitive, Decimal, MathematicalOps};
+
+use crate::domain::{
+    grade_stats::{HistogramBin, Summary},
+    grades::DbGradeRecord,
+    subject_id::SubjectId,
+};
+
+const SUMMARY_DIGITS: u32 = 2;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Moments {
+    pub mean: Decimal,
+    pub std_deviation: Decimal,
+}
+
+impl Moments {
+    pub fn z_score(&self, value: Decimal) -> Decimal {
+        (value - self.mean)
+            .checked_div(self.std_deviation)
+            .unwrap_or(Decimal::ZERO)
+    }
+}
+
+pub fn group_by<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> HashMap<K, Vec<V>>
+where
+    K: Eq + Hash,
+{
+    pairs
+        .into_iter()
+        .fold(HashMap::<_, Vec<_>>::new(), |mut map, (key, value)| {
+            map.entry(key).or_default().push(value);
+            map
+        })
+}
+
+pub fn graded_values(records: &[DbGradeRecord]) -> Vec<(&DbGradeRecord, Decimal)> {
+    records
+        .iter()
+        .filter_map(|record| record.grade.map(|grade| (record, Decimal::from(grade))))
+        .collect()
+}
+
+pub fn group_by_subject(graded: &[(&DbGradeRecord, Decimal)]) -> HashMap<SubjectId, Vec<Decimal>> {
+    group_by(
+        graded
+            .iter()
+            .map(|(record, value)| (record.subject_id.clone(), *value)),
+    )
+}
+
+pub fn mean(values: &[Decimal]) -> Option<Decimal> {
+    values
+        .iter()
+        .copied()
+        .sum::<Decimal>()
+        .checked_div(Decimal::from(values.len()))
+}
+
+pub fn std_deviation(values: &[Decimal], mean: Decimal) -> Decimal {
+    let deviation_sum = values
+        .iter()
+        .map(|value| (value - mean).powu(2))
+        .sum::<Decimal>();
+    deviation_sum
+        .checked_div(Decimal::from(values.len().saturating_sub(1)))
+        .and_then(|variance| variance.sqrt())
+        .unwrap_or(Decimal::ZERO)
+}
+
+pub fn moments(values: &[Decimal]) -> Option<Moments> {
+    let mean = mean(values)?;
+    let std_deviation = std_deviation(values, mean);
+    Some(Moments {
+        mean,
+        std_deviation,
+    })
+}
+
+pub fn quantile(sorted: &[Decimal], q: Decimal) -> Option<Decimal> {
+    let last = sorted.len().checked_sub(1)?;
+    let position = q * Decimal::from(last);
+    let floor = position.floor();
+    let index = floor.to_usize()?;
+    let low = *sorted.get(index)?;
+    let high = sorted.get(index + 1).copied().unwrap_or(low);
+    Some(low + (high - low) * (position - floor))
+}
+
+pub fn summarize(values: &[Decimal]) -> Option<Summary> {
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    let mean = mean(&sorted)?;
+    let quartile = |q| quantile(&sorted, Decimal::new(q, 2));
+    let summary = Summary {
+        count: sorted.len(),
+        mean: mean.round_dp(SUMMARY_DIGITS),
+        median: quartile(50)?.round_dp(SUMMARY_DIGITS),
+        std_deviation: std_deviation(&sorted, mean).round_dp(SUMMARY_DIGITS),
+        min: *sorted.first()?,
+        max: *sorted.last()?,
+        lower_quartile: quartile(25)?.round_dp(SUMMARY_DIGITS),
+        upper_quartile: quartile(75)?.round_dp(SUMMARY_DIGITS),
+    };
+    Some(summary)
+}
+
+pub fn histogram(values: &[Decimal], min: Decimal, max: Decimal, bins: usize) -> Vec<HistogramBin> {
+    let width = (max - min) / Decimal::from(bins.max(1));
+    let mut histogram = (0..bins)
+        .map(|bin| HistogramBin {
+            from: min + width * Decimal::from(bin),
+            to: min + width * Decimal::from(bin + 1),
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+    for value in values {
+        let bin = (value - min)
+            .checked_div(width)
+            .and_then(|bin| bin.floor().to_usize())
+            .unwrap_or_default()
+            .min(bins.saturating_sub(1));
+        if let Some(bin) = histogram.get_mut(bin) {
+            bin.count += 1;
+        }
+    }
+    histogram
+}
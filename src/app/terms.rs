@@ -0,0 +1,91 @@
+use crate::{
+    app::AppError,
+    domain::{auth::User, role::Role, term_id::TermId, terms::*},
+    services::database::terms::{get_term, get_terms, insert_term, update_active_term},
+};
+
+use super::{validation::ValidationErrors, AppContext};
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn list_terms(ctx: &AppContext) -> crate::Result<Vec<Term>> {
+    ctx.get_terms().await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn select_term(ctx: &AppContext, term: Option<String>) -> crate::Result<TermSelection> {
+    let term = term.and_then(|term| term.parse().ok());
+    select_term_with(ctx, term).await
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn create_term<T>(
+    ctx: &AppContext,
+    user: User,
+    form: T,
+) -> crate::Result<(), AppError<TermError>>
+where
+    T: core::fmt::Debug + TryInto<NewTerm, Error = ValidationErrors>,
+{
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(TermError::Forbidden.into()));
+    }
+    let term: NewTerm = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    if term.ends_on < term.starts_on {
+        return Err(crate::Error::expected(TermError::InvalidDates.into()));
+    }
+    ctx.create_term(term).await.map_err(crate::Error::cast)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn activate_term(
+    ctx: &AppContext,
+    user: User,
+    term_id: String,
+) -> crate::Result<(), TermError> {
+    if user.role != Role::Teacher {
+        return Err(crate::Error::expected(TermError::Forbidden));
+    }
+    let term_id = term_id
+        .parse()
+        .map_err(|_| crate::Error::expected(TermError::NotFound))?;
+    ctx.activate_term(term_id).await
+}
+
+async fn select_term_with(
+    storage: &impl GetTerms,
+    term: Option<TermId>,
+) -> crate::Result<TermSelection> {
+    let terms = storage.get_terms().await?;
+    let selected = term
+        .and_then(|term| terms.iter().find(|candidate| candidate.id == term))
+        .or_else(|| terms.iter().find(|candidate| candidate.is_active))
+        .cloned();
+    Ok(TermSelection { terms, selected })
+}
+
+impl GetTerms for AppContext {
+    async fn get_terms(&self) -> crate::Result<Vec<Term>> {
+        get_terms(&self.database).await
+    }
+}
+
+impl GetTerm for AppContext {
+    async fn get_term(&self, term_id: Option<TermId>) -> crate::Result<Option<Term>> {
+        get_term(&self.database, term_id).await
+    }
+}
+
+impl CreateTerm for AppContext {
+    async fn create_term(&self, term: NewTerm) -> crate::Result<(), TermError> {
+        insert_term(&self.database, term).await
+    }
+}
+
+impl ActivateTerm for AppContext {
+    async fn activate_term(&self, term_id: TermId) -> crate::Result<(), TermError> {
+        update_active_term(&self.database, term_id).await
+    }
+}
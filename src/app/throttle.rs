@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use crate::{
+    domain::throttle::*,
+    services::{
+        database::throttle::{get_login_failures, record_login_attempt},
+        throttler::compute_login_delay,
+    },
+};
+
+use super::AppContext;
+
+async fn check_login_throttle_with(
+    storage: &impl GetLoginFailures,
+    policy: &impl ComputeLoginDelay,
+    audit: &impl RecordLoginAttempt,
+    source: &LoginSource,
+) -> crate::Result<(), Throttled> {
+    let failures = storage
+        .get_login_failures(source)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    let Some(retry_after) = policy.compute_login_delay(&failures) else {
+        return Ok(());
+    };
+    tracing::warn!(?source, ?failures, ?retry_after, "login attempt throttled");
+    let attempt = LoginAttempt {
+        source: source.clone(),
+        outcome: LoginAttemptOutcome::Throttled,
+    };
+    audit
+        .record_login_attempt(attempt)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    Err(crate::Error::expected(Throttled { retry_after }))
+}
+
+impl CheckLoginThrottle for AppContext {
+    async fn check_login_throttle(&self, source: &LoginSource) -> crate::Result<(), Throttled> {
+        check_login_throttle_with(self, self, self, source).await
+    }
+}
+
+impl GetLoginFailures for AppContext {
+    async fn get_login_failures(&self, source: &LoginSource) -> crate::Result<LoginFailures> {
+        get_login_failures(&self.database, source, self.throttler.window()).await
+    }
+}
+
+impl ComputeLoginDelay for AppContext {
+    fn compute_login_delay(&self, failures: &LoginFailures) -> Option<Duration> {
+        compute_login_delay(&self.throttler, failures)
+    }
+}
+
+impl RecordLoginAttempt for AppContext {
+    async fn record_login_attempt(&self, attempt: LoginAttempt) -> crate::Result<()> {
+        record_login_attempt(&self.database, attempt).await
+    }
+}
@@ -0,0 +1,511 @@
+use std::net::IpAddr;
+
+use anyhow::anyhow;
+
+use crate::{
+    app::AppError,
+    domain::{
+        auth::{DbUser, DecodeUserId, EncodeUserId, StartSession, User},
+        email::{Email, MaybeEmail},
+        role::Role,
+        throttle::{
+            CheckLoginThrottle, LoginAttempt, LoginAttemptOutcome, LoginSource, RecordLoginAttempt,
+        },
+        token::SessionId,
+        two_factor::*,
+        user_id::{DbUserId, UserId},
+    },
+    error::ErrorKind,
+    services::{
+        authenticator::{
+            check_totp_code, decrypt_totp_secret, is_two_factor_required, issue_totp_secret,
+            provision_totp,
+        },
+        database::two_factor::{
+            activate_totp, get_recovery_codes, get_totp_credential, record_challenge_attempt,
+            save_totp_secret, use_recovery_code, use_totp_step,
+        },
+        hasher::{is_recovery_code, issue_recovery_codes, verify_recovery_code},
+        token_issuer::{issue_two_factor_challenge, parse_two_factor_challenge},
+    },
+};
+
+use super::{validation::ValidationErrors, AppContext};
+
+const MAX_CHALLENGE_ATTEMPTS: i32 = 5;
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn setup_two_factor(
+    ctx: &AppContext,
+    user: User,
+) -> crate::Result<TwoFactorProvisioning, TwoFactorError> {
+    let user_id = ctx.decode_user_id(user.id).map_err(crate::Error::cast)?;
+    ctx.setup_two_factor(user_id, &user.email).await
+}
+
+async fn setup_two_factor_with(
+    issuer: &impl IssueTotpSecret,
+    storage: &impl SaveTotpSecret,
+    provisioner: &impl ProvisionTotp,
+    user_id: DbUserId,
+    email: &Email,
+) -> crate::Result<TwoFactorProvisioning, TwoFactorError> {
+    let IssuedTotpSecret { secret, encrypted } = issuer
+        .issue_totp_secret()
+        .map_err(crate::Error::from_internal)?;
+    storage.save_totp_secret(user_id, encrypted).await?;
+    provisioner
+        .provision_totp(&secret, email)
+        .map_err(crate::Error::from_internal)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn enable_two_factor<T>(
+    ctx: &AppContext,
+    user: User,
+    form: T,
+) -> crate::Result<Vec<RecoveryCode>, AppError<TwoFactorError>>
+where
+    T: core::fmt::Debug + TryInto<TwoFactorCode, Error = ValidationErrors>,
+{
+    let code = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    let user_id = ctx
+        .decode_user_id(user.id)
+        .map_err(crate::Error::cast::<TwoFactorError>)
+        .map_err(crate::Error::cast)?;
+    ctx.enable_two_factor(user_id, code)
+        .await
+        .map_err(crate::Error::cast)
+}
+
+async fn enable_two_factor_with(
+    storage: &impl GetTotpCredential,
+    decryptor: &impl DecryptTotpSecret,
+    totp: &impl CheckTotpCode,
+    issuer: &impl IssueRecoveryCodes,
+    activator: &impl ActivateTotp,
+    user_id: DbUserId,
+    code: TwoFactorCode,
+) -> crate::Result<Vec<RecoveryCode>, TwoFactorError> {
+    let credential = storage
+        .get_totp_credential(user_id)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .ok_or(crate::Error::expected(TwoFactorError::InvalidCode))?;
+    if credential.is_enabled {
+        return Err(crate::Error::expected(TwoFactorError::AlreadyEnabled));
+    }
+    let secret = decryptor
+        .decrypt_totp_secret(&credential.secret)
+        .map_err(crate::Error::from_internal)?;
+    let step = totp
+        .check_totp_code(&secret, &code)
+        .ok_or(crate::Error::expected(TwoFactorError::InvalidCode))?;
+    let (codes, hashes) = issuer
+        .issue_recovery_codes()
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .map(|IssuedRecoveryCode { code, hash }| (code, hash))
+        .unzip();
+    activator
+        .activate_totp(user_id, step, hashes)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    Ok(codes)
+}
+
+#[tracing::instrument(skip(ctx), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn verify_two_factor<T>(
+    ctx: &AppContext,
+    form: T,
+    ip: IpAddr,
+) -> crate::Result<TwoFactorLogin, AppError<TwoFactorError>>
+where
+    T: core::fmt::Debug + TryInto<TwoFactorData, Error = ValidationErrors>,
+{
+    let data = form
+        .try_into()
+        .map_err(AppError::Validation)
+        .map_err(crate::Error::expected)?;
+    ctx.verify_two_factor(TwoFactorRequest { data, ip })
+        .await
+        .map_err(crate::Error::cast)
+}
+
+async fn verify_two_factor_with(
+    parser: &impl ParseTwoFactorChallenge,
+    throttle: &impl CheckLoginThrottle,
+    challenges: &impl RecordChallengeAttempt,
+    audit: &impl RecordLoginAttempt,
+    confirmer: &impl ConfirmTwoFactor,
+    sessions: &impl StartSession,
+    request: TwoFactorRequest,
+) -> crate::Result<TwoFactorLogin, TwoFactorError> {
+    let TwoFactorRequest {
+        data: TwoFactorData { challenge, code },
+        ip,
+    } = request;
+    let claims = parser.parse_two_factor_challenge(challenge)?;
+    let source = LoginSource {
+        email: claims.email.clone(),
+        ip,
+    };
+    throttle
+        .check_login_throttle(&source)
+        .await
+        .map_err(crate::Error::cast)?;
+    let attempts = challenges
+        .record_challenge_attempt(&claims)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    if attempts > MAX_CHALLENGE_ATTEMPTS {
+        return Err(crate::Error::expected(TwoFactorError::ChallengeExpired));
+    }
+    let confirmation = confirmer.confirm_two_factor(claims.user_id, code).await;
+    let outcome = match &confirmation {
+        Ok(_) => Some(LoginAttemptOutcome::Succeeded),
+        Err(error) if matches!(error.kind, ErrorKind::Expected(TwoFactorError::InvalidCode)) => {
+            Some(LoginAttemptOutcome::Failed)
+        }
+        Err(_) => None,
+    };
+    if let Some(outcome) = outcome {
+        audit
+            .record_login_attempt(LoginAttempt { source, outcome })
+            .await
+            .map_err(crate::Error::from_internal)?;
+    }
+    let TwoFactorConfirmation {
+        user_id,
+        recovery_codes,
+    } = confirmation?;
+    let tokens = sessions
+        .start_session(user_id, SessionId::new())
+        .await
+        .map_err(crate::Error::from_internal)?;
+    Ok(TwoFactorLogin {
+        tokens,
+        recovery_codes,
+    })
+}
+
+async fn confirm_two_factor_with(
+    decoder: &impl DecodeUserId,
+    storage: &impl GetTotpCredential,
+    enabler: &impl EnableTwoFactor,
+    checker: &impl CheckSecondFactor,
+    user_id: UserId,
+    code: TwoFactorCode,
+) -> crate::Result<TwoFactorConfirmation, TwoFactorError> {
+    let user_id = decoder
+        .decode_user_id(user_id)
+        .map_err(crate::Error::cast)?;
+    let credential = storage
+        .get_totp_credential(user_id)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .ok_or(crate::Error::expected(TwoFactorError::ChallengeExpired))?;
+    let recovery_codes = if credential.is_enabled {
+        checker.check_second_factor(user_id, code).await?;
+        Vec::new()
+    } else {
+        enabler.enable_two_factor(user_id, code).await?
+    };
+    Ok(TwoFactorConfirmation {
+        user_id,
+        recovery_codes,
+    })
+}
+
+async fn check_two_factor_with(
+    storage: &impl GetTotpCredential,
+    policy: &impl IsTwoFactorRequired,
+    encoder: &impl EncodeUserId,
+    issuer: &impl IssueTwoFactorChallenge,
+    setup: &impl SetupTwoFactor,
+    user: &DbUser,
+    email: &MaybeEmail,
+) -> crate::Result<Option<TwoFactorPrompt>> {
+    let is_enabled = storage
+        .get_totp_credential(user.id)
+        .await?
+        .is_some_and(|credential| credential.is_enabled);
+    if !is_enabled && !policy.is_two_factor_required(user.role) {
+        return Ok(None);
+    }
+    let provisioning = match is_enabled {
+        true => None,
+        false => setup
+            .setup_two_factor(user.id, &user.email)
+            .await
+            .map(Some)
+            .map_err(|error| match error.kind {
+                ErrorKind::Expected(error) => {
+                    crate::Error::internal(anyhow!("set up mandatory two-factor: {error:?}"))
+                }
+                ErrorKind::Internal(error) => crate::Error::internal(error),
+            })?,
+    };
+    let user_id = encoder.encode_user_id(user.id)?;
+    let challenge = issuer.issue_two_factor_challenge(user_id, email.clone())?;
+    Ok(Some(TwoFactorPrompt {
+        challenge,
+        provisioning,
+    }))
+}
+
+async fn check_second_factor_with(
+    storage: &impl GetTotpCredential,
+    decryptor: &impl DecryptTotpSecret,
+    totp: &impl CheckTotpCode,
+    steps: &impl UseTotpStep,
+    recovery: &impl RedeemRecoveryCode,
+    user_id: DbUserId,
+    code: TwoFactorCode,
+) -> crate::Result<(), TwoFactorError> {
+    let credential = storage
+        .get_totp_credential(user_id)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .filter(|credential| credential.is_enabled)
+        .ok_or(crate::Error::expected(TwoFactorError::InvalidCode))?;
+    let secret = decryptor
+        .decrypt_totp_secret(&credential.secret)
+        .map_err(crate::Error::from_internal)?;
+    if let Some(step) = totp.check_totp_code(&secret, &code) {
+        return match steps
+            .use_totp_step(user_id, step)
+            .await
+            .map_err(crate::Error::from_internal)?
+        {
+            true => Ok(()),
+            false => Err(crate::Error::expected(TwoFactorError::InvalidCode)),
+        };
+    }
+    recovery.redeem_recovery_code(user_id, &code).await
+}
+
+async fn redeem_recovery_code_with(
+    shape: &impl IsRecoveryCode,
+    storage: &impl GetRecoveryCodes,
+    verifier: &impl VerifyRecoveryCode,
+    consumer: &impl UseRecoveryCode,
+    user_id: DbUserId,
+    code: &TwoFactorCode,
+) -> crate::Result<(), TwoFactorError> {
+    if !shape.is_recovery_code(code) {
+        return Err(crate::Error::expected(TwoFactorError::InvalidCode));
+    }
+    let recovery_code = storage
+        .get_recovery_codes(user_id)
+        .await
+        .map_err(crate::Error::from_internal)?
+        .into_iter()
+        .find(|recovery_code| verifier.verify_recovery_code(code, &recovery_code.hash))
+        .ok_or(crate::Error::expected(TwoFactorError::InvalidCode))?;
+    match consumer
+        .use_recovery_code(recovery_code.id)
+        .await
+        .map_err(crate::Error::from_internal)?
+    {
+        true => Ok(()),
+        false => Err(crate::Error::expected(TwoFactorError::InvalidCode)),
+    }
+}
+
+impl CheckTwoFactor for AppContext {
+    async fn check_two_factor(
+        &self,
+        user: &DbUser,
+        email: &MaybeEmail,
+    ) -> crate::Result<Option<TwoFactorPrompt>> {
+        check_two_factor_with(self, self, self, self, self, user, email).await
+    }
+}
+
+impl VerifyTwoFactor for AppContext {
+    async fn verify_two_factor(
+        &self,
+        request: TwoFactorRequest,
+    ) -> crate::Result<TwoFactorLogin, TwoFactorError> {
+        verify_two_factor_with(self, self, self, self, self, self, request).await
+    }
+}
+
+impl ConfirmTwoFactor for AppContext {
+    async fn confirm_two_factor(
+        &self,
+        user_id: UserId,
+        code: TwoFactorCode,
+    ) -> crate::Result<TwoFactorConfirmation, TwoFactorError> {
+        confirm_two_factor_with(self, self, self, self, user_id, code).await
+    }
+}
+
+impl SetupTwoFactor for AppContext {
+    async fn setup_two_factor(
+        &self,
+        user_id: DbUserId,
+        email: &Email,
+    ) -> crate::Result<TwoFactorProvisioning, TwoFactorError> {
+        setup_two_factor_with(self, self, self, user_id, email).await
+    }
+}
+
+impl EnableTwoFactor for AppContext {
+    async fn enable_two_factor(
+        &self,
+        user_id: DbUserId,
+        code: TwoFactorCode,
+    ) -> crate::Result<Vec<RecoveryCode>, TwoFactorError> {
+        enable_two_factor_with(self, self, self, self, self, user_id, code).await
+    }
+}
+
+impl CheckSecondFactor for AppContext {
+    async fn check_second_factor(
+        &self,
+        user_id: DbUserId,
+        code: TwoFactorCode,
+    ) -> crate::Result<(), TwoFactorError> {
+        check_second_factor_with(self, self, self, self, self, user_id, code).await
+    }
+}
+
+impl RedeemRecoveryCode for AppContext {
+    async fn redeem_recovery_code(
+        &self,
+        user_id: DbUserId,
+        code: &TwoFactorCode,
+    ) -> crate::Result<(), TwoFactorError> {
+        redeem_recovery_code_with(self, self, self, self, user_id, code).await
+    }
+}
+
+impl IsTwoFactorRequired for AppContext {
+    fn is_two_factor_required(&self, role: Role) -> bool {
+        is_two_factor_required(&self.authenticator, role)
+    }
+}
+
+impl IssueTwoFactorChallenge for AppContext {
+    fn issue_two_factor_challenge(
+        &self,
+        user_id: UserId,
+        email: MaybeEmail,
+    ) -> crate::Result<TwoFactorChallenge> {
+        issue_two_factor_challenge(&self.token_issuer, user_id, email)
+    }
+}
+
+impl ParseTwoFactorChallenge for AppContext {
+    fn parse_two_factor_challenge(
+        &self,
+        challenge: TwoFactorChallenge,
+    ) -> crate::Result<ChallengeClaims, TwoFactorError> {
+        parse_two_factor_challenge(&self.token_issuer, challenge)
+    }
+}
+
+impl IssueTotpSecret for AppContext {
+    fn issue_totp_secret(&self) -> crate::Result<IssuedTotpSecret> {
+        issue_totp_secret(&self.authenticator)
+    }
+}
+
+impl DecryptTotpSecret for AppContext {
+    fn decrypt_totp_secret(&self, secret: &EncryptedTotpSecret) -> crate::Result<TotpSecret> {
+        decrypt_totp_secret(&self.authenticator, secret)
+    }
+}
+
+impl ProvisionTotp for AppContext {
+    fn provision_totp(
+        &self,
+        secret: &TotpSecret,
+        email: &Email,
+    ) -> crate::Result<TwoFactorProvisioning> {
+        provision_totp(&self.authenticator, secret, email)
+    }
+}
+
+impl CheckTotpCode for AppContext {
+    fn check_totp_code(&self, secret: &TotpSecret, code: &TwoFactorCode) -> Option<TotpStep> {
+        check_totp_code(&self.authenticator, secret, code)
+    }
+}
+
+impl IssueRecoveryCodes for AppContext {
+    fn issue_recovery_codes(&self) -> crate::Result<Vec<IssuedRecoveryCode>> {
+        issue_recovery_codes(&self.hasher)
+    }
+}
+
+impl IsRecoveryCode for AppContext {
+    fn is_recovery_code(&self, code: &TwoFactorCode) -> bool {
+        is_recovery_code(code)
+    }
+}
+
+impl VerifyRecoveryCode for AppContext {
+    fn verify_recovery_code(&self, code: &TwoFactorCode, hash: &RecoveryCodeHash) -> bool {
+        verify_recovery_code(&self.hasher, code, hash)
+    }
+}
+
+impl SaveTotpSecret for AppContext {
+    async fn save_totp_secret(
+        &self,
+        user_id: DbUserId,
+        secret: EncryptedTotpSecret,
+    ) -> crate::Result<(), TwoFactorError> {
+        save_totp_secret(&self.database, user_id, secret).await
+    }
+}
+
+impl GetTotpCredential for AppContext {
+    async fn get_totp_credential(
+        &self,
+        user_id: DbUserId,
+    ) -> crate::Result<Option<DbTotpCredential>> {
+        get_totp_credential(&self.database, user_id).await
+    }
+}
+
+impl ActivateTotp for AppContext {
+    async fn activate_totp(
+        &self,
+        user_id: DbUserId,
+        step: TotpStep,
+        recovery_codes: Vec<RecoveryCodeHash>,
+    ) -> crate::Result<()> {
+        activate_totp(&self.database, user_id, step, recovery_codes).await
+    }
+}
+
+impl UseTotpStep for AppContext {
+    async fn use_totp_step(&self, user_id: DbUserId, step: TotpStep) -> crate::Result<bool> {
+        use_totp_step(&self.database, user_id, step).await
+    }
+}
+
+impl RecordChallengeAttempt for AppContext {
+    async fn record_challenge_attempt(&self, challenge: &ChallengeClaims) -> crate::Result<i32> {
+        record_challenge_attempt(&self.database, challenge.challenge_id, challenge.expires_at).await
+    }
+}
+
+impl GetRecoveryCodes for AppContext {
+    async fn get_recovery_codes(&self, user_id: DbUserId) -> crate::Result<Vec<DbRecoveryCode>> {
+        get_recovery_codes(&self.database, user_id).await
+    }
+}
+
+impl UseRecoveryCode for AppContext {
+    async fn use_recovery_code(&self, id: RecoveryCodeId) -> crate::Result<bool> {
+        use_recovery_code(&self.database, id).await
+    }
+}
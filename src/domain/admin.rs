@@ -0,0 +1,127 @@
+use serde::Serialize;
+
+use crate::app::localization::LocalizedError;
+
+use super::{
+    auth::DecodeIdError,
+    email::Email,
+    name::Name,
+    role::Role,
+    user_id::{DbUserId, UserId},
+};
+
+pub trait ListUsers {
+    async fn list_users(&self, search: Option<String>) -> crate::Result<Vec<ManagedUser>>;
+}
+
+pub trait ChangeUserRole {
+    async fn change_user_role(
+        &self,
+        admin_id: UserId,
+        change: RoleChange,
+    ) -> crate::Result<(), ManageUserError>;
+}
+
+pub trait SetUserDisabled {
+    async fn set_user_disabled(
+        &self,
+        admin_id: UserId,
+        user_id: UserId,
+        is_disabled: bool,
+    ) -> crate::Result<(), ManageUserError>;
+}
+
+pub trait DeleteUser {
+    async fn delete_user(
+        &self,
+        admin_id: UserId,
+        user_id: UserId,
+    ) -> crate::Result<(), ManageUserError>;
+}
+
+pub trait GetManagedUsers {
+    async fn get_managed_users(&self, search: Option<String>) -> crate::Result<Vec<DbManagedUser>>;
+}
+
+pub trait UpdateUserRole {
+    async fn update_user_role(
+        &self,
+        user_id: DbUserId,
+        role: Role,
+    ) -> crate::Result<(), UpdateUserError>;
+}
+
+pub trait UpdateUserDisabled {
+    async fn update_user_disabled(
+        &self,
+        user_id: DbUserId,
+        is_disabled: bool,
+    ) -> crate::Result<(), UpdateUserError>;
+}
+
+pub trait RemoveUser {
+    async fn remove_user(&self, user_id: DbUserId) -> crate::Result<(), UpdateUserError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct RoleChange {
+    pub user: UserId,
+    pub role: Role,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedUser {
+    pub id: UserId,
+    pub email: Email,
+    pub name: Name,
+    pub role: Role,
+    pub is_verified: bool,
+    pub is_disabled: bool,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbManagedUser {
+    pub id: DbUserId,
+    pub email: Email,
+    pub name: Name,
+    pub role: Role,
+    pub is_verified: bool,
+    pub is_disabled: bool,
+}
+
+#[derive(Debug)]
+pub enum ManageUserError {
+    UnknownUser,
+    SelfModification,
+}
+
+#[derive(Debug)]
+pub enum UpdateUserError {
+    NotFound,
+}
+
+impl From<ManageUserError> for LocalizedError {
+    fn from(value: ManageUserError) -> Self {
+        match value {
+            ManageUserError::UnknownUser => Self::new("NOT_FOUND"),
+            ManageUserError::SelfModification => Self::new("SELF_MODIFICATION"),
+        }
+    }
+}
+
+impl From<UpdateUserError> for ManageUserError {
+    fn from(value: UpdateUserError) -> Self {
+        match value {
+            UpdateUserError::NotFound => Self::UnknownUser,
+        }
+    }
+}
+
+impl From<DecodeIdError> for ManageUserError {
+    fn from(value: DecodeIdError) -> Self {
+        match value {
+            DecodeIdError::InvalidFormat => Self::UnknownUser,
+        }
+    }
+}
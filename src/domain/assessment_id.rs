@@ -0,0 +1,36 @@
+use std::str::FromStr;
+
+use educe::Educe;
+use serde::Serialize;
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+#[derive(Educe, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, sqlx::Type)]
+#[educe(Into(i64))]
+#[sqlx(transparent)]
+pub struct AssessmentId(i64);
+
+impl FromStr for AssessmentId {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value.parse().map(Self)
+    }
+}
+
+impl TryFrom<String> for AssessmentId {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Validation::new(value)
+            .check_or_else(
+                |v| v.parse::<Self>().is_ok(),
+                || LocalizedError::new("INVALID_ASSESSMENT_ID"),
+            )
+            .finish()
+            .map(|v| v.parse().unwrap())
+    }
+}
@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum AssessmentKind {
+    Test,
+    Homework,
+    Exam,
+}
+
+impl AssessmentKind {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "test" => Some(Self::Test),
+            "homework" => Some(Self::Homework),
+            "exam" => Some(Self::Exam),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<String> for AssessmentKind {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Validation::new(value)
+            .check_or_else(
+                |v| Self::parse(v).is_some(),
+                || LocalizedError::new("INVALID_ASSESSMENT_KIND"),
+            )
+            .finish()
+            .map(|v| Self::parse(&v).unwrap())
+    }
+}
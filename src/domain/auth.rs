@@ -1,4 +1,7 @@
+use std::{net::IpAddr, time::Duration};
+
 use serde::Serialize;
+use unic_langid::LanguageIdentifier;
 
 use crate::app::localization::LocalizedError;
 
@@ -6,18 +9,72 @@ use crate::domain::{
     email::{Email, MaybeEmail},
     name::Name,
     password::{MaybePassword, Password, PasswordHash},
-    token::AuthToken,
+    throttle::Throttled,
+    token::{
+        AuthToken, RefreshToken, RefreshTokenHash, ResetToken, ResetTokenHash, SessionId, TokenId,
+        VerificationToken,
+    },
+    two_factor::TwoFactorPrompt,
     user_id::{DbUserId, UserId},
 };
 
 use super::role::Role;
 
 pub trait Signup {
-    async fn signup(&self, signup_data: SignupData) -> crate::Result<(), SignupError>;
+    async fn signup(
+        &self,
+        signup_data: SignupData,
+        locale: LanguageIdentifier,
+    ) -> crate::Result<(), SignupError>;
+}
+
+pub trait VerifyEmail {
+    async fn verify_email(&self, token: VerificationToken) -> crate::Result<(), VerifyEmailError>;
+}
+
+pub trait ResendVerification {
+    async fn resend_verification(&self, request: VerificationRequest) -> crate::Result<()>;
 }
 
 pub trait Login {
-    async fn login(&self, login_data: LoginData) -> crate::Result<AuthToken, LoginError>;
+    async fn login(&self, request: LoginRequest) -> crate::Result<LoginOutcome, LoginError>;
+}
+
+pub trait Refresh {
+    async fn refresh(&self, token: RefreshToken) -> crate::Result<AuthTokens, RefreshError>;
+}
+
+pub trait Logout {
+    async fn logout(&self, token: AuthToken) -> crate::Result<(), AuthError>;
+}
+
+pub trait ResumeSession {
+    async fn resume_session(
+        &self,
+        user_id: DbUserId,
+        session_id: SessionId,
+        refresh_token: IssuedRefreshToken,
+    ) -> crate::Result<AuthTokens>;
+}
+
+pub trait StartSession {
+    async fn start_session(
+        &self,
+        user_id: DbUserId,
+        session_id: SessionId,
+    ) -> crate::Result<AuthTokens>;
+}
+
+pub trait RequestPasswordReset {
+    async fn request_password_reset(&self, request: PasswordResetRequest) -> crate::Result<()>;
+}
+
+pub trait ResetPassword {
+    async fn reset_password(
+        &self,
+        token: ResetToken,
+        password: Password,
+    ) -> crate::Result<(), ResetPasswordError>;
 }
 
 pub trait Authenticate {
@@ -49,11 +106,95 @@ pub trait VerifyPassword {
 }
 
 pub trait IssueToken {
-    fn issue_token(&self, user_id: UserId) -> crate::Result<AuthToken>;
+    fn issue_token(&self, user_id: UserId, session_id: SessionId) -> crate::Result<AuthToken>;
 }
 
 pub trait ParseToken {
-    fn parse_token(&self, token: AuthToken) -> crate::Result<UserId, ParseTokenError>;
+    fn parse_token(&self, token: AuthToken) -> crate::Result<AuthClaims, ParseTokenError>;
+}
+
+pub trait IssueRefreshToken {
+    fn issue_refresh_token(&self) -> crate::Result<IssuedRefreshToken>;
+}
+
+pub trait RotateRefreshToken {
+    fn rotate_refresh_token(&self, token: &RefreshToken) -> IssuedRefreshToken;
+}
+
+pub trait HashRefreshToken {
+    fn hash_refresh_token(&self, token: &RefreshToken) -> RefreshTokenHash;
+}
+
+pub trait SaveRefreshToken {
+    async fn save_refresh_token(&self, token: NewRefreshToken) -> crate::Result<()>;
+}
+
+pub trait UseRefreshToken {
+    async fn use_refresh_token(
+        &self,
+        hash: RefreshTokenHash,
+    ) -> crate::Result<DbRefreshToken, UseRefreshTokenError>;
+}
+
+pub trait RevokeSession {
+    async fn revoke_session(&self, session_id: SessionId) -> crate::Result<()>;
+}
+
+pub trait RevokeToken {
+    async fn revoke_token(&self, token_id: TokenId, expires_at: u64) -> crate::Result<()>;
+}
+
+pub trait IsTokenRevoked {
+    async fn is_token_revoked(&self, token_id: TokenId) -> crate::Result<bool>;
+}
+
+pub trait IssueVerificationToken {
+    fn issue_verification_token(&self, email: &Email) -> crate::Result<VerificationToken>;
+}
+
+pub trait ParseVerificationToken {
+    fn parse_verification_token(
+        &self,
+        token: VerificationToken,
+    ) -> crate::Result<MaybeEmail, ParseTokenError>;
+}
+
+pub trait MarkEmailVerified {
+    async fn mark_email_verified(
+        &self,
+        email: &MaybeEmail,
+    ) -> crate::Result<(), MarkEmailVerifiedError>;
+}
+
+pub trait IssueResetToken {
+    fn issue_reset_token(&self) -> crate::Result<IssuedResetToken>;
+}
+
+pub trait HashResetToken {
+    fn hash_reset_token(&self, token: &ResetToken) -> ResetTokenHash;
+}
+
+pub trait SaveResetToken {
+    async fn save_reset_token(&self, token: NewResetToken) -> crate::Result<()>;
+}
+
+pub trait UseResetToken {
+    async fn use_reset_token(
+        &self,
+        hash: ResetTokenHash,
+    ) -> crate::Result<DbUserId, UseResetTokenError>;
+}
+
+pub trait UpdatePassword {
+    async fn update_password(
+        &self,
+        user_id: DbUserId,
+        password_hash: PasswordHash,
+    ) -> crate::Result<()>;
+}
+
+pub trait RevokeUserSessions {
+    async fn revoke_user_sessions(&self, user_id: DbUserId) -> crate::Result<()>;
 }
 
 pub trait EncodeUserId {
@@ -77,6 +218,29 @@ pub struct LoginData {
     pub password: MaybePassword,
 }
 
+#[derive(Debug, Clone)]
+pub struct LoginRequest {
+    pub data: LoginData,
+    pub ip: IpAddr,
+}
+
+#[derive(Debug, Clone)]
+pub struct VerificationRequest {
+    pub email: MaybeEmail,
+    pub locale: LanguageIdentifier,
+}
+
+#[derive(Debug, Clone)]
+pub struct PasswordResetRequest {
+    pub email: MaybeEmail,
+    pub locale: LanguageIdentifier,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewPassword {
+    pub password: Password,
+}
+
 #[derive(Debug, Clone)]
 pub struct NewUser {
     pub email: Email,
@@ -85,6 +249,76 @@ pub struct NewUser {
     pub role: Role,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthTokens {
+    pub access_token: AuthToken,
+    pub refresh_token: RefreshToken,
+}
+
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    Authenticated(AuthTokens),
+    TwoFactorRequired(TwoFactorPrompt),
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthClaims {
+    pub token_id: TokenId,
+    pub session_id: SessionId,
+    pub user_id: UserId,
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedRefreshToken {
+    pub token: RefreshToken,
+    pub hash: RefreshTokenHash,
+    pub ttl: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewRefreshToken {
+    pub hash: RefreshTokenHash,
+    pub user_id: DbUserId,
+    pub session_id: SessionId,
+    pub ttl: Duration,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbRefreshToken {
+    pub user_id: DbUserId,
+    pub session_id: SessionId,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedResetToken {
+    pub token: ResetToken,
+    pub hash: ResetTokenHash,
+    pub ttl: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewResetToken {
+    pub hash: ResetTokenHash,
+    pub user_id: DbUserId,
+    pub ttl: Duration,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailVerificationMail {
+    pub name: Name,
+    pub token: VerificationToken,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordResetMail {
+    pub name: Name,
+    pub token: ResetToken,
+    pub ttl_minutes: u64,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct User {
     pub id: UserId,
@@ -100,6 +334,8 @@ pub struct DbUser {
     pub name: Name,
     pub password_hash: PasswordHash,
     pub role: Role,
+    pub is_verified: bool,
+    pub is_disabled: bool,
 }
 
 #[derive(Debug)]
@@ -110,6 +346,24 @@ pub enum SignupError {
 #[derive(Debug)]
 pub enum LoginError {
     InvalidCredentials,
+    EmailNotVerified,
+    AccountDisabled,
+    TooManyAttempts(Duration),
+}
+
+#[derive(Debug)]
+pub enum VerifyEmailError {
+    InvalidToken,
+}
+
+#[derive(Debug)]
+pub enum RefreshError {
+    SessionExpired,
+}
+
+#[derive(Debug)]
+pub enum ResetPasswordError {
+    InvalidToken,
 }
 
 #[derive(Debug)]
@@ -127,6 +381,11 @@ pub enum FindUserError {
     NotFound,
 }
 
+#[derive(Debug)]
+pub enum MarkEmailVerifiedError {
+    NotFound,
+}
+
 #[derive(Debug)]
 pub enum VerifyPasswordError {
     InvalidPassword,
@@ -147,6 +406,17 @@ pub enum DecodeIdError {
     InvalidFormat,
 }
 
+#[derive(Debug)]
+pub enum UseRefreshTokenError {
+    NotFound,
+    Reused(SessionId),
+}
+
+#[derive(Debug)]
+pub enum UseResetTokenError {
+    NotFound,
+}
+
 impl From<SignupError> for LocalizedError {
     fn from(value: SignupError) -> Self {
         match value {
@@ -159,6 +429,51 @@ impl From<LoginError> for LocalizedError {
     fn from(value: LoginError) -> Self {
         match value {
             LoginError::InvalidCredentials => Self::new("INVALID_CREDENTIALS"),
+            LoginError::EmailNotVerified => Self::new("EMAIL_NOT_VERIFIED"),
+            LoginError::AccountDisabled => Self::new("ACCOUNT_DISABLED"),
+            LoginError::TooManyAttempts(retry_after) => {
+                Self::new("TOO_MANY_ATTEMPTS").with_number("seconds", retry_after.as_secs() as f64)
+            }
+        }
+    }
+}
+
+impl From<VerifyEmailError> for LocalizedError {
+    fn from(value: VerifyEmailError) -> Self {
+        match value {
+            VerifyEmailError::InvalidToken => Self::new("INVALID_VERIFICATION_TOKEN"),
+        }
+    }
+}
+
+impl From<ResetPasswordError> for LocalizedError {
+    fn from(value: ResetPasswordError) -> Self {
+        match value {
+            ResetPasswordError::InvalidToken => Self::new("INVALID_RESET_TOKEN"),
+        }
+    }
+}
+
+impl From<AuthError> for LocalizedError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::Unauthenticated => Self::new("UNAUTHORIZED"),
+        }
+    }
+}
+
+impl From<RefreshError> for LocalizedError {
+    fn from(value: RefreshError) -> Self {
+        match value {
+            RefreshError::SessionExpired => Self::new("SESSION_EXPIRED"),
+        }
+    }
+}
+
+impl From<UseResetTokenError> for ResetPasswordError {
+    fn from(value: UseResetTokenError) -> Self {
+        match value {
+            UseResetTokenError::NotFound => Self::InvalidToken,
         }
     }
 }
@@ -179,6 +494,12 @@ impl From<FindUserError> for LoginError {
     }
 }
 
+impl From<Throttled> for LoginError {
+    fn from(value: Throttled) -> Self {
+        Self::TooManyAttempts(value.retry_after)
+    }
+}
+
 impl From<VerifyPasswordError> for LoginError {
     fn from(value: VerifyPasswordError) -> Self {
         match value {
@@ -187,6 +508,22 @@ impl From<VerifyPasswordError> for LoginError {
     }
 }
 
+impl From<ParseTokenError> for VerifyEmailError {
+    fn from(value: ParseTokenError) -> Self {
+        match value {
+            ParseTokenError::Invalid => Self::InvalidToken,
+        }
+    }
+}
+
+impl From<MarkEmailVerifiedError> for VerifyEmailError {
+    fn from(value: MarkEmailVerifiedError) -> Self {
+        match value {
+            MarkEmailVerifiedError::NotFound => Self::InvalidToken,
+        }
+    }
+}
+
 impl From<GetUserError> for AuthError {
     fn from(value: GetUserError) -> Self {
         match value {
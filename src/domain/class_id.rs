@@ -0,0 +1,39 @@
+use educe::Educe;
+use serde::Serialize;
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+const MIN_LENGTH: usize = 1;
+const MAX_LENGTH: usize = 20;
+
+#[derive(Educe, Debug, Clone, Serialize, Hash, PartialEq, Eq, sqlx::Type)]
+#[educe(Into(String))]
+#[sqlx(transparent)]
+pub struct ClassId(String);
+
+impl ClassId {
+    pub fn new(id: String) -> Result<Self, ValidationFailure<String>> {
+        Validation::new(id)
+            .check_or_else(
+                |v| v.len() >= MIN_LENGTH,
+                || LocalizedError::new("CLASS_ID_TOO_SHORT").with_number("min", MIN_LENGTH as f64),
+            )
+            .check_or_else(
+                |v| v.len() <= MAX_LENGTH,
+                || LocalizedError::new("CLASS_ID_TOO_LONG").with_number("max", MAX_LENGTH as f64),
+            )
+            .finish()
+            .map(Self)
+    }
+}
+
+impl TryFrom<String> for ClassId {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
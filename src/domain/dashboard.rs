@@ -0,0 +1,80 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::app::localization::LocalizedError;
+
+use super::{
+    grade::Grade,
+    subject_id::SubjectId,
+    subject_title::SubjectTitle,
+    term_id::TermId,
+    term_name::TermName,
+    user_id::{DbUserId, UserId},
+};
+
+pub trait GetStudentDashboard {
+    async fn get_student_dashboard(
+        &self,
+        student_id: UserId,
+        term: Option<TermId>,
+    ) -> crate::Result<StudentDashboard>;
+}
+
+pub trait GetDbTermAverages {
+    async fn get_db_term_averages(&self, student_id: DbUserId) -> crate::Result<Vec<TermAverage>>;
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentDashboard {
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub average: Option<Decimal>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub class_average: Option<Decimal>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub average_z_score: Option<Decimal>,
+    pub subjects: Vec<SubjectComparison>,
+    pub trend: Vec<TermAverage>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubjectComparison {
+    pub subject_id: SubjectId,
+    pub subject_title: SubjectTitle,
+    pub grade: Grade,
+    pub scaled_value: Option<String>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub class_mean: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub difference: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub z_score: Decimal,
+    pub class_size: usize,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct TermAverage {
+    pub term_id: TermId,
+    pub term_name: TermName,
+    #[serde_as(as = "DisplayFromStr")]
+    pub average: Decimal,
+}
+
+#[derive(Debug)]
+pub enum DashboardError {
+    Forbidden,
+}
+
+impl From<DashboardError> for LocalizedError {
+    fn from(value: DashboardError) -> Self {
+        match value {
+            DashboardError::Forbidden => Self::new("FORBIDDEN"),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use serde::Serialize;
+
+use crate::app::localization::LocalizedError;
+
+use super::{
+    class_id::ClassId,
+    class_name::ClassName,
+    email::Email,
+    grade::Grade,
+    grade_status::GradeStatus,
+    grade_version::GradeVersion,
+    grading_scale::Gpa,
+    name::Name,
+    subject_id::SubjectId,
+    subject_title::SubjectTitle,
+    term_id::TermId,
+    user_id::{DbUserId, UserId},
+};
+
+pub trait ListClasses {
+    async fn list_classes(&self) -> crate::Result<Vec<ClassSummary>>;
+}
+
+pub trait CreateClass {
+    async fn create_class(&self, class: Class) -> crate::Result<(), EnrollmentError>;
+}
+
+pub trait DeleteClass {
+    async fn delete_class(&self, class_id: ClassId) -> crate::Result<(), EnrollmentError>;
+}
+
+pub trait GetClassRoster {
+    async fn get_class_roster(
+        &self,
+        class_id: ClassId,
+    ) -> crate::Result<ClassRoster, EnrollmentError>;
+}
+
+pub trait EnrollStudent {
+    async fn enroll_student(
+        &self,
+        class_id: ClassId,
+        student_id: UserId,
+    ) -> crate::Result<(), EnrollmentError>;
+}
+
+pub trait UnenrollStudent {
+    async fn unenroll_student(
+        &self,
+        class_id: ClassId,
+        student_id: UserId,
+    ) -> crate::Result<(), EnrollmentError>;
+}
+
+pub trait AssignSubject {
+    async fn assign_subject(
+        &self,
+        class_id: ClassId,
+        subject_id: SubjectId,
+    ) -> crate::Result<(), EnrollmentError>;
+}
+
+pub trait UnassignSubject {
+    async fn unassign_subject(
+        &self,
+        class_id: ClassId,
+        subject_id: SubjectId,
+    ) -> crate::Result<(), EnrollmentError>;
+}
+
+pub trait GetRosterGrades {
+    async fn get_roster_grades(
+        &self,
+        subject: Option<SubjectId>,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<RosterGrade>>;
+}
+
+pub trait GetDbClass {
+    async fn get_db_class(&self, class_id: &ClassId) -> crate::Result<Class, EnrollmentError>;
+}
+
+pub trait GetDbClassStudents {
+    async fn get_db_class_students(&self, class_id: &ClassId)
+        -> crate::Result<Vec<DbClassStudent>>;
+}
+
+pub trait GetClassSubjects {
+    async fn get_class_subjects(&self, class_id: &ClassId) -> crate::Result<Vec<ClassSubject>>;
+}
+
+pub trait SaveClassStudent {
+    async fn save_class_student(
+        &self,
+        class_id: ClassId,
+        student_id: DbUserId,
+    ) -> crate::Result<(), EnrollmentError>;
+}
+
+pub trait RemoveClassStudent {
+    async fn remove_class_student(
+        &self,
+        class_id: ClassId,
+        student_id: DbUserId,
+    ) -> crate::Result<(), EnrollmentError>;
+}
+
+pub trait GetDbRosterGrades {
+    async fn get_db_roster_grades(
+        &self,
+        subject: Option<SubjectId>,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<DbRosterGrade>>;
+}
+
+pub trait GetDbEnrollments {
+    async fn get_db_enrollments(&self, students: Vec<DbUserId>)
+        -> crate::Result<Vec<DbEnrollment>>;
+}
+
+pub trait GetDbClassmates {
+    async fn get_db_classmates(&self, student: DbUserId) -> crate::Result<Vec<DbEnrollment>>;
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Class {
+    pub id: ClassId,
+    pub name: ClassName,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassSummary {
+    pub id: ClassId,
+    pub name: ClassName,
+    pub student_count: i64,
+    pub subject_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassRoster {
+    pub class: Class,
+    pub students: Vec<ClassStudent>,
+    pub subjects: Vec<ClassSubject>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassStudent {
+    pub id: UserId,
+    pub name: Name,
+    pub email: Email,
+    pub is_enrolled: bool,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbClassStudent {
+    pub id: DbUserId,
+    pub name: Name,
+    pub email: Email,
+    pub is_enrolled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassSubject {
+    pub id: SubjectId,
+    pub title: SubjectTitle,
+    pub is_assigned: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterGrade {
+    pub student_id: UserId,
+    pub student_name: Name,
+    pub grade: Option<Grade>,
+    pub scaled_value: Option<String>,
+    pub gpa: Option<Gpa>,
+    pub status: GradeStatus,
+    pub version: Option<GradeVersion>,
+    pub subject_id: SubjectId,
+    pub subject_title: SubjectTitle,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbRosterGrade {
+    pub student_id: DbUserId,
+    pub student_name: Name,
+    pub grade: Option<Grade>,
+    pub scaled_value: Option<String>,
+    pub gpa: Option<Gpa>,
+    pub status: GradeStatus,
+    pub version: Option<GradeVersion>,
+    pub subject_id: SubjectId,
+    pub subject_title: SubjectTitle,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbEnrollment {
+    pub student_id: DbUserId,
+    pub subject_id: SubjectId,
+}
+
+#[derive(Debug)]
+pub enum EnrollmentError {
+    Forbidden,
+    NotFound,
+    AlreadyExists,
+}
+
+impl From<EnrollmentError> for LocalizedError {
+    fn from(value: EnrollmentError) -> Self {
+        match value {
+            EnrollmentError::Forbidden => Self::new("FORBIDDEN"),
+            EnrollmentError::NotFound => Self::new("NOT_FOUND"),
+            EnrollmentError::AlreadyExists => Self::new("CLASS_EXISTS"),
+        }
+    }
+}
@@ -1,68 +1,74 @@
+use educe::Educe;
 use rust_decimal::Decimal;
 use serde::Serialize;
 use serde_with::{serde_as, DisplayFromStr};
 
-use crate::app::{
-    localization::LocalizedError,
-    validation::{Validation, ValidationFailure},
-};
+use crate::app::validation::ValidationFailure;
 
-const MIN_INTEGER: i64 = 0;
-const MAX_INTEGER: i64 = 100;
-const FRACTION_DIGITS: u32 = 2;
+use super::grading_scale::GradingScale;
 
-const MIN_FRACTION: i64 = 0;
-const MAX_FRACTION: i64 = 10i64.pow(FRACTION_DIGITS) - 1;
+const MIN_VALUE: i64 = 0;
+const MAX_VALUE: i64 = 100;
+const FRACTION_DIGITS: u32 = 2;
 
 #[serde_as]
 #[derive(Debug, Clone, Copy, Serialize, sqlx::Type)]
 #[sqlx(transparent)]
 pub struct Grade(#[serde_as(as = "DisplayFromStr")] Decimal);
 
+#[derive(Educe, Debug, Clone)]
+#[educe(Into(String))]
+pub struct RawGrade(String);
+
 impl Grade {
     pub fn new(grade: String) -> Result<Self, ValidationFailure<String>> {
-        match Self::parse(&grade) {
-            Some(grade) => Ok(grade),
-            None => Validation::new(grade)
-                .error(
-                    LocalizedError::new("GRADE_INVALID_FORMAT")
-                        .with_number("min", MIN_INTEGER as f64)
-                        .with_number("max", MAX_INTEGER as f64)
-                        .with_number("fractionDigits", FRACTION_DIGITS as f64),
-                )
-                .finish()
-                .map(|_| unreachable!()),
-        }
+        GradingScale::Percent
+            .grade(grade)
+            .map(|scaled| scaled.grade)
+    }
+
+    pub(super) fn parse(grade: &str) -> Option<Self> {
+        parse_fixed(grade, MIN_VALUE, MAX_VALUE, FRACTION_DIGITS).map(Self)
+    }
+
+    pub(super) fn from_canonical(value: Decimal) -> Self {
+        let mut value = value
+            .round_dp(FRACTION_DIGITS)
+            .clamp(Decimal::from(MIN_VALUE), Decimal::from(MAX_VALUE));
+        value.rescale(FRACTION_DIGITS);
+        Self(value)
     }
+}
 
-    fn parse(grade: &str) -> Option<Self> {
-        let (integer, fraction) = match grade.split_once('.') {
-            Some((integer, fraction)) => {
-                let integer = integer.parse::<i64>();
-                let fraction = if fraction.len() == 1 {
-                    fraction.parse::<i64>().map(|x| x * 10)
-                } else {
-                    fraction.parse::<i64>()
-                };
-                integer.and_then(|integer| fraction.map(|fraction| (integer, fraction)))
-            }
-            None => {
-                let integer = grade.parse::<i64>();
-                integer.map(|integer| (integer, 0))
-            }
-        }
-        .ok()?;
-        let (integer, fraction) = match (integer, fraction) {
-            (integer @ MAX_INTEGER, fraction @ MIN_FRACTION)
-            | (integer @ MIN_INTEGER..MAX_INTEGER, fraction @ MIN_FRACTION..=MAX_FRACTION) => {
-                Some((integer, fraction))
-            }
-            _ => None,
-        }?;
-        let shifted = integer * 10i64.pow(FRACTION_DIGITS) + fraction;
-        let decimal = Decimal::new(shifted, FRACTION_DIGITS);
-        Some(Self(decimal))
+pub(super) fn parse_fixed(
+    value: &str,
+    min: i64,
+    max: i64,
+    fraction_digits: u32,
+) -> Option<Decimal> {
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if integer.is_empty()
+        || !is_digits(integer)
+        || !is_digits(fraction)
+        || fraction.len() > fraction_digits as usize
+        || (value.contains('.') && fraction.is_empty())
+    {
+        return None;
     }
+    let integer = integer.parse::<i64>().ok()?;
+    let fraction = format!("{fraction:0<width$}", width = fraction_digits as usize);
+    let fraction = match fraction.is_empty() {
+        true => 0,
+        false => fraction.parse::<i64>().ok()?,
+    };
+    let shifted = integer
+        .checked_mul(10i64.pow(fraction_digits))?
+        .checked_add(fraction)?;
+    let decimal = Decimal::new(shifted, fraction_digits);
+    (Decimal::from(min)..=Decimal::from(max))
+        .contains(&decimal)
+        .then_some(decimal)
 }
 
 impl From<Grade> for Decimal {
@@ -78,3 +84,11 @@ impl TryFrom<String> for Grade {
         Self::new(value)
     }
 }
+
+impl TryFrom<String> for RawGrade {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(value.trim().to_owned()))
+    }
+}
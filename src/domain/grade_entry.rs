@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::{localization::LocalizedError, validation::ValidationErrors};
+
+use super::{
+    grade_status::GradeStatus,
+    grades::DbGradeChange,
+    user_id::{DbUserId, UserId},
+};
+
+pub trait EnterGrades {
+    async fn enter_grades(
+        &self,
+        entry: GradeEntry,
+    ) -> crate::Result<GradeEntryOutcome, GradeEntryError>;
+}
+
+pub trait SaveDbGrades {
+    async fn save_db_grades(
+        &self,
+        actor: DbUserId,
+        changes: Vec<DbGradeChange>,
+    ) -> crate::Result<Vec<SavedGrade>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct GradeEntry {
+    pub actor: UserId,
+    pub rows: Vec<GradeEntryRow>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GradeEntryRow {
+    pub student: String,
+    pub subject: String,
+    pub grade: String,
+    pub status: String,
+    #[serde(default)]
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradeEntryResult {
+    #[serde(flatten)]
+    pub row: GradeEntryRow,
+    pub errors: ValidationErrors,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradeEntryOutcome {
+    pub rows: Vec<GradeEntryResult>,
+    pub saved_count: usize,
+    pub invalid_count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum SavedGrade {
+    Saved,
+    Unchanged,
+    Conflict {
+        status: GradeStatus,
+        current: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+pub enum GradeEntryError {
+    Forbidden,
+}
+
+impl From<GradeEntryError> for LocalizedError {
+    fn from(value: GradeEntryError) -> Self {
+        match value {
+            GradeEntryError::Forbidden => Self::new("FORBIDDEN"),
+        }
+    }
+}
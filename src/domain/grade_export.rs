@@ -0,0 +1,127 @@
+use std::str::FromStr;
+
+use unic_langid::LanguageIdentifier;
+
+use crate::app::localization::LocalizedError;
+
+use super::{
+    enrollment::RosterGrade, grade_status::GradeStatus, grades::GradeRecord, name::Name,
+    subject_id::SubjectId, subject_title::SubjectTitle, term_id::TermId, user_id::UserId,
+};
+
+pub trait ExportGrades {
+    async fn export_grades(
+        &self,
+        subject: Option<SubjectId>,
+        term: Option<TermId>,
+        format: ExportFormat,
+        locale: LanguageIdentifier,
+    ) -> crate::Result<GradeExport>;
+}
+
+pub trait LocalizeText {
+    fn localize_text(&self, locale: &LanguageIdentifier, key: &str) -> crate::Result<String>;
+}
+
+pub trait WriteSpreadsheet {
+    fn write_spreadsheet(&self, sheet: &GradeSheet, format: ExportFormat)
+        -> crate::Result<Vec<u8>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+#[derive(Debug, Clone)]
+pub struct GradeSheet {
+    pub columns: Vec<String>,
+    pub rows: Vec<GradeSheetRow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GradeSheetRow {
+    pub student_id: UserId,
+    pub student_name: Name,
+    pub subject_id: SubjectId,
+    pub subject_title: SubjectTitle,
+    pub grade: Option<String>,
+    pub status: GradeStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct GradeExport {
+    pub subject: Option<SubjectId>,
+    pub format: ExportFormat,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum GradeExportError {
+    Forbidden,
+    UnsupportedFormat,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = GradeExportError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "xlsx" => Ok(Self::Xlsx),
+            _ => Err(GradeExportError::UnsupportedFormat),
+        }
+    }
+}
+
+impl From<GradeRecord> for GradeSheetRow {
+    fn from(value: GradeRecord) -> Self {
+        Self {
+            student_id: value.student_id,
+            student_name: value.student_name,
+            subject_id: value.subject_id,
+            subject_title: value.subject_title,
+            grade: value.scaled_value,
+            status: value.status,
+        }
+    }
+}
+
+impl From<RosterGrade> for GradeSheetRow {
+    fn from(value: RosterGrade) -> Self {
+        Self {
+            student_id: value.student_id,
+            student_name: value.student_name,
+            subject_id: value.subject_id,
+            subject_title: value.subject_title,
+            grade: value.scaled_value,
+            status: value.status,
+        }
+    }
+}
+
+impl From<GradeExportError> for LocalizedError {
+    fn from(value: GradeExportError) -> Self {
+        match value {
+            GradeExportError::Forbidden => Self::new("FORBIDDEN"),
+            GradeExportError::UnsupportedFormat => Self::new("UNSUPPORTED_EXPORT_FORMAT"),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use serde::Serialize;
+
+use crate::app::{localization::LocalizedError, validation::ValidationErrors};
+
+use super::{
+    email::Email,
+    user_id::{DbUserId, UserId},
+};
+
+pub trait ImportGrades {
+    async fn import_grades(
+        &self,
+        import: GradeImport,
+    ) -> crate::Result<GradeImportOutcome, GradeImportError>;
+}
+
+pub trait GetImportStudents {
+    async fn get_import_students(
+        &self,
+        emails: Vec<String>,
+        ids: Vec<DbUserId>,
+    ) -> crate::Result<Vec<ImportStudent>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct GradeImport {
+    pub actor: UserId,
+    pub rows: Vec<GradeImportRow>,
+    pub content: String,
+    pub confirm: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GradeImportRow {
+    pub line: u64,
+    pub student: String,
+    pub subject: String,
+    pub grade: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradeImportPreviewRow {
+    #[serde(flatten)]
+    pub row: GradeImportRow,
+    pub errors: ValidationErrors,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradeImportPreview {
+    pub rows: Vec<GradeImportPreviewRow>,
+    pub valid_count: usize,
+    pub invalid_count: usize,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GradeImportOutcome {
+    Preview(GradeImportPreview),
+    Applied { count: usize },
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ImportStudent {
+    pub id: DbUserId,
+    pub email: Email,
+}
+
+#[derive(Debug)]
+pub enum GradeImportError {
+    Forbidden,
+    InvalidFile,
+    MissingColumns,
+}
+
+impl From<GradeImportError> for LocalizedError {
+    fn from(value: GradeImportError) -> Self {
+        match value {
+            GradeImportError::Forbidden => Self::new("FORBIDDEN"),
+            GradeImportError::InvalidFile => Self::new("INVALID_IMPORT_FILE"),
+            GradeImportError::MissingColumns => Self::new("IMPORT_MISSING_COLUMNS"),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use serde::Serialize;
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GradeSort {
+    #[default]
+    Student,
+    Subject,
+    Grade,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl GradeSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Student => "student",
+            Self::Subject => "subject",
+            Self::Grade => "grade",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "student" => Some(Self::Student),
+            "subject" => Some(Self::Subject),
+            "grade" => Some(Self::Grade),
+            _ => None,
+        }
+    }
+}
+
+impl SortOrder {
+    pub fn is_desc(self) -> bool {
+        self == Self::Desc
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "asc" => Some(Self::Asc),
+            "desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<String> for GradeSort {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Validation::new(value)
+            .check_or_else(
+                |v| Self::parse(v).is_some(),
+                || LocalizedError::new("INVALID_GRADE_SORT"),
+            )
+            .finish()
+            .map(|v| Self::parse(&v).unwrap())
+    }
+}
+
+impl TryFrom<String> for SortOrder {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Validation::new(value)
+            .check_or_else(
+                |v| Self::parse(v).is_some(),
+                || LocalizedError::new("INVALID_SORT_ORDER"),
+            )
+            .finish()
+            .map(|v| Self::parse(&v).unwrap())
+    }
+}
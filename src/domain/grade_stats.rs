@@ -0,0 +1,53 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
+
+use super::{subject_id::SubjectId, subject_title::SubjectTitle, term_id::TermId};
+
+pub trait GetGradeStatistics {
+    async fn get_grade_statistics(
+        &self,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<SubjectStatistics>>;
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubjectStatistics {
+    pub subject_id: SubjectId,
+    pub subject_title: SubjectTitle,
+    #[serde(flatten)]
+    pub summary: Summary,
+    pub histogram: Vec<HistogramBin>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub count: usize,
+    #[serde_as(as = "DisplayFromStr")]
+    pub mean: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub median: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub std_deviation: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub min: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub max: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub lower_quartile: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub upper_quartile: Decimal,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+pub struct HistogramBin {
+    #[serde_as(as = "DisplayFromStr")]
+    pub from: Decimal,
+    #[serde_as(as = "DisplayFromStr")]
+    pub to: Decimal,
+    pub count: usize,
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum GradeStatus {
+    #[default]
+    Graded,
+    Ungraded,
+    Excused,
+    Absent,
+    Retracted,
+}
+
+impl GradeStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Graded => "graded",
+            Self::Ungraded => "ungraded",
+            Self::Excused => "excused",
+            Self::Absent => "absent",
+            Self::Retracted => "retracted",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "graded" => Some(Self::Graded),
+            "ungraded" => Some(Self::Ungraded),
+            "excused" => Some(Self::Excused),
+            "absent" => Some(Self::Absent),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<String> for GradeStatus {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Validation::new(value)
+            .check_or_else(
+                |v| Self::parse(v).is_some(),
+                || LocalizedError::new("INVALID_GRADE_STATUS"),
+            )
+            .finish()
+            .map(|v| Self::parse(&v).unwrap())
+    }
+}
@@ -0,0 +1,58 @@
+use std::str::FromStr;
+
+use educe::Educe;
+use serde::Serialize;
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+#[derive(Educe, Debug, Clone, Copy, PartialEq, Eq, Serialize, sqlx::Type)]
+#[educe(Into(i64))]
+#[sqlx(transparent)]
+pub struct GradeVersion(i64);
+
+#[derive(Debug, Clone, Default)]
+pub struct OptionalGradeVersion(Option<GradeVersion>);
+
+impl FromStr for GradeVersion {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value.parse().map(Self)
+    }
+}
+
+impl TryFrom<String> for GradeVersion {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Validation::new(value)
+            .check_or_else(
+                |v| v.parse::<Self>().is_ok(),
+                || LocalizedError::new("INVALID_GRADE_VERSION"),
+            )
+            .finish()
+            .map(|v| v.parse().unwrap())
+    }
+}
+
+impl TryFrom<String> for OptionalGradeVersion {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.trim() {
+            "" => Ok(Self(None)),
+            version => GradeVersion::try_from(version.to_owned())
+                .map(Some)
+                .map(Self),
+        }
+    }
+}
+
+impl From<OptionalGradeVersion> for Option<GradeVersion> {
+    fn from(value: OptionalGradeVersion) -> Self {
+        value.0
+    }
+}
@@ -1,20 +1,75 @@
+use rust_decimal::Decimal;
 use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
 
 use crate::app::localization::LocalizedError;
 
 use super::{
+    assessment_id::AssessmentId,
+    assessment_kind::AssessmentKind,
+    assessment_title::AssessmentTitle,
     email::Email,
+    enrollment::{DbRosterGrade, RosterGrade},
     grade::Grade,
+    grade_sort::{GradeSort, SortOrder},
+    grade_status::GradeStatus,
+    grade_version::GradeVersion,
+    grading_scale::{Gpa, GradingScale, ScaledGrade},
     name::Name,
+    reason::Reason,
     subject_id::SubjectId,
     subject_title::SubjectTitle,
+    term_id::TermId,
     user_id::{DbUserId, UserId},
+    weight::Weight,
 };
 
 pub trait GetSubjects {
     async fn get_subjects(&self) -> crate::Result<Vec<Subject>>;
 }
 
+pub trait GetManagedSubjects {
+    async fn get_managed_subjects(&self) -> crate::Result<Vec<ManagedSubject>>;
+}
+
+pub trait CreateSubject {
+    async fn create_subject(&self, subject: NewSubject) -> crate::Result<(), ManageSubjectError>;
+}
+
+pub trait RenameSubject {
+    async fn rename_subject(&self, subject: Subject) -> crate::Result<(), ManageSubjectError>;
+}
+
+pub trait SetSubjectArchived {
+    async fn set_subject_archived(
+        &self,
+        subject_id: SubjectId,
+        is_archived: bool,
+    ) -> crate::Result<(), ManageSubjectError>;
+}
+
+pub trait SetGradingScale {
+    async fn set_grading_scale(
+        &self,
+        subject: SubjectScale,
+    ) -> crate::Result<(), ManageSubjectError>;
+}
+
+pub trait GetGradingScale {
+    async fn get_grading_scale(
+        &self,
+        subject_id: &SubjectId,
+    ) -> crate::Result<Option<GradingScale>>;
+}
+
+pub trait GetGradingScales {
+    async fn get_grading_scales(&self) -> crate::Result<Vec<SubjectScale>>;
+}
+
+pub trait DeleteSubject {
+    async fn delete_subject(&self, subject_id: SubjectId) -> crate::Result<(), ManageSubjectError>;
+}
+
 pub trait GetGrade {
     async fn get_grade(
         &self,
@@ -32,38 +87,180 @@ pub trait GetDbGrade {
 }
 
 pub trait GetStudentGrades {
-    async fn get_student_grades(&self, student_id: UserId) -> crate::Result<Vec<StudentGrade>>;
+    async fn get_student_grades(
+        &self,
+        student_id: UserId,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<StudentGrade>>;
 }
 
 pub trait GetGrades {
-    async fn get_grades(&self, subject: Option<SubjectId>) -> crate::Result<Vec<GradeRecord>>;
+    async fn get_grades(
+        &self,
+        subject: Option<SubjectId>,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<GradeRecord>>;
 }
 
 pub trait GetDbStudentGrades {
-    async fn get_db_student_grades(&self, student_id: DbUserId)
-        -> crate::Result<Vec<StudentGrade>>;
+    async fn get_db_student_grades(
+        &self,
+        student_id: DbUserId,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<StudentGrade>>;
 }
 
 pub trait GetDbGrades {
-    async fn get_db_grades(&self, subject: Option<SubjectId>) -> crate::Result<Vec<DbGradeRecord>>;
+    async fn get_db_grades(
+        &self,
+        subject: Option<SubjectId>,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<DbGradeRecord>>;
+}
+
+pub trait GetGradePage {
+    async fn get_grade_page(&self, query: GradeQuery) -> crate::Result<GradePage>;
+}
+
+pub trait GetFilteredGrades {
+    async fn get_filtered_grades(&self, query: GradeQuery) -> crate::Result<Vec<RosterGrade>>;
+}
+
+pub trait GetDbGradePage {
+    async fn get_db_grade_page(
+        &self,
+        query: DbGradeQuery,
+        limit: Option<i64>,
+    ) -> crate::Result<Vec<DbGradePageRow>>;
 }
 
 pub trait UpdateGrade {
     async fn update_grade(
         &self,
-        subject: SubjectId,
-        student: UserId,
-        grade: Grade,
-    ) -> crate::Result<GradeRecord>;
+        actor: UserId,
+        change: GradeChange,
+    ) -> crate::Result<GradeRecord, UpdateGradeError>;
 }
 
 pub trait UpdateDbGrade {
     async fn update_db_grade(
         &self,
-        subject: SubjectId,
-        student: DbUserId,
-        grade: Grade,
-    ) -> crate::Result<()>;
+        actor: DbUserId,
+        change: DbGradeChange,
+    ) -> crate::Result<(), UpdateGradeError>;
+}
+
+pub trait RemoveGrade {
+    async fn remove_grade(
+        &self,
+        actor: UserId,
+        removal: GradeRemoval,
+    ) -> crate::Result<GradeRecord, RemoveGradeError>;
+}
+
+pub trait RemoveDbGrade {
+    async fn remove_db_grade(
+        &self,
+        actor: DbUserId,
+        removal: DbGradeRemoval,
+    ) -> crate::Result<(), RemoveGradeError>;
+}
+
+pub trait GetGradeHistory {
+    async fn get_grade_history(
+        &self,
+        subject_id: SubjectId,
+        student_id: UserId,
+    ) -> crate::Result<GradeHistory, GetGradeError>;
+}
+
+pub trait GetDbGradeEvents {
+    async fn get_db_grade_events(
+        &self,
+        subject_id: SubjectId,
+        student_id: DbUserId,
+    ) -> crate::Result<Vec<GradeEvent>>;
+}
+
+pub trait GetGradebook {
+    async fn get_gradebook(
+        &self,
+        subject_id: SubjectId,
+    ) -> crate::Result<Gradebook, AssessmentError>;
+}
+
+pub trait CreateAssessment {
+    async fn create_assessment(
+        &self,
+        assessment: NewAssessment,
+    ) -> crate::Result<(), AssessmentError>;
+}
+
+pub trait DeleteAssessment {
+    async fn delete_assessment(
+        &self,
+        actor: UserId,
+        subject_id: SubjectId,
+        assessment_id: AssessmentId,
+    ) -> crate::Result<(), AssessmentError>;
+}
+
+pub trait SaveAssessmentScore {
+    async fn save_assessment_score(
+        &self,
+        actor: UserId,
+        score: AssessmentScore,
+    ) -> crate::Result<(), AssessmentError>;
+}
+
+pub trait GetDbSubject {
+    async fn get_db_subject(
+        &self,
+        subject_id: &SubjectId,
+    ) -> crate::Result<Subject, AssessmentError>;
+}
+
+pub trait GetAssessments {
+    async fn get_assessments(&self, subject_id: &SubjectId) -> crate::Result<Vec<Assessment>>;
+}
+
+pub trait GetDbGradebookStudents {
+    async fn get_db_gradebook_students(
+        &self,
+        subject_id: &SubjectId,
+    ) -> crate::Result<Vec<DbGradebookStudent>>;
+}
+
+pub trait GetDbAssessmentScores {
+    async fn get_db_assessment_scores(
+        &self,
+        subject_id: &SubjectId,
+    ) -> crate::Result<Vec<DbAssessmentScore>>;
+}
+
+pub trait RemoveAssessment {
+    async fn remove_assessment(
+        &self,
+        actor: DbUserId,
+        subject_id: SubjectId,
+        assessment_id: AssessmentId,
+    ) -> crate::Result<(), AssessmentError>;
+}
+
+pub trait SaveDbAssessmentScore {
+    async fn save_db_assessment_score(
+        &self,
+        actor: DbUserId,
+        score: DbAssessmentScore,
+    ) -> crate::Result<(), AssessmentError>;
+}
+
+pub trait GetDbStudentAssessments {
+    async fn get_db_student_assessments(
+        &self,
+        student_id: DbUserId,
+        term: Option<TermId>,
+    ) -> crate::Result<Vec<StudentAssessment>>;
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, sqlx::FromRow)]
@@ -72,12 +269,108 @@ pub struct Subject {
     pub title: SubjectTitle,
 }
 
+#[derive(Debug, Clone)]
+pub struct NewSubject {
+    pub id: SubjectId,
+    pub title: SubjectTitle,
+    pub grading_scale: GradingScale,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SubjectScale {
+    pub id: SubjectId,
+    pub grading_scale: GradingScale,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedSubject {
+    pub id: SubjectId,
+    pub title: SubjectTitle,
+    pub grading_scale: GradingScale,
+    pub is_archived: bool,
+    pub grade_count: i64,
+}
+
 #[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct StudentGrade {
-    pub grade: Grade,
+    pub grade: Option<Grade>,
+    pub scaled_value: Option<String>,
+    pub gpa: Option<Gpa>,
+    pub status: GradeStatus,
     pub subject_id: SubjectId,
     pub subject_title: SubjectTitle,
+    #[sqlx(skip)]
+    pub assessments: Vec<StudentAssessment>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Assessment {
+    pub id: AssessmentId,
+    pub subject_id: SubjectId,
+    pub title: AssessmentTitle,
+    pub kind: AssessmentKind,
+    pub weight: Weight,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAssessment {
+    pub subject_id: SubjectId,
+    pub title: AssessmentTitle,
+    pub kind: AssessmentKind,
+    pub weight: Weight,
+}
+
+#[derive(Debug, Clone)]
+pub struct AssessmentScore {
+    pub subject_id: SubjectId,
+    pub assessment_id: AssessmentId,
+    pub student_id: UserId,
+    pub score: Grade,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbAssessmentScore {
+    pub subject_id: SubjectId,
+    pub assessment_id: AssessmentId,
+    pub student_id: DbUserId,
+    pub score: Grade,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentAssessment {
+    pub subject_id: SubjectId,
+    pub title: AssessmentTitle,
+    pub kind: AssessmentKind,
+    pub weight: Weight,
+    pub score: Option<Grade>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Gradebook {
+    pub subject: Subject,
+    pub assessments: Vec<Assessment>,
+    pub students: Vec<GradebookStudent>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradebookStudent {
+    pub id: UserId,
+    pub name: Name,
+    pub scores: Vec<Option<Grade>>,
+    pub final_grade: Option<Grade>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbGradebookStudent {
+    pub id: DbUserId,
+    pub name: Name,
+    pub final_grade: Option<Grade>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -85,7 +378,11 @@ pub struct StudentGrade {
 pub struct GradeRecord {
     pub student_id: UserId,
     pub student_name: Name,
-    pub grade: Grade,
+    pub grade: Option<Grade>,
+    pub scaled_value: Option<String>,
+    pub gpa: Option<Gpa>,
+    pub status: GradeStatus,
+    pub version: GradeVersion,
     pub subject_id: SubjectId,
     pub subject_title: SubjectTitle,
 }
@@ -94,11 +391,124 @@ pub struct GradeRecord {
 pub struct DbGradeRecord {
     pub student_id: DbUserId,
     pub student_name: Name,
-    pub grade: Grade,
+    pub grade: Option<Grade>,
+    pub scaled_value: Option<String>,
+    pub gpa: Option<Gpa>,
+    pub status: GradeStatus,
+    pub version: GradeVersion,
     pub subject_id: SubjectId,
     pub subject_title: SubjectTitle,
 }
 
+#[derive(Debug, Clone)]
+pub struct GradeQuery {
+    pub subject: Option<SubjectId>,
+    pub term: Option<TermId>,
+    pub search: Option<String>,
+    pub sort: GradeSort,
+    pub order: SortOrder,
+    pub after: Option<GradeCursor>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DbGradeQuery {
+    pub subject: Option<SubjectId>,
+    pub term: Option<TermId>,
+    pub search: Option<String>,
+    pub sort: GradeSort,
+    pub order: SortOrder,
+    pub after: Option<DbGradeCursor>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GradeCursor {
+    #[serde_as(as = "DisplayFromStr")]
+    pub grade_key: Decimal,
+    pub primary_key: String,
+    pub secondary_key: String,
+    pub student: UserId,
+    pub subject: SubjectId,
+}
+
+#[derive(Debug, Clone)]
+pub struct DbGradeCursor {
+    pub grade_key: Decimal,
+    pub primary_key: String,
+    pub secondary_key: String,
+    pub student: DbUserId,
+    pub subject: SubjectId,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbGradePageRow {
+    #[sqlx(flatten)]
+    pub grade: DbRosterGrade,
+    pub grade_key: Decimal,
+    pub primary_key: String,
+    pub secondary_key: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GradePage {
+    pub grades: Vec<RosterGrade>,
+    pub next: Option<GradeCursor>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GradeChange {
+    pub subject: SubjectId,
+    pub student: UserId,
+    pub grade: Option<ScaledGrade>,
+    pub status: GradeStatus,
+    pub version: Option<GradeVersion>,
+    pub reason: Option<Reason>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DbGradeChange {
+    pub subject: SubjectId,
+    pub student: DbUserId,
+    pub grade: Option<ScaledGrade>,
+    pub status: GradeStatus,
+    pub version: Option<GradeVersion>,
+    pub reason: Option<Reason>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GradeRemoval {
+    pub subject: SubjectId,
+    pub student: UserId,
+    pub version: Option<GradeVersion>,
+    pub reason: Reason,
+}
+
+#[derive(Debug, Clone)]
+pub struct DbGradeRemoval {
+    pub subject: SubjectId,
+    pub student: DbUserId,
+    pub version: Option<GradeVersion>,
+    pub reason: Reason,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct GradeEvent {
+    pub actor_name: Option<Name>,
+    pub old_value: Option<Grade>,
+    pub new_value: Option<Grade>,
+    pub status: GradeStatus,
+    pub reason: Option<Reason>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GradeHistory {
+    pub grade: GradeRecord,
+    pub events: Vec<GradeEvent>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Student {
     pub id: UserId,
@@ -111,6 +521,43 @@ pub enum GetGradeError {
     NotFound,
 }
 
+#[derive(Debug)]
+pub enum UpdateGradeError {
+    Forbidden,
+    NotFound,
+    NotEnrolled,
+    Computed,
+    Conflict {
+        status: GradeStatus,
+        current: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+pub enum RemoveGradeError {
+    Forbidden,
+    NotFound,
+    Conflict {
+        status: GradeStatus,
+        current: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+pub enum AssessmentError {
+    Forbidden,
+    NotFound,
+    GradeLocked(GradeStatus),
+}
+
+#[derive(Debug)]
+pub enum ManageSubjectError {
+    Forbidden,
+    NotFound,
+    AlreadyExists,
+    HasGrades,
+}
+
 impl From<GetGradeError> for LocalizedError {
     fn from(value: GetGradeError) -> Self {
         match value {
@@ -118,3 +565,56 @@ impl From<GetGradeError> for LocalizedError {
         }
     }
 }
+
+impl From<UpdateGradeError> for LocalizedError {
+    fn from(value: UpdateGradeError) -> Self {
+        match value {
+            UpdateGradeError::Forbidden => Self::new("FORBIDDEN"),
+            UpdateGradeError::NotFound => Self::new("NOT_FOUND"),
+            UpdateGradeError::NotEnrolled => Self::new("NOT_ENROLLED"),
+            UpdateGradeError::Computed => Self::new("GRADE_COMPUTED"),
+            UpdateGradeError::Conflict { status, current } => {
+                let error = Self::new("GRADE_CONFLICT").with_text("status", status.as_str());
+                match current {
+                    Some(current) => error.with_text("current", current),
+                    None => error,
+                }
+            }
+        }
+    }
+}
+
+impl From<RemoveGradeError> for LocalizedError {
+    fn from(value: RemoveGradeError) -> Self {
+        match value {
+            RemoveGradeError::Forbidden => Self::new("FORBIDDEN"),
+            RemoveGradeError::NotFound => Self::new("NOT_FOUND"),
+            RemoveGradeError::Conflict { status, current } => {
+                UpdateGradeError::Conflict { status, current }.into()
+            }
+        }
+    }
+}
+
+impl From<AssessmentError> for LocalizedError {
+    fn from(value: AssessmentError) -> Self {
+        match value {
+            AssessmentError::Forbidden => Self::new("FORBIDDEN"),
+            AssessmentError::NotFound => Self::new("NOT_FOUND"),
+            AssessmentError::GradeLocked(status) => {
+                Self::new("GRADE_LOCKED").with_text("status", status.as_str())
+            }
+        }
+    }
+}
+
+impl From<ManageSubjectError> for LocalizedError {
+    fn from(value: ManageSubjectError) -> Self {
+        match value {
+            ManageSubjectError::Forbidden => Self::new("FORBIDDEN"),
+            ManageSubjectError::NotFound => Self::new("NOT_FOUND"),
+            ManageSubjectError::AlreadyExists => Self::new("SUBJECT_EXISTS"),
+            ManageSubjectError::HasGrades => Self::new("SUBJECT_HAS_GRADES"),
+        }
+    }
+}
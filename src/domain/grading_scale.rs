@@ -0,0 +1,198 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+use super::grade::{parse_fixed, Grade};
+
+const GPA_DIGITS: u32 = 2;
+
+static LETTER_BANDS: [LetterBand; 5] = [
+    LetterBand::new("A", 90, 95, 4),
+    LetterBand::new("B", 80, 85, 3),
+    LetterBand::new("C", 70, 75, 2),
+    LetterBand::new("D", 60, 65, 1),
+    LetterBand::new("F", 0, 30, 0),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum GradingScale {
+    #[default]
+    Percent,
+    TwelvePoint,
+    Letter,
+    Gpa,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaledGrade {
+    pub grade: Grade,
+    pub scaled_value: String,
+    pub gpa: Gpa,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Copy, Serialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct Gpa(#[serde_as(as = "DisplayFromStr")] Decimal);
+
+struct LetterBand {
+    letter: &'static str,
+    min: i64,
+    canonical: i64,
+    gpa: i64,
+}
+
+struct NumericRange {
+    min: i64,
+    max: i64,
+    fraction_digits: u32,
+}
+
+impl GradingScale {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Percent => "percent",
+            Self::TwelvePoint => "twelve-point",
+            Self::Letter => "letter",
+            Self::Gpa => "gpa",
+        }
+    }
+
+    pub fn grade(self, value: String) -> Result<ScaledGrade, ValidationFailure<String>> {
+        match self.parse_grade(&value) {
+            Some(grade) => Ok(self.scale(grade)),
+            None => Err(ValidationFailure {
+                input: value,
+                errors: vec![self.invalid_format()],
+            }),
+        }
+    }
+
+    pub fn scale(self, grade: Grade) -> ScaledGrade {
+        let canonical = Decimal::from(grade);
+        let band = LetterBand::of(canonical);
+        let (scaled_value, gpa) = match self {
+            Self::Percent => (canonical.to_string(), Decimal::from(band.gpa)),
+            Self::TwelvePoint => {
+                let points = (canonical * Decimal::from(12) / Decimal::from(100))
+                    .round()
+                    .max(Decimal::ONE);
+                (points.to_string(), Decimal::from(band.gpa))
+            }
+            Self::Letter => (band.letter.to_owned(), Decimal::from(band.gpa)),
+            Self::Gpa => {
+                let mut gpa = (canonical / Decimal::from(25)).round_dp(GPA_DIGITS);
+                gpa.rescale(GPA_DIGITS);
+                (gpa.to_string(), gpa)
+            }
+        };
+        ScaledGrade {
+            grade,
+            scaled_value,
+            gpa: Gpa(gpa),
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "percent" => Some(Self::Percent),
+            "twelve-point" => Some(Self::TwelvePoint),
+            "letter" => Some(Self::Letter),
+            "gpa" => Some(Self::Gpa),
+            _ => None,
+        }
+    }
+
+    fn parse_grade(self, value: &str) -> Option<Grade> {
+        match self {
+            Self::Percent => Grade::parse(value),
+            Self::Letter => LETTER_BANDS
+                .iter()
+                .find(|band| band.letter.eq_ignore_ascii_case(value.trim()))
+                .map(|band| Grade::from_canonical(Decimal::from(band.canonical))),
+            Self::TwelvePoint | Self::Gpa => {
+                let range = self.range()?;
+                let value = parse_fixed(value, range.min, range.max, range.fraction_digits)?;
+                let canonical = value * Decimal::from(100) / Decimal::from(range.max);
+                Some(Grade::from_canonical(canonical))
+            }
+        }
+    }
+
+    fn range(self) -> Option<NumericRange> {
+        match self {
+            Self::Percent => Some(NumericRange::new(0, 100, 2)),
+            Self::TwelvePoint => Some(NumericRange::new(1, 12, 0)),
+            Self::Gpa => Some(NumericRange::new(0, 4, GPA_DIGITS)),
+            Self::Letter => None,
+        }
+    }
+
+    fn invalid_format(self) -> LocalizedError {
+        let error = LocalizedError::new("GRADE_INVALID_FORMAT").with_text("scale", self.as_str());
+        match self.range() {
+            Some(range) => error
+                .with_number("min", range.min as f64)
+                .with_number("max", range.max as f64)
+                .with_number("fractionDigits", range.fraction_digits as f64),
+            None => {
+                let letters = LETTER_BANDS
+                    .iter()
+                    .map(|band| band.letter)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                error.with_text("letters", letters)
+            }
+        }
+    }
+}
+
+impl LetterBand {
+    const fn new(letter: &'static str, min: i64, canonical: i64, gpa: i64) -> Self {
+        Self {
+            letter,
+            min,
+            canonical,
+            gpa,
+        }
+    }
+
+    fn of(canonical: Decimal) -> &'static Self {
+        LETTER_BANDS
+            .iter()
+            .find(|band| canonical >= Decimal::from(band.min))
+            .unwrap_or(&LETTER_BANDS[LETTER_BANDS.len() - 1])
+    }
+}
+
+impl NumericRange {
+    const fn new(min: i64, max: i64, fraction_digits: u32) -> Self {
+        Self {
+            min,
+            max,
+            fraction_digits,
+        }
+    }
+}
+
+impl TryFrom<String> for GradingScale {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Validation::new(value)
+            .check_or_else(
+                |v| Self::parse(v).is_some(),
+                || LocalizedError::new("INVALID_GRADING_SCALE"),
+            )
+            .finish()
+            .map(|v| Self::parse(&v).unwrap())
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use unic_langid::LanguageIdentifier;
+
+use crate::app::localization::LocalizedError;
+
+use super::{
+    auth::{NewUser, SignupData},
+    email::Email,
+    name::Name,
+    token::{InviteToken, InviteTokenHash},
+    user_id::{DbUserId, UserId},
+};
+
+pub trait InviteTeacher {
+    async fn invite_teacher(&self, request: InviteRequest) -> crate::Result<(), InviteError>;
+}
+
+pub trait SignupWithInvite {
+    async fn signup_with_invite(
+        &self,
+        signup_data: InvitedSignupData,
+    ) -> crate::Result<(), InvitedSignupError>;
+}
+
+pub trait IssueInviteToken {
+    fn issue_invite_token(&self) -> crate::Result<IssuedInviteToken>;
+}
+
+pub trait HashInviteToken {
+    fn hash_invite_token(&self, token: &InviteToken) -> InviteTokenHash;
+}
+
+pub trait SaveInvite {
+    async fn save_invite(&self, invite: NewInvite) -> crate::Result<()>;
+}
+
+pub trait SaveInvitedUser {
+    async fn save_invited_user(
+        &self,
+        user: NewUser,
+        invite: InviteTokenHash,
+    ) -> crate::Result<(), InvitedSignupError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct InviteData {
+    pub email: Email,
+}
+
+#[derive(Debug, Clone)]
+pub struct InviteRequest {
+    pub inviter_id: UserId,
+    pub inviter_name: Name,
+    pub email: Email,
+    pub locale: LanguageIdentifier,
+}
+
+#[derive(Debug, Clone)]
+pub struct InvitedSignupData {
+    pub data: SignupData,
+    pub invite: InviteToken,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedInviteToken {
+    pub token: InviteToken,
+    pub hash: InviteTokenHash,
+    pub ttl: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewInvite {
+    pub hash: InviteTokenHash,
+    pub email: Email,
+    pub invited_by: DbUserId,
+    pub ttl: Duration,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeacherInviteMail {
+    pub inviter: Name,
+    pub token: InviteToken,
+    pub ttl_days: u64,
+}
+
+#[derive(Debug)]
+pub enum InviteError {
+    Forbidden,
+    EmailTaken,
+}
+
+#[derive(Debug)]
+pub enum InvitedSignupError {
+    InvalidInvite,
+    EmailMismatch,
+    EmailTaken,
+}
+
+impl From<InviteError> for LocalizedError {
+    fn from(value: InviteError) -> Self {
+        match value {
+            InviteError::Forbidden => Self::new("FORBIDDEN"),
+            InviteError::EmailTaken => Self::new("EMAIL_TAKEN"),
+        }
+    }
+}
+
+impl From<InvitedSignupError> for LocalizedError {
+    fn from(value: InvitedSignupError) -> Self {
+        match value {
+            InvitedSignupError::InvalidInvite => Self::new("INVALID_INVITE"),
+            InvitedSignupError::EmailMismatch => Self::new("INVITE_EMAIL_MISMATCH"),
+            InvitedSignupError::EmailTaken => Self::new("EMAIL_TAKEN"),
+        }
+    }
+}
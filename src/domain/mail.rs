@@ -0,0 +1,38 @@
+use serde::Serialize;
+use unic_langid::LanguageIdentifier;
+
+use super::email::Email;
+
+pub trait RenderMail {
+    fn render_mail<T>(
+        &self,
+        template: MailTemplate,
+        data: T,
+        locale: &LanguageIdentifier,
+    ) -> crate::Result<MailContent>
+    where
+        T: Serialize;
+}
+
+pub trait SendMail {
+    async fn send_mail(&self, mail: Mail) -> crate::Result<()>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum MailTemplate {
+    PasswordReset,
+    EmailVerification,
+    TeacherInvite,
+}
+
+#[derive(Debug, Clone)]
+pub struct MailContent {
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Mail {
+    pub to: Email,
+    pub content: MailContent,
+}
@@ -1,18 +1,44 @@
+pub mod admin;
 pub mod auth;
+pub mod dashboard;
+pub mod enrollment;
+pub mod grade_entry;
+pub mod grade_export;
+pub mod grade_import;
+pub mod grade_stats;
 pub mod grades;
+pub mod invites;
 pub mod keys;
 pub mod performance;
+pub mod terms;
 
+pub mod assessment_id;
+pub mod assessment_kind;
+pub mod assessment_title;
+pub mod class_id;
+pub mod class_name;
 pub mod email;
 pub mod grade;
+pub mod grade_sort;
+pub mod grade_status;
+pub mod grade_version;
+pub mod grading_scale;
 pub mod key;
+pub mod mail;
 pub mod name;
 pub mod password;
 pub mod percentile;
+pub mod reason;
 pub mod role;
 pub mod signature;
 pub mod subject_id;
 pub mod subject_title;
+pub mod term_date;
+pub mod term_id;
+pub mod term_name;
+pub mod throttle;
 pub mod token;
+pub mod two_factor;
 pub mod user_id;
 pub mod verifying_key;
+pub mod weight;
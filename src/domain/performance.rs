@@ -3,12 +3,16 @@ use serde::Serialize;
 use crate::app::localization::LocalizedError;
 
 use super::{
-    key::Key, name::Name, percentile::Percentile, signature::Signature, user_id::DbUserId,
-    verifying_key::VerifyingKey,
+    key::Key, name::Name, percentile::Percentile, signature::Signature, term_id::TermId,
+    term_name::TermName, user_id::DbUserId, verifying_key::VerifyingKey,
 };
 
 pub trait GetSignature {
-    async fn get_signature(&self, key: Key) -> crate::Result<SignedEvaluation, KeyLookupError>;
+    async fn get_signature(
+        &self,
+        key: Key,
+        term: Option<TermId>,
+    ) -> crate::Result<SignedEvaluation, KeyLookupError>;
 }
 
 pub trait GetVerifyingKey {
@@ -19,6 +23,7 @@ pub trait GetPerformanceEvaluation {
     async fn get_performance_evaluation(
         &self,
         key: Key,
+        term: Option<TermId>,
     ) -> crate::Result<PerformanceEvaluation, KeyLookupError>;
 }
 
@@ -39,18 +44,21 @@ pub struct SignedEvaluation {
 #[derive(Debug, Clone, Serialize)]
 pub struct PerformanceEvaluation {
     pub student: Name,
+    pub term: TermName,
     pub percentile: Percentile,
 }
 
 #[derive(Debug)]
 pub enum KeyLookupError {
     UnknownKey,
+    UnknownTerm,
 }
 
 impl From<KeyLookupError> for LocalizedError {
     fn from(value: KeyLookupError) -> Self {
         match value {
             KeyLookupError::UnknownKey => Self::new("UNKNOWN_KEY"),
+            KeyLookupError::UnknownTerm => Self::new("UNKNOWN_TERM"),
         }
     }
 }
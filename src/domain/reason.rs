@@ -0,0 +1,65 @@
+use educe::Educe;
+use serde::Serialize;
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+const MIN_LENGTH: usize = 3;
+const MAX_LENGTH: usize = 500;
+
+#[derive(Educe, Debug, Clone, Serialize, sqlx::Type)]
+#[educe(Into(String))]
+#[sqlx(transparent)]
+pub struct Reason(String);
+
+#[derive(Debug, Clone, Default)]
+pub struct OptionalReason(Option<Reason>);
+
+impl Reason {
+    pub fn new(reason: String) -> Result<Self, ValidationFailure<String>> {
+        Validation::new(reason.trim().to_owned())
+            .check_or_else(
+                |v| v.chars().count() >= MIN_LENGTH,
+                || LocalizedError::new("REASON_TOO_SHORT").with_number("min", MIN_LENGTH as f64),
+            )
+            .check_or_else(
+                |v| v.chars().count() <= MAX_LENGTH,
+                || LocalizedError::new("REASON_TOO_LONG").with_number("max", MAX_LENGTH as f64),
+            )
+            .finish()
+            .map(Self)
+    }
+}
+
+impl OptionalReason {
+    pub fn new(reason: String) -> Result<Self, ValidationFailure<String>> {
+        match reason.trim() {
+            "" => Ok(Self(None)),
+            _ => Reason::new(reason).map(Some).map(Self),
+        }
+    }
+}
+
+impl TryFrom<String> for Reason {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<String> for OptionalReason {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<OptionalReason> for Option<Reason> {
+    fn from(value: OptionalReason) -> Self {
+        value.0
+    }
+}
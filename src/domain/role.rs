@@ -1,9 +1,40 @@
 use serde::Serialize;
 
-#[derive(Clone, Debug, Serialize, sqlx::Type)]
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, sqlx::Type)]
 #[sqlx(type_name = "text", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum Role {
     Teacher,
     Student,
+    Admin,
+}
+
+impl Role {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "teacher" => Some(Self::Teacher),
+            "student" => Some(Self::Student),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<String> for Role {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Validation::new(value)
+            .check_or_else(
+                |v| Self::parse(v).is_some(),
+                || LocalizedError::new("INVALID_ROLE"),
+            )
+            .finish()
+            .map(|v| Self::parse(&v).unwrap())
+    }
 }
@@ -0,0 +1,51 @@
+use educe::Educe;
+use serde::Serialize;
+use time::{Date, Month};
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+#[derive(Educe, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, sqlx::Type)]
+#[educe(Into(String))]
+#[sqlx(transparent)]
+pub struct TermDate(String);
+
+impl TermDate {
+    pub fn new(date: String) -> Result<Self, ValidationFailure<String>> {
+        Validation::new(date)
+            .check_or_else(
+                |v| Self::parse(v).is_some(),
+                || LocalizedError::new("INVALID_TERM_DATE"),
+            )
+            .finish()
+            .map(|v| Self::parse(&v).unwrap())
+    }
+
+    fn parse(date: &str) -> Option<Self> {
+        let mut parts = date.trim().splitn(3, '-');
+        let year = parts
+            .next()
+            .filter(|year| year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()))?
+            .parse()
+            .ok()?;
+        let month = parts.next()?.parse::<u8>().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        let date = Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()?;
+        Some(Self(format!(
+            "{:04}-{:02}-{:02}",
+            date.year(),
+            u8::from(date.month()),
+            date.day()
+        )))
+    }
+}
+
+impl TryFrom<String> for TermDate {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
@@ -0,0 +1,69 @@
+use serde::Serialize;
+
+use crate::app::localization::LocalizedError;
+
+use super::{term_date::TermDate, term_id::TermId, term_name::TermName};
+
+pub trait GetTerms {
+    async fn get_terms(&self) -> crate::Result<Vec<Term>>;
+}
+
+pub trait GetTerm {
+    async fn get_term(&self, term_id: Option<TermId>) -> crate::Result<Option<Term>>;
+}
+
+pub trait CreateTerm {
+    async fn create_term(&self, term: NewTerm) -> crate::Result<(), TermError>;
+}
+
+pub trait ActivateTerm {
+    async fn activate_term(&self, term_id: TermId) -> crate::Result<(), TermError>;
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Term {
+    pub id: TermId,
+    pub name: TermName,
+    pub starts_on: TermDate,
+    pub ends_on: TermDate,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTerm {
+    pub name: TermName,
+    pub starts_on: TermDate,
+    pub ends_on: TermDate,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TermSelection {
+    pub terms: Vec<Term>,
+    pub selected: Option<Term>,
+}
+
+#[derive(Debug)]
+pub enum TermError {
+    Forbidden,
+    NotFound,
+    AlreadyExists,
+    InvalidDates,
+}
+
+impl TermSelection {
+    pub fn selected_id(&self) -> Option<TermId> {
+        self.selected.as_ref().map(|term| term.id)
+    }
+}
+
+impl From<TermError> for LocalizedError {
+    fn from(value: TermError) -> Self {
+        match value {
+            TermError::Forbidden => Self::new("FORBIDDEN"),
+            TermError::NotFound => Self::new("NOT_FOUND"),
+            TermError::AlreadyExists => Self::new("TERM_EXISTS"),
+            TermError::InvalidDates => Self::new("TERM_INVALID_DATES"),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::{net::IpAddr, time::Duration};
+
+use super::email::MaybeEmail;
+
+pub trait CheckLoginThrottle {
+    async fn check_login_throttle(&self, source: &LoginSource) -> crate::Result<(), Throttled>;
+}
+
+pub trait GetLoginFailures {
+    async fn get_login_failures(&self, source: &LoginSource) -> crate::Result<LoginFailures>;
+}
+
+pub trait ComputeLoginDelay {
+    fn compute_login_delay(&self, failures: &LoginFailures) -> Option<Duration>;
+}
+
+pub trait RecordLoginAttempt {
+    async fn record_login_attempt(&self, attempt: LoginAttempt) -> crate::Result<()>;
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginSource {
+    pub email: MaybeEmail,
+    pub ip: IpAddr,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginAttempt {
+    pub source: LoginSource,
+    pub outcome: LoginAttemptOutcome,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum LoginAttemptOutcome {
+    Succeeded,
+    Failed,
+    Throttled,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LoginFailures {
+    pub email_failures: i64,
+    pub email_last_failure_at: Option<i64>,
+    pub ip_failures: i64,
+    pub ip_last_failure_at: Option<i64>,
+}
+
+#[derive(Debug)]
+pub struct Throttled {
+    pub retry_after: Duration,
+}
@@ -1,12 +1,121 @@
 use educe::Educe;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Educe, Debug, Clone, Serialize)]
 #[educe(Into(String))]
 pub struct AuthToken(String);
 
+#[derive(Educe, Debug, Clone, Serialize)]
+#[educe(Into(String))]
+pub struct RefreshToken(String);
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct RefreshTokenHash(String);
+
+#[derive(Educe, Debug, Clone, Serialize)]
+#[educe(Into(String))]
+pub struct ResetToken(String);
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct ResetTokenHash(String);
+
+#[derive(Educe, Debug, Clone, Serialize)]
+#[educe(Into(String))]
+pub struct VerificationToken(String);
+
+#[derive(Educe, Debug, Clone, Serialize)]
+#[educe(Into(String))]
+pub struct InviteToken(String);
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct InviteTokenHash(String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct TokenId(Uuid);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct SessionId(Uuid);
+
 impl AuthToken {
     pub fn new(value: String) -> Self {
         Self(value)
     }
 }
+
+impl RefreshToken {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl RefreshTokenHash {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl ResetToken {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl ResetTokenHash {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl VerificationToken {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl InviteToken {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl InviteTokenHash {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl TokenId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl SessionId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl AsRef<str> for RefreshToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for ResetToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for InviteToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
@@ -0,0 +1,379 @@
+use std::{net::IpAddr, time::Duration};
+
+use educe::Educe;
+use secrecy::{ExposeSecret, SecretSlice, SecretString};
+use serde::Serialize;
+
+use crate::app::localization::LocalizedError;
+
+use super::{
+    auth::{AuthTokens, DbUser, DecodeIdError},
+    email::{Email, MaybeEmail},
+    role::Role,
+    throttle::Throttled,
+    token::TokenId,
+    user_id::{DbUserId, UserId},
+};
+
+pub trait CheckTwoFactor {
+    async fn check_two_factor(
+        &self,
+        user: &DbUser,
+        email: &MaybeEmail,
+    ) -> crate::Result<Option<TwoFactorPrompt>>;
+}
+
+pub trait VerifyTwoFactor {
+    async fn verify_two_factor(
+        &self,
+        request: TwoFactorRequest,
+    ) -> crate::Result<TwoFactorLogin, TwoFactorError>;
+}
+
+pub trait ConfirmTwoFactor {
+    async fn confirm_two_factor(
+        &self,
+        user_id: UserId,
+        code: TwoFactorCode,
+    ) -> crate::Result<TwoFactorConfirmation, TwoFactorError>;
+}
+
+pub trait RecordChallengeAttempt {
+    async fn record_challenge_attempt(&self, challenge: &ChallengeClaims) -> crate::Result<i32>;
+}
+
+pub trait SetupTwoFactor {
+    async fn setup_two_factor(
+        &self,
+        user_id: DbUserId,
+        email: &Email,
+    ) -> crate::Result<TwoFactorProvisioning, TwoFactorError>;
+}
+
+pub trait EnableTwoFactor {
+    async fn enable_two_factor(
+        &self,
+        user_id: DbUserId,
+        code: TwoFactorCode,
+    ) -> crate::Result<Vec<RecoveryCode>, TwoFactorError>;
+}
+
+pub trait CheckSecondFactor {
+    async fn check_second_factor(
+        &self,
+        user_id: DbUserId,
+        code: TwoFactorCode,
+    ) -> crate::Result<(), TwoFactorError>;
+}
+
+pub trait RedeemRecoveryCode {
+    async fn redeem_recovery_code(
+        &self,
+        user_id: DbUserId,
+        code: &TwoFactorCode,
+    ) -> crate::Result<(), TwoFactorError>;
+}
+
+pub trait IsTwoFactorRequired {
+    fn is_two_factor_required(&self, role: Role) -> bool;
+}
+
+pub trait IssueTwoFactorChallenge {
+    fn issue_two_factor_challenge(
+        &self,
+        user_id: UserId,
+        email: MaybeEmail,
+    ) -> crate::Result<TwoFactorChallenge>;
+}
+
+pub trait ParseTwoFactorChallenge {
+    fn parse_two_factor_challenge(
+        &self,
+        challenge: TwoFactorChallenge,
+    ) -> crate::Result<ChallengeClaims, TwoFactorError>;
+}
+
+pub trait IssueTotpSecret {
+    fn issue_totp_secret(&self) -> crate::Result<IssuedTotpSecret>;
+}
+
+pub trait DecryptTotpSecret {
+    fn decrypt_totp_secret(&self, secret: &EncryptedTotpSecret) -> crate::Result<TotpSecret>;
+}
+
+pub trait ProvisionTotp {
+    fn provision_totp(
+        &self,
+        secret: &TotpSecret,
+        email: &Email,
+    ) -> crate::Result<TwoFactorProvisioning>;
+}
+
+pub trait CheckTotpCode {
+    fn check_totp_code(&self, secret: &TotpSecret, code: &TwoFactorCode) -> Option<TotpStep>;
+}
+
+pub trait IssueRecoveryCodes {
+    fn issue_recovery_codes(&self) -> crate::Result<Vec<IssuedRecoveryCode>>;
+}
+
+pub trait IsRecoveryCode {
+    fn is_recovery_code(&self, code: &TwoFactorCode) -> bool;
+}
+
+pub trait VerifyRecoveryCode {
+    fn verify_recovery_code(&self, code: &TwoFactorCode, hash: &RecoveryCodeHash) -> bool;
+}
+
+pub trait SaveTotpSecret {
+    async fn save_totp_secret(
+        &self,
+        user_id: DbUserId,
+        secret: EncryptedTotpSecret,
+    ) -> crate::Result<(), TwoFactorError>;
+}
+
+pub trait GetTotpCredential {
+    async fn get_totp_credential(
+        &self,
+        user_id: DbUserId,
+    ) -> crate::Result<Option<DbTotpCredential>>;
+}
+
+pub trait ActivateTotp {
+    async fn activate_totp(
+        &self,
+        user_id: DbUserId,
+        step: TotpStep,
+        recovery_codes: Vec<RecoveryCodeHash>,
+    ) -> crate::Result<()>;
+}
+
+pub trait UseTotpStep {
+    async fn use_totp_step(&self, user_id: DbUserId, step: TotpStep) -> crate::Result<bool>;
+}
+
+pub trait GetRecoveryCodes {
+    async fn get_recovery_codes(&self, user_id: DbUserId) -> crate::Result<Vec<DbRecoveryCode>>;
+}
+
+pub trait UseRecoveryCode {
+    async fn use_recovery_code(&self, id: RecoveryCodeId) -> crate::Result<bool>;
+}
+
+#[derive(Educe, Debug, Clone, Serialize)]
+#[educe(Into(String))]
+pub struct TwoFactorChallenge(String);
+
+#[derive(Educe, Clone)]
+#[educe(Debug, Into(String))]
+pub struct TwoFactorCode(#[educe(Debug(ignore))] String);
+
+#[derive(Debug, Clone)]
+pub struct TotpSecret(SecretSlice<u8>);
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct EncryptedTotpSecret(String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct TotpStep(i64);
+
+#[derive(Educe, Clone, Debug)]
+#[educe(Into(SecretString))]
+pub struct RecoveryCode(SecretString);
+
+#[derive(Debug, Clone, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct RecoveryCodeHash(String);
+
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct RecoveryCodeId(i64);
+
+#[derive(Debug, Clone)]
+pub struct TwoFactorData {
+    pub challenge: TwoFactorChallenge,
+    pub code: TwoFactorCode,
+}
+
+#[derive(Debug, Clone)]
+pub struct TwoFactorRequest {
+    pub data: TwoFactorData,
+    pub ip: IpAddr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChallengeClaims {
+    pub challenge_id: TokenId,
+    pub user_id: UserId,
+    pub email: MaybeEmail,
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorPrompt {
+    pub challenge: TwoFactorChallenge,
+    pub provisioning: Option<TwoFactorProvisioning>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorProvisioning {
+    pub secret: String,
+    pub uri: String,
+    pub qr_code: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TwoFactorLogin {
+    pub tokens: AuthTokens,
+    pub recovery_codes: Vec<RecoveryCode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TwoFactorConfirmation {
+    pub user_id: DbUserId,
+    pub recovery_codes: Vec<RecoveryCode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedTotpSecret {
+    pub secret: TotpSecret,
+    pub encrypted: EncryptedTotpSecret,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssuedRecoveryCode {
+    pub code: RecoveryCode,
+    pub hash: RecoveryCodeHash,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbTotpCredential {
+    pub secret: EncryptedTotpSecret,
+    pub is_enabled: bool,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DbRecoveryCode {
+    pub id: RecoveryCodeId,
+    pub hash: RecoveryCodeHash,
+}
+
+#[derive(Debug)]
+pub enum TwoFactorError {
+    InvalidCode,
+    ChallengeExpired,
+    AlreadyEnabled,
+    TooManyAttempts(Duration),
+}
+
+impl TwoFactorChallenge {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl TwoFactorCode {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+
+    pub fn normalized(&self) -> String {
+        self.0
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    }
+}
+
+impl TotpSecret {
+    pub fn new(value: Vec<u8>) -> Self {
+        Self(SecretSlice::from(value))
+    }
+}
+
+impl EncryptedTotpSecret {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl TotpStep {
+    pub fn new(value: i64) -> Self {
+        Self(value)
+    }
+}
+
+impl RecoveryCode {
+    pub fn new(value: SecretString) -> Self {
+        Self(value)
+    }
+}
+
+impl RecoveryCodeHash {
+    pub fn new(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<String> for TwoFactorCode {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl ExposeSecret<[u8]> for TotpSecret {
+    fn expose_secret(&self) -> &[u8] {
+        self.0.expose_secret()
+    }
+}
+
+impl ExposeSecret<str> for RecoveryCode {
+    fn expose_secret(&self) -> &str {
+        self.0.expose_secret()
+    }
+}
+
+impl AsRef<str> for EncryptedTotpSecret {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for RecoveryCodeHash {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<TwoFactorError> for LocalizedError {
+    fn from(value: TwoFactorError) -> Self {
+        match value {
+            TwoFactorError::InvalidCode => Self::new("INVALID_TWO_FACTOR_CODE"),
+            TwoFactorError::ChallengeExpired => Self::new("TWO_FACTOR_EXPIRED"),
+            TwoFactorError::AlreadyEnabled => Self::new("TWO_FACTOR_ENABLED"),
+            TwoFactorError::TooManyAttempts(retry_after) => {
+                Self::new("TOO_MANY_ATTEMPTS").with_number("seconds", retry_after.as_secs() as f64)
+            }
+        }
+    }
+}
+
+impl From<Throttled> for TwoFactorError {
+    fn from(value: Throttled) -> Self {
+        Self::TooManyAttempts(value.retry_after)
+    }
+}
+
+impl From<DecodeIdError> for TwoFactorError {
+    fn from(value: DecodeIdError) -> Self {
+        match value {
+            DecodeIdError::InvalidFormat => Self::ChallengeExpired,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::app::{
+    localization::LocalizedError,
+    validation::{Validation, ValidationFailure},
+};
+
+const MIN_WEIGHT: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+const MAX_WEIGHT: Decimal = Decimal::ONE_HUNDRED;
+const FRACTION_DIGITS: u32 = 2;
+
+#[serde_as]
+#[derive(Debug, Clone, Copy, Serialize, sqlx::Type)]
+#[sqlx(transparent)]
+pub struct Weight(#[serde_as(as = "DisplayFromStr")] Decimal);
+
+impl Weight {
+    pub fn new(weight: String) -> Result<Self, ValidationFailure<String>> {
+        Validation::new(weight)
+            .check_or_else(
+                |v| Self::parse(v).is_some(),
+                || {
+                    LocalizedError::new("WEIGHT_INVALID_FORMAT")
+                        .with_number("max", 100.0)
+                        .with_number("fractionDigits", FRACTION_DIGITS as f64)
+                },
+            )
+            .finish()
+            .map(|v| Self::parse(&v).unwrap())
+    }
+
+    fn parse(weight: &str) -> Option<Self> {
+        Decimal::from_str(weight.trim())
+            .ok()
+            .filter(|weight| weight.scale() <= FRACTION_DIGITS)
+            .filter(|weight| (MIN_WEIGHT..=MAX_WEIGHT).contains(weight))
+            .map(Self)
+    }
+}
+
+impl From<Weight> for Decimal {
+    fn from(value: Weight) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for Weight {
+    type Error = ValidationFailure<String>;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
@@ -0,0 +1,179 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+    Form, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        admin::{change_user_role, delete_user, list_users, set_user_disabled},
+        validation::{try_convert, ValidationErrors},
+        AppContext, AppError,
+    },
+    domain::{
+        admin::{ManageUserError, ManagedUser, RoleChange},
+        user_id::UserId,
+    },
+    error::Error,
+};
+
+use super::{
+    error::HttpError,
+    middleware::{auth::Admin, template::TemplateName, view::View},
+};
+
+const ADMIN_USERS_PAGE: &str = "admin-users.html";
+
+pub fn admin_routes() -> Router<AppContext> {
+    let user_routes = Router::new()
+        .route("/", delete(handle_delete_user))
+        .route("/role", post(handle_change_role))
+        .route("/disable", post(handle_disable_user))
+        .route("/enable", post(handle_enable_user));
+    Router::new()
+        .route("/users", get(users_page))
+        .nest("/users/{user_id}", user_routes)
+}
+
+type HttpManageUserError = Error<ManageUserError>;
+
+type HttpChangeRoleError = Error<AppError<ManageUserError>, RoleChangeRequest>;
+
+#[derive(Clone, Debug, Deserialize)]
+struct UsersQuery {
+    search: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct UserPath {
+    user_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct RoleForm {
+    role: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct RoleChangeRequest {
+    user: String,
+    role: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UsersData {
+    users: Vec<ManagedUser>,
+    search: Option<String>,
+    current_user_id: UserId,
+}
+
+async fn users_page(
+    Admin(admin): Admin,
+    State(ctx): State<AppContext>,
+    Query(query): Query<UsersQuery>,
+) -> Result<View<UsersData>, View<Error>> {
+    users_view(&ctx, admin.id, query.search)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))
+}
+
+async fn handle_change_role(
+    Admin(admin): Admin,
+    State(ctx): State<AppContext>,
+    Path(path): Path<UserPath>,
+    Query(query): Query<UsersQuery>,
+    Form(form): Form<RoleForm>,
+) -> Result<View<UsersData>, View<HttpChangeRoleError>> {
+    let req = RoleChangeRequest {
+        user: path.user_id,
+        role: form.role,
+    };
+    change_user_role(&ctx, admin.id.clone(), req.clone())
+        .await
+        .map_err(|error| View::new(TemplateName::error(), error.with_input(req.clone())))?;
+    users_view(&ctx, admin.id, query.search)
+        .await
+        .map_err(|error| {
+            View::new(
+                TemplateName::error(),
+                Error::from_internal(error).with_input(req),
+            )
+        })
+}
+
+async fn handle_disable_user(
+    Admin(admin): Admin,
+    State(ctx): State<AppContext>,
+    Path(path): Path<UserPath>,
+    Query(query): Query<UsersQuery>,
+) -> Result<View<UsersData>, View<HttpManageUserError>> {
+    set_user_disabled(&ctx, admin.id.clone(), path.user_id, true)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), error))?;
+    users_view(&ctx, admin.id, query.search)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))
+}
+
+async fn handle_enable_user(
+    Admin(admin): Admin,
+    State(ctx): State<AppContext>,
+    Path(path): Path<UserPath>,
+    Query(query): Query<UsersQuery>,
+) -> Result<View<UsersData>, View<HttpManageUserError>> {
+    set_user_disabled(&ctx, admin.id.clone(), path.user_id, false)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), error))?;
+    users_view(&ctx, admin.id, query.search)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))
+}
+
+async fn handle_delete_user(
+    Admin(admin): Admin,
+    State(ctx): State<AppContext>,
+    Path(path): Path<UserPath>,
+    Query(query): Query<UsersQuery>,
+) -> Result<View<UsersData>, View<HttpManageUserError>> {
+    delete_user(&ctx, admin.id.clone(), path.user_id)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), error))?;
+    users_view(&ctx, admin.id, query.search)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))
+}
+
+async fn users_view(
+    ctx: &AppContext,
+    current_user_id: UserId,
+    search: Option<String>,
+) -> crate::Result<View<UsersData>> {
+    list_users(ctx, search.clone())
+        .await
+        .map(|users| UsersData {
+            users,
+            search,
+            current_user_id,
+        })
+        .map(|data| View::new(ADMIN_USERS_PAGE, data))
+}
+
+impl HttpError for ManageUserError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::UnknownUser => StatusCode::NOT_FOUND,
+            Self::SelfModification => StatusCode::CONFLICT,
+        }
+    }
+}
+
+impl TryFrom<RoleChangeRequest> for RoleChange {
+    type Error = ValidationErrors;
+
+    fn try_from(value: RoleChangeRequest) -> Result<Self, Self::Error> {
+        try_convert!(RoleChangeRequest value => RoleChange { user, role })
+    }
+}
@@ -0,0 +1,196 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, put},
+    Form, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        assessments::{create_assessment, delete_assessment, get_gradebook, save_assessment_score},
+        validation::{try_convert, ValidationErrors},
+        AppContext, AppError,
+    },
+    domain::grades::{AssessmentError, AssessmentScore, Gradebook, NewAssessment},
+    error::Error,
+};
+
+use super::{
+    error::HttpError,
+    middleware::{auth::Teacher, template::TemplateName, view::View},
+};
+
+const ASSESSMENTS_PAGE: &str = "assessments.html";
+
+const GRADEBOOK: &str = "components/gradebook.html";
+
+const INLINE_ERROR: &str = "components/inline-error.html";
+
+pub fn assessments_routes() -> Router<AppContext> {
+    Router::new()
+        .route("/", get(assessments_page).post(handle_create_assessment))
+        .route("/{assessment_id}", delete(handle_delete_assessment))
+        .route(
+            "/{assessment_id}/scores/{student_id}",
+            put(handle_save_score),
+        )
+}
+
+type HttpAssessmentError = Error<AssessmentError>;
+
+#[derive(Clone, Debug, Deserialize)]
+struct SubjectPath {
+    subject_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct AssessmentPath {
+    subject_id: String,
+    assessment_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ScorePath {
+    subject_id: String,
+    assessment_id: String,
+    student_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct AssessmentForm {
+    title: String,
+    kind: String,
+    weight: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ScoreForm {
+    score: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct AssessmentRequest {
+    subject_id: String,
+    title: String,
+    kind: String,
+    weight: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct ScoreRequest {
+    subject_id: String,
+    assessment_id: String,
+    student_id: String,
+    score: String,
+}
+
+async fn assessments_page(
+    _: Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+) -> Result<View<Gradebook>, View<HttpAssessmentError>> {
+    get_gradebook(&ctx, path.subject_id)
+        .await
+        .map(|gradebook| View::new(ASSESSMENTS_PAGE, gradebook))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
+async fn handle_create_assessment(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+    Form(form): Form<AssessmentForm>,
+) -> Result<View<Gradebook>, View<Error<AppError<AssessmentError>, AssessmentRequest>>> {
+    let req = AssessmentRequest {
+        subject_id: path.subject_id,
+        title: form.title,
+        kind: form.kind,
+        weight: form.weight,
+    };
+    create_assessment(&ctx, user, req.clone())
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error.with_input(req.clone())))?;
+    get_gradebook(&ctx, req.subject_id.clone())
+        .await
+        .map(|gradebook| View::new(GRADEBOOK, gradebook))
+        .map_err(|error| View::new(INLINE_ERROR, Error::cast(error).with_input(req)))
+}
+
+async fn handle_delete_assessment(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<AssessmentPath>,
+) -> Result<View<Gradebook>, View<HttpAssessmentError>> {
+    delete_assessment(&ctx, user, path.subject_id.clone(), path.assessment_id)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    gradebook_view(&ctx, path.subject_id).await
+}
+
+async fn handle_save_score(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<ScorePath>,
+    Form(form): Form<ScoreForm>,
+) -> Result<View<Gradebook>, View<Error<AppError<AssessmentError>, ScoreRequest>>> {
+    let req = ScoreRequest {
+        subject_id: path.subject_id,
+        assessment_id: path.assessment_id,
+        student_id: path.student_id,
+        score: form.score,
+    };
+    save_assessment_score(&ctx, user, req.clone())
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error.with_input(req.clone())))?;
+    get_gradebook(&ctx, req.subject_id.clone())
+        .await
+        .map(|gradebook| View::new(GRADEBOOK, gradebook))
+        .map_err(|error| View::new(INLINE_ERROR, Error::cast(error).with_input(req)))
+}
+
+async fn gradebook_view(
+    ctx: &AppContext,
+    subject_id: String,
+) -> Result<View<Gradebook>, View<HttpAssessmentError>> {
+    get_gradebook(ctx, subject_id)
+        .await
+        .map(|gradebook| View::new(GRADEBOOK, gradebook))
+        .map_err(|error| View::new(INLINE_ERROR, error))
+}
+
+impl HttpError for AssessmentError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::GradeLocked(_) => StatusCode::CONFLICT,
+        }
+    }
+}
+
+impl TryFrom<AssessmentRequest> for NewAssessment {
+    type Error = ValidationErrors;
+
+    fn try_from(value: AssessmentRequest) -> Result<Self, Self::Error> {
+        try_convert!(AssessmentRequest value => NewAssessment {
+            subject_id,
+            title,
+            kind,
+            weight
+        })
+    }
+}
+
+impl TryFrom<ScoreRequest> for AssessmentScore {
+    type Error = ValidationErrors;
+
+    fn try_from(value: ScoreRequest) -> Result<Self, Self::Error> {
+        try_convert!(ScoreRequest value => AssessmentScore {
+            subject_id,
+            assessment_id,
+            student_id,
+            score
+        })
+    }
+}
@@ -1,29 +1,50 @@
+use std::{convert::Infallible, net::SocketAddr};
+
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Redirect,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header::RETRY_AFTER, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Redirect, Response},
     routing::{get, post},
     Form, Router,
 };
-use secrecy::SecretString;
+use axum_extra::extract::CookieJar;
+use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     app::{
-        auth::{login, signup},
+        auth::{
+            login, logout, refresh, request_password_reset, resend_verification, reset_password,
+            signup, verify_email,
+        },
+        invites::signup_with_invite,
+        two_factor::{enable_two_factor, setup_two_factor, verify_two_factor},
         validation::{try_convert, ValidationErrors},
         AppContext, AppError,
     },
     domain::{
-        auth::{LoginData, LoginError, SignupData, SignupError, User},
-        token::AuthToken,
+        auth::{
+            AuthError, AuthTokens, LoginData, LoginError, LoginOutcome, NewPassword, RefreshError,
+            ResetPasswordError, SignupData, SignupError, User, VerifyEmailError,
+        },
+        invites::{InvitedSignupData, InvitedSignupError},
+        token::{AuthToken, InviteToken},
+        two_factor::{
+            RecoveryCode, TwoFactorChallenge, TwoFactorCode, TwoFactorData, TwoFactorError,
+            TwoFactorLogin, TwoFactorProvisioning,
+        },
     },
     Error,
 };
 
 use super::{
     error::HttpError,
-    middleware::{template::Template, view::View},
+    middleware::{
+        auth::Teacher,
+        session::{deliver_tokens, end_session, REFRESH_TOKEN_COOKIE},
+        template::{Locale, Template, TemplateName},
+        view::View,
+    },
     serialize_secret,
 };
 
@@ -35,55 +56,270 @@ const LOGIN_PAGE: &str = "login.html";
 
 const AUTH_TOKEN_SCRIPT: &str = "auth-token.html";
 
+const LOGOUT_SCRIPT: &str = "logout.html";
+
+const PASSWORD_RESET_PAGE: &str = "password-reset.html";
+
+const PASSWORD_RESET_SENT_PAGE: &str = "password-reset-sent.html";
+
+const NEW_PASSWORD_PAGE: &str = "new-password.html";
+
+const VERIFICATION_SENT_PAGE: &str = "verification-sent.html";
+
+const VERIFICATION_RESEND_PAGE: &str = "verification-resend.html";
+
+const EMAIL_VERIFIED_PAGE: &str = "email-verified.html";
+
+const TWO_FACTOR_PAGE: &str = "two-factor.html";
+
+const TWO_FACTOR_SETUP_PAGE: &str = "two-factor-setup.html";
+
+const RECOVERY_CODES_PAGE: &str = "recovery-codes.html";
+
 pub fn auth_routes() -> Router<AppContext> {
     let signup = Router::new()
         .route("/", get(singup_page))
-        .route("/", post(handle_signup));
+        .route("/", post(handle_signup))
+        .route("/invite", post(handle_invited_signup));
     let login = Router::new()
         .route("/", get(login_page))
         .route("/", post(handle_login));
+    let reset = Router::new()
+        .route("/", get(password_reset_page))
+        .route("/", post(handle_password_reset))
+        .route("/{token}", get(new_password_page))
+        .route("/{token}", post(handle_new_password));
+    let two_factor = Router::new()
+        .route("/", post(handle_two_factor))
+        .route("/setup", post(handle_two_factor_setup))
+        .route("/enable", post(handle_two_factor_enable));
     Router::new()
         .route("/profile-link", get(profile_link))
+        .route("/refresh", post(handle_refresh))
+        .route("/logout", post(handle_logout))
+        .route("/verify", get(verification_resend_page))
+        .route("/verify", post(handle_verification_resend))
+        .route("/verify/{token}", get(handle_verify_email))
         .nest("/signup", signup)
         .nest("/login", login)
+        .nest("/reset", reset)
+        .nest("/2fa", two_factor)
 }
 
 type HttpSignupError = Error<AppError<SignupError>, SignupForm>;
 
+type HttpInvitedSignupError = Error<AppError<InvitedSignupError>, InvitedSignupForm>;
+
 type HttpLoginError = Error<AppError<LoginError>, LoginForm>;
 
-async fn singup_page() -> Template<()> {
-    Template::new(SIGNUP_PAGE, ())
+type HttpRefreshError = Error<RefreshError>;
+
+type HttpLogoutError = Error<AuthError>;
+
+type HttpVerifyEmailError = Error<VerifyEmailError>;
+
+type HttpVerificationResendError = Error<Infallible, VerificationResendForm>;
+
+type HttpPasswordResetError = Error<Infallible, PasswordResetForm>;
+
+type HttpNewPasswordError = Error<AppError<ResetPasswordError>, NewPasswordForm>;
+
+type HttpTwoFactorError = Error<AppError<TwoFactorError>, TwoFactorForm>;
+
+type HttpTwoFactorSetupError = Error<TwoFactorError>;
+
+type HttpTwoFactorEnableError = Error<AppError<TwoFactorError>, TwoFactorEnableForm>;
+
+async fn singup_page(Query(query): Query<SignupQuery>) -> Template<SignupQuery> {
+    Template::new(SIGNUP_PAGE, query)
 }
 
 async fn login_page() -> Template<()> {
     Template::new(LOGIN_PAGE, ())
 }
 
+async fn password_reset_page() -> Template<()> {
+    Template::new(PASSWORD_RESET_PAGE, ())
+}
+
+async fn verification_resend_page() -> Template<()> {
+    Template::new(VERIFICATION_RESEND_PAGE, ())
+}
+
+async fn new_password_page() -> Template<()> {
+    Template::new(NEW_PASSWORD_PAGE, ())
+}
+
 async fn profile_link(user: User) -> Template<ProfileLinkData> {
     Template::new(PROFILE_LINK, ProfileLinkData { user })
 }
 
 async fn handle_signup(
     State(ctx): State<AppContext>,
+    Locale(locale): Locale,
     Form(form): Form<SignupForm>,
-) -> Result<Redirect, View<HttpSignupError>> {
+) -> Result<View<()>, View<HttpSignupError>> {
+    let form_copy = form.clone();
+    signup(&ctx, form, locale)
+        .await
+        .map(|_| View::new(VERIFICATION_SENT_PAGE, ()))
+        .map_err(|error| View::new(SIGNUP_PAGE, error.with_input(form_copy)))
+}
+
+async fn handle_invited_signup(
+    State(ctx): State<AppContext>,
+    Form(form): Form<InvitedSignupForm>,
+) -> Result<View<()>, View<HttpInvitedSignupError>> {
     let form_copy = form.clone();
-    signup(&ctx, form)
+    signup_with_invite(&ctx, form)
         .await
-        .map(|_| Redirect::to("/"))
+        .map(|_| View::new(EMAIL_VERIFIED_PAGE, ()))
         .map_err(|error| View::new(SIGNUP_PAGE, error.with_input(form_copy)))
 }
 
+async fn handle_verify_email(
+    State(ctx): State<AppContext>,
+    Path(token): Path<String>,
+) -> Result<Template<()>, Template<HttpVerifyEmailError>> {
+    verify_email(&ctx, token)
+        .await
+        .map(|_| Template::new(EMAIL_VERIFIED_PAGE, ()))
+        .map_err(|error| Template::new(TemplateName::error(), error))
+}
+
+async fn handle_verification_resend(
+    State(ctx): State<AppContext>,
+    Locale(locale): Locale,
+    Form(form): Form<VerificationResendForm>,
+) -> Result<View<()>, View<HttpVerificationResendError>> {
+    let form_copy = form.clone();
+    resend_verification(&ctx, form.email, locale)
+        .await
+        .map(|_| View::new(VERIFICATION_SENT_PAGE, ()))
+        .map_err(|error| View::new(VERIFICATION_RESEND_PAGE, error.with_input(form_copy)))
+}
+
 async fn handle_login(
     State(ctx): State<AppContext>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    jar: CookieJar,
     Form(form): Form<LoginForm>,
-) -> Result<View<LoginResponse>, View<HttpLoginError>> {
+) -> Result<Response, View<HttpLoginError>> {
     let form_copy = form.clone();
-    login(&ctx, form)
+    let outcome = login(&ctx, form, addr.ip())
         .await
-        .map(|access_token| View::new(AUTH_TOKEN_SCRIPT, LoginResponse { access_token }))
-        .map_err(|error| View::new(LOGIN_PAGE, error.with_input(form_copy)))
+        .map_err(|error| View::new(LOGIN_PAGE, error.with_input(form_copy)))?;
+    let response = match outcome {
+        LoginOutcome::Authenticated(tokens) => {
+            let (jar, tokens) = deliver_tokens(&ctx, jar, tokens);
+            (jar, View::new(AUTH_TOKEN_SCRIPT, tokens)).into_response()
+        }
+        LoginOutcome::TwoFactorRequired(prompt) => {
+            View::new(TWO_FACTOR_PAGE, prompt).into_response()
+        }
+    };
+    Ok(response)
+}
+
+async fn handle_two_factor(
+    State(ctx): State<AppContext>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    jar: CookieJar,
+    Form(form): Form<TwoFactorForm>,
+) -> Result<Response, View<HttpTwoFactorError>> {
+    let form_copy = form.clone();
+    let TwoFactorLogin {
+        tokens,
+        recovery_codes,
+    } = verify_two_factor(&ctx, form, addr.ip())
+        .await
+        .map_err(|error| View::new(TWO_FACTOR_PAGE, error.with_input(form_copy)))?;
+    let (jar, tokens) = deliver_tokens(&ctx, jar, tokens);
+    let response = match recovery_codes.is_empty() {
+        true => (jar, View::new(AUTH_TOKEN_SCRIPT, tokens)).into_response(),
+        false => {
+            let data = RecoveryCodesData::new(tokens, recovery_codes);
+            (jar, View::new(RECOVERY_CODES_PAGE, data)).into_response()
+        }
+    };
+    Ok(response)
+}
+
+async fn handle_two_factor_setup(
+    State(ctx): State<AppContext>,
+    Teacher(user): Teacher,
+) -> Result<View<TwoFactorProvisioning>, View<HttpTwoFactorSetupError>> {
+    setup_two_factor(&ctx, user)
+        .await
+        .map(|provisioning| View::new(TWO_FACTOR_SETUP_PAGE, provisioning))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
+async fn handle_two_factor_enable(
+    State(ctx): State<AppContext>,
+    Teacher(user): Teacher,
+    Form(form): Form<TwoFactorEnableForm>,
+) -> Result<View<RecoveryCodesData>, View<HttpTwoFactorEnableError>> {
+    let form_copy = form.clone();
+    enable_two_factor(&ctx, user, form)
+        .await
+        .map(|recovery_codes| RecoveryCodesData::new(None, recovery_codes))
+        .map(|data| View::new(RECOVERY_CODES_PAGE, data))
+        .map_err(|error| View::new(TWO_FACTOR_SETUP_PAGE, error.with_input(form_copy)))
+}
+
+async fn handle_refresh(
+    State(ctx): State<AppContext>,
+    jar: CookieJar,
+    Form(form): Form<RefreshForm>,
+) -> Result<(CookieJar, View<Option<AuthTokens>>), View<HttpRefreshError>> {
+    let refresh_token = form
+        .refresh_token
+        .or_else(|| {
+            jar.get(REFRESH_TOKEN_COOKIE)
+                .map(|cookie| cookie.value().to_owned())
+        })
+        .unwrap_or_default();
+    refresh(&ctx, refresh_token)
+        .await
+        .map(|tokens| deliver_tokens(&ctx, jar, tokens))
+        .map(|(jar, tokens)| (jar, View::new(AUTH_TOKEN_SCRIPT, tokens)))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
+async fn handle_logout(
+    token: AuthToken,
+    State(ctx): State<AppContext>,
+    jar: CookieJar,
+) -> Result<(CookieJar, View<()>), View<HttpLogoutError>> {
+    logout(&ctx, token)
+        .await
+        .map(|_| (end_session(jar), View::new(LOGOUT_SCRIPT, ())))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
+async fn handle_password_reset(
+    State(ctx): State<AppContext>,
+    Locale(locale): Locale,
+    Form(form): Form<PasswordResetForm>,
+) -> Result<View<()>, View<HttpPasswordResetError>> {
+    let form_copy = form.clone();
+    request_password_reset(&ctx, form.email, locale)
+        .await
+        .map(|_| View::new(PASSWORD_RESET_SENT_PAGE, ()))
+        .map_err(|error| View::new(PASSWORD_RESET_PAGE, error.with_input(form_copy)))
+}
+
+async fn handle_new_password(
+    State(ctx): State<AppContext>,
+    Path(token): Path<String>,
+    Form(form): Form<NewPasswordForm>,
+) -> Result<Redirect, View<HttpNewPasswordError>> {
+    let form_copy = form.clone();
+    reset_password(&ctx, token, form)
+        .await
+        .map(|_| Redirect::to("/auth/login"))
+        .map_err(|error| View::new(NEW_PASSWORD_PAGE, error.with_input(form_copy)))
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -100,6 +336,21 @@ struct SignupForm {
     name: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SignupQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invite: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InvitedSignupForm {
+    email: String,
+    #[serde(serialize_with = "serialize_secret")]
+    password: SecretString,
+    name: String,
+    invite: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct LoginForm {
     email: String,
@@ -107,10 +358,46 @@ struct LoginForm {
     password: SecretString,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VerificationResendForm {
+    email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PasswordResetForm {
+    email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NewPasswordForm {
+    #[serde(serialize_with = "serialize_secret")]
+    password: SecretString,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TwoFactorForm {
+    challenge: String,
+    #[serde(serialize_with = "serialize_secret")]
+    code: SecretString,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TwoFactorEnableForm {
+    #[serde(serialize_with = "serialize_secret")]
+    code: SecretString,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct LoginResponse {
-    access_token: AuthToken,
+struct RecoveryCodesData {
+    tokens: Option<AuthTokens>,
+    recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RefreshForm {
+    refresh_token: Option<String>,
 }
 
 impl HttpError for SignupError {
@@ -121,9 +408,96 @@ impl HttpError for SignupError {
     }
 }
 
+impl HttpError for InvitedSignupError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidInvite => StatusCode::BAD_REQUEST,
+            Self::EmailMismatch => StatusCode::BAD_REQUEST,
+            Self::EmailTaken => StatusCode::CONFLICT,
+        }
+    }
+}
+
 impl HttpError for LoginError {
     fn status_code(&self) -> StatusCode {
-        StatusCode::UNAUTHORIZED
+        match self {
+            Self::InvalidCredentials => StatusCode::UNAUTHORIZED,
+            Self::EmailNotVerified => StatusCode::FORBIDDEN,
+            Self::AccountDisabled => StatusCode::FORBIDDEN,
+            Self::TooManyAttempts(_) => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Self::TooManyAttempts(retry_after) = self {
+            headers.insert(RETRY_AFTER, HeaderValue::from(retry_after.as_secs()));
+        }
+        headers
+    }
+}
+
+impl HttpError for VerifyEmailError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidToken => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl HttpError for RefreshError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::SessionExpired => StatusCode::UNAUTHORIZED,
+        }
+    }
+}
+
+impl HttpError for ResetPasswordError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidToken => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl HttpError for TwoFactorError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::InvalidCode => StatusCode::UNAUTHORIZED,
+            Self::ChallengeExpired => StatusCode::UNAUTHORIZED,
+            Self::AlreadyEnabled => StatusCode::CONFLICT,
+            Self::TooManyAttempts(_) => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Self::TooManyAttempts(retry_after) = self {
+            headers.insert(RETRY_AFTER, HeaderValue::from(retry_after.as_secs()));
+        }
+        headers
+    }
+}
+
+impl HttpError for AuthError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Unauthenticated => StatusCode::UNAUTHORIZED,
+        }
+    }
+}
+
+impl RecoveryCodesData {
+    fn new(tokens: Option<AuthTokens>, recovery_codes: Vec<RecoveryCode>) -> Self {
+        let recovery_codes = recovery_codes
+            .iter()
+            .map(|code| code.expose_secret().to_owned())
+            .collect();
+        Self {
+            tokens,
+            recovery_codes,
+        }
     }
 }
 
@@ -139,6 +513,28 @@ impl TryFrom<SignupForm> for SignupData {
     }
 }
 
+impl TryFrom<InvitedSignupForm> for InvitedSignupData {
+    type Error = ValidationErrors;
+
+    fn try_from(value: InvitedSignupForm) -> Result<Self, Self::Error> {
+        let InvitedSignupForm {
+            email,
+            password,
+            name,
+            invite,
+        } = value;
+        let data = SignupData::try_from(SignupForm {
+            email,
+            password,
+            name,
+        })?;
+        Ok(Self {
+            data,
+            invite: InviteToken::new(invite),
+        })
+    }
+}
+
 impl TryFrom<LoginForm> for LoginData {
     type Error = ValidationErrors;
 
@@ -149,3 +545,30 @@ impl TryFrom<LoginForm> for LoginData {
         })
     }
 }
+
+impl TryFrom<NewPasswordForm> for NewPassword {
+    type Error = ValidationErrors;
+
+    fn try_from(value: NewPasswordForm) -> Result<Self, Self::Error> {
+        try_convert!(NewPasswordForm value => NewPassword { password })
+    }
+}
+
+impl TryFrom<TwoFactorForm> for TwoFactorData {
+    type Error = ValidationErrors;
+
+    fn try_from(value: TwoFactorForm) -> Result<Self, Self::Error> {
+        Ok(Self {
+            challenge: TwoFactorChallenge::new(value.challenge),
+            code: TwoFactorCode::new(value.code.expose_secret().to_owned()),
+        })
+    }
+}
+
+impl TryFrom<TwoFactorEnableForm> for TwoFactorCode {
+    type Error = ValidationErrors;
+
+    fn try_from(value: TwoFactorEnableForm) -> Result<Self, Self::Error> {
+        Ok(Self::new(value.code.expose_secret().to_owned()))
+    }
+}
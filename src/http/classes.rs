@@ -0,0 +1,214 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Form, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        enrollment::{
+            create_class, delete_class, get_class_roster, list_classes, set_student_enrolled,
+            set_subject_assigned,
+        },
+        validation::{try_convert, ValidationErrors},
+        AppContext, AppError,
+    },
+    domain::enrollment::{Class, ClassRoster, ClassSummary, EnrollmentError},
+    error::Error,
+};
+
+use super::{
+    error::HttpError,
+    middleware::{auth::Teacher, template::TemplateName, view::View},
+};
+
+const CLASSES_PAGE: &str = "classes.html";
+
+const CLASSES_TABLE: &str = "components/classes-table.html";
+
+const CLASS_PAGE: &str = "class.html";
+
+const CLASS_ROSTER: &str = "components/class-roster.html";
+
+const INLINE_ERROR: &str = "components/inline-error.html";
+
+pub fn classes_routes() -> Router<AppContext> {
+    let class_routes = Router::new()
+        .route("/", get(class_page).delete(handle_delete_class))
+        .route(
+            "/students/{student_id}",
+            post(handle_enroll_student).delete(handle_unenroll_student),
+        )
+        .route(
+            "/subjects/{subject_id}",
+            post(handle_assign_subject).delete(handle_unassign_subject),
+        );
+    Router::new()
+        .route("/", get(classes_page).post(handle_create_class))
+        .nest("/{class_id}", class_routes)
+}
+
+type HttpCreateClassError = Error<AppError<EnrollmentError>, ClassForm>;
+
+type HttpEnrollmentError = Error<EnrollmentError>;
+
+#[derive(Clone, Debug, Deserialize)]
+struct ClassPath {
+    class_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct StudentPath {
+    class_id: String,
+    student_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct SubjectPath {
+    class_id: String,
+    subject_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ClassForm {
+    id: String,
+    name: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct ClassesData {
+    classes: Vec<ClassSummary>,
+}
+
+async fn classes_page(
+    _: Teacher,
+    State(ctx): State<AppContext>,
+) -> Result<View<ClassesData>, View<Error>> {
+    classes_view(&ctx, CLASSES_PAGE)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))
+}
+
+async fn handle_create_class(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Form(form): Form<ClassForm>,
+) -> Result<View<ClassesData>, View<HttpCreateClassError>> {
+    let form_copy = form.clone();
+    create_class(&ctx, user, form)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error.with_input(form_copy.clone())))?;
+    classes_view(&ctx, CLASSES_TABLE).await.map_err(|error| {
+        View::new(
+            INLINE_ERROR,
+            Error::from_internal(error).with_input(form_copy),
+        )
+    })
+}
+
+async fn handle_delete_class(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<ClassPath>,
+) -> Result<View<ClassesData>, View<HttpEnrollmentError>> {
+    delete_class(&ctx, user, path.class_id)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    classes_view(&ctx, CLASSES_TABLE)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, Error::from_internal(error)))
+}
+
+async fn class_page(
+    _: Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<ClassPath>,
+) -> Result<View<ClassRoster>, View<HttpEnrollmentError>> {
+    get_class_roster(&ctx, path.class_id)
+        .await
+        .map(|roster| View::new(CLASS_PAGE, roster))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
+async fn handle_enroll_student(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<StudentPath>,
+) -> Result<View<ClassRoster>, View<HttpEnrollmentError>> {
+    set_student_enrolled(&ctx, user, path.class_id.clone(), path.student_id, true)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    roster_view(&ctx, path.class_id).await
+}
+
+async fn handle_unenroll_student(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<StudentPath>,
+) -> Result<View<ClassRoster>, View<HttpEnrollmentError>> {
+    set_student_enrolled(&ctx, user, path.class_id.clone(), path.student_id, false)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    roster_view(&ctx, path.class_id).await
+}
+
+async fn handle_assign_subject(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+) -> Result<View<ClassRoster>, View<HttpEnrollmentError>> {
+    set_subject_assigned(&ctx, user, path.class_id.clone(), path.subject_id, true)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    roster_view(&ctx, path.class_id).await
+}
+
+async fn handle_unassign_subject(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+) -> Result<View<ClassRoster>, View<HttpEnrollmentError>> {
+    set_subject_assigned(&ctx, user, path.class_id.clone(), path.subject_id, false)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    roster_view(&ctx, path.class_id).await
+}
+
+async fn classes_view(
+    ctx: &AppContext,
+    template_name: &'static str,
+) -> crate::Result<View<ClassesData>> {
+    list_classes(ctx)
+        .await
+        .map(|classes| View::new(template_name, ClassesData { classes }))
+}
+
+async fn roster_view(
+    ctx: &AppContext,
+    class_id: String,
+) -> Result<View<ClassRoster>, View<HttpEnrollmentError>> {
+    get_class_roster(ctx, class_id)
+        .await
+        .map(|roster| View::new(CLASS_ROSTER, roster))
+        .map_err(|error| View::new(INLINE_ERROR, error))
+}
+
+impl HttpError for EnrollmentError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::AlreadyExists => StatusCode::CONFLICT,
+        }
+    }
+}
+
+impl TryFrom<ClassForm> for Class {
+    type Error = ValidationErrors;
+
+    fn try_from(value: ClassForm) -> Result<Self, Self::Error> {
+        try_convert!(ClassForm value => Class { id, name })
+    }
+}
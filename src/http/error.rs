@@ -1,11 +1,15 @@
 use std::convert::Infallible;
 
-use axum::http::StatusCode;
+use axum::http::{HeaderMap, StatusCode};
 
 use crate::{app::AppError, error::ErrorKind};
 
 pub trait HttpError {
     fn status_code(&self) -> StatusCode;
+
+    fn headers(&self) -> HeaderMap {
+        HeaderMap::new()
+    }
 }
 
 impl HttpError for Infallible {
@@ -24,6 +28,13 @@ where
             ErrorKind::Expected(error) => error.status_code(),
         }
     }
+
+    fn headers(&self) -> HeaderMap {
+        match &self {
+            ErrorKind::Internal(_) => HeaderMap::new(),
+            ErrorKind::Expected(error) => error.headers(),
+        }
+    }
 }
 
 impl<E> HttpError for AppError<E>
@@ -36,4 +47,11 @@ where
             AppError::Logical(error) => error.status_code(),
         }
     }
+
+    fn headers(&self) -> HeaderMap {
+        match &self {
+            AppError::Validation(_) => HeaderMap::new(),
+            AppError::Logical(error) => error.headers(),
+        }
+    }
 }
@@ -1,25 +1,52 @@
-use std::{collections::HashSet, convert::Infallible};
+use std::collections::{HashMap, HashSet};
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Multipart, Path, Query, State},
+    http::{
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE, ETAG, IF_MATCH},
+        HeaderMap, HeaderName, StatusCode,
+    },
     response::{Html, IntoResponse, Response},
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Form, Router,
 };
+use mime::TEXT_CSV_UTF_8;
 use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
 
 use crate::{
     app::{
-        grades::{get_grade, get_grades, update_grade, UpdateGradeRequest},
+        dashboard::get_student_dashboard,
+        enrollment::get_roster_grades,
+        grade_entry::enter_grades,
+        grade_export::{export_grades, grades_csv},
+        grade_import::import_grades,
+        grade_stats::get_grade_statistics,
+        grades::{
+            get_filtered_grades, get_grade, get_grade_history, get_grade_page, remove_grade,
+            update_grade, GradePageRequest, RemoveGradeRequest, UpdateGradeRequest,
+        },
+        terms::select_term,
         validation::{try_convert, ValidationErrors},
         AppContext, AppError,
     },
     domain::{
         auth::User,
-        grades::{GetGradeError, GetGrades, GetStudentGrades, GetSubjects, GradeRecord, Subject},
+        dashboard::{DashboardError, StudentDashboard},
+        enrollment::RosterGrade,
+        grade_entry::{GradeEntryError, GradeEntryOutcome, GradeEntryResult, GradeEntryRow},
+        grade_export::GradeExportError,
+        grade_import::{GradeImportError, GradeImportOutcome},
+        grade_stats::SubjectStatistics,
+        grade_status::GradeStatus,
+        grade_version::GradeVersion,
+        grades::{
+            GetGradeError, GetStudentGrades, GetSubjects, GradeCursor, GradeHistory, GradeRecord,
+            RemoveGradeError, StudentGrade, Subject, UpdateGradeError,
+        },
         name::Name,
         role::Role,
+        terms::TermSelection,
         user_id::UserId,
     },
     error::Error,
@@ -28,8 +55,9 @@ use crate::{
 use super::{
     error::HttpError,
     middleware::{
-        template::{Template, TemplateName},
-        view::View,
+        auth::Teacher,
+        template::{Locale, Template, TemplateName},
+        view::{accepts_csv, View},
     },
 };
 
@@ -43,27 +71,100 @@ const TEACHER_GRADES: &str = "teacher-grades.html";
 
 const STUDENT_GRADES: &str = "student-grades.html";
 
+const ADMIN_HOME: &str = "components/admin-home.html";
+
+const GRADE_IMPORT: &str = "grade-import.html";
+
+const GRADE_HISTORY: &str = "grade-history.html";
+
+const GRADE_ENTRY: &str = "grade-entry.html";
+
+const GRADE_STATS: &str = "grade-stats.html";
+
+const STUDENT_DASHBOARD: &str = "student-dashboard.html";
+
+const UTF8_BOM: char = '\u{feff}';
+
+type ETagHeader = [(HeaderName, String); 1];
+
 pub fn grades_routes() -> Router<AppContext> {
     let grade_routes = Router::new()
         .route("/", get(grade))
         .route("/edit", get(grade_edit))
-        .route("/", put(edit_grade));
+        .route("/history", get(grade_history))
+        .route("/", put(edit_grade))
+        .route("/", delete(retract_grade));
     Router::new()
         .route("/", get(grades_page))
         .route("/add", get(grade_add))
         .route("/add", post(add_grade))
+        .route("/entry", get(grade_entry))
+        .route("/entry", post(handle_entry))
+        .route("/import", post(handle_import))
+        .route("/export", get(handle_export))
+        .route("/stats", get(grade_stats))
+        .route("/dashboard", get(student_dashboard))
         .nest("/{subject_id}/{student_id}", grade_routes)
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 struct GradesQuery {
     subject: Option<String>,
+    term: Option<String>,
+    search: Option<String>,
+    sort: Option<String>,
+    order: Option<String>,
+    after_grade: Option<String>,
+    after_primary: Option<String>,
+    after_secondary: Option<String>,
+    after_student: Option<String>,
+    after_subject: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct ExportQuery {
+    subject: Option<String>,
+    term: Option<String>,
+    format: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 struct TeacherGrades {
-    grades: Vec<GradeRecord>,
+    grades: Vec<RosterGrade>,
+    next: Option<GradeCursor>,
+    is_first_page: bool,
     subjects: Vec<Subject>,
+    selected_subject: Option<String>,
+    search: Option<String>,
+    sort: Option<String>,
+    order: Option<String>,
+    term: TermSelection,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct TermQuery {
+    term: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct GradeStats {
+    subjects: Vec<SubjectStatistics>,
+    term: TermSelection,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct DashboardData {
+    #[serde(flatten)]
+    dashboard: StudentDashboard,
+    term: TermSelection,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct StudentGrades {
+    grades: Vec<StudentGrade>,
+    term: TermSelection,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -73,45 +174,69 @@ struct GradePath {
 }
 
 async fn grade(
+    _: Teacher,
     State(ctx): State<AppContext>,
     Path(path): Path<GradePath>,
-) -> Result<Template<GradeRecord>, Template<Error<GetGradeError>>> {
+) -> Result<(ETagHeader, Template<GradeRecord>), Template<Error<GetGradeError>>> {
     get_grade(&ctx, path.subject_id, path.student_id)
         .await
-        .map(|grade| Template::new(GRADE, grade))
+        .map(|grade| (etag(&grade), Template::new(GRADE, grade)))
         .map_err(|error| Template::new(TemplateName::error(), error))
 }
 
 async fn grade_edit(
+    _: Teacher,
     State(ctx): State<AppContext>,
     Path(path): Path<GradePath>,
-) -> Result<Template<GradeRecord>, Template<Error<GetGradeError>>> {
+) -> Result<(ETagHeader, Template<GradeRecord>), Template<Error<GetGradeError>>> {
     get_grade(&ctx, path.subject_id, path.student_id)
         .await
-        .map(|grade| Template::new(GRADE_EDIT, grade))
+        .map(|grade| (etag(&grade), Template::new(GRADE_EDIT, grade)))
         .map_err(|error| Template::new(TemplateName::error(), error))
 }
 
+async fn grade_history(
+    _: Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<GradePath>,
+) -> Result<View<GradeHistory>, View<Error<GetGradeError>>> {
+    get_grade_history(&ctx, path.subject_id, path.student_id)
+        .await
+        .map(|history| View::new(GRADE_HISTORY, history))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
 struct Student {
     id: UserId,
     name: Name,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct GradeAddQuery {
+    subject_id: Option<String>,
+    student_id: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 struct GradeAddOptions {
     students: HashSet<Student>,
     subjects: HashSet<Subject>,
+    selected_subject: Option<String>,
+    selected_student: Option<String>,
 }
 
 async fn grade_add(
+    _: Teacher,
     State(ctx): State<AppContext>,
+    Query(query): Query<GradeAddQuery>,
 ) -> Result<Template<GradeAddOptions>, Template<Error>> {
-    let grades = ctx
-        .get_grades(None)
+    let roster = get_roster_grades(&ctx, None, None)
         .await
         .map_err(|error| Template::new(TemplateName::error(), error))?;
-    let students = grades
+    let students = roster
         .iter()
         .cloned()
         .map(|record| Student {
@@ -119,7 +244,7 @@ async fn grade_add(
             name: record.student_name,
         })
         .collect();
-    let subjects = grades
+    let subjects = roster
         .iter()
         .cloned()
         .map(|record| Subject {
@@ -127,7 +252,12 @@ async fn grade_add(
             title: record.subject_title,
         })
         .collect();
-    let options = GradeAddOptions { students, subjects };
+    let options = GradeAddOptions {
+        students,
+        subjects,
+        selected_subject: query.subject_id,
+        selected_student: query.student_id,
+    };
     Ok(Template::new(GRADE_ADD, options))
 }
 
@@ -137,18 +267,27 @@ struct NewGrade {
     subject_id: String,
     student_id: String,
     grade: String,
+    status: Option<String>,
+    #[serde(default)]
+    reason: String,
 }
 
 async fn add_grade(
+    Teacher(user): Teacher,
     State(ctx): State<AppContext>,
     Form(form): Form<NewGrade>,
-) -> Result<Html<&'static str>, View<Error<AppError<Infallible>>>> {
+) -> Result<Html<&'static str>, View<Error<AppError<UpdateGradeError>>>> {
     let req = GradeThing {
         subject: form.subject_id,
         student: form.student_id,
         grade: form.grade,
+        status: form
+            .status
+            .unwrap_or_else(|| GradeStatus::Graded.as_str().to_owned()),
+        version: String::new(),
+        reason: form.reason,
     };
-    update_grade(&ctx, req)
+    update_grade(&ctx, user, req)
         .await
         .map(|_| Html("<script>window.location = \"/\"</script>"))
         .map_err(|error| View::new(TemplateName::error(), error))
@@ -157,6 +296,11 @@ async fn add_grade(
 #[derive(Clone, Debug, Deserialize)]
 struct GradeForm {
     grade: String,
+    status: Option<String>,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    reason: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -164,45 +308,393 @@ struct GradeThing {
     pub subject: String,
     pub student: String,
     pub grade: String,
+    pub status: String,
+    pub version: String,
+    pub reason: String,
 }
 
 async fn edit_grade(
+    Teacher(user): Teacher,
     State(ctx): State<AppContext>,
     Path(path): Path<GradePath>,
+    headers: HeaderMap,
     Form(form): Form<GradeForm>,
-) -> Result<View<GradeRecord>, View<Error<AppError<Infallible>, GradeThing>>> {
+) -> Result<(ETagHeader, View<GradeRecord>), View<Error<AppError<UpdateGradeError>, GradeThing>>> {
+    let version = if_match(&headers).unwrap_or(form.version);
     let req = GradeThing {
         subject: path.subject_id,
         student: path.student_id,
         grade: form.grade,
+        status: form
+            .status
+            .unwrap_or_else(|| GradeStatus::Graded.as_str().to_owned()),
+        version,
+        reason: form.reason,
+    };
+    update_grade(&ctx, user, req.clone())
+        .await
+        .map(|grade| (etag(&grade), View::new(GRADE, grade)))
+        .map_err(|error| View::new(GRADE_EDIT, error.with_input(req)))
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct RetractQuery {
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GradeRetraction {
+    pub subject: String,
+    pub student: String,
+    pub version: String,
+    pub reason: String,
+}
+
+async fn retract_grade(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<GradePath>,
+    headers: HeaderMap,
+    Query(query): Query<RetractQuery>,
+) -> Result<(ETagHeader, View<GradeRecord>), View<Error<AppError<RemoveGradeError>, GradeRetraction>>>
+{
+    let req = GradeRetraction {
+        subject: path.subject_id,
+        student: path.student_id,
+        version: if_match(&headers).unwrap_or(query.version),
+        reason: query.reason,
     };
-    update_grade(&ctx, req.clone())
+    remove_grade(&ctx, user, req.clone())
         .await
-        .map(|grade| View::new(GRADE, grade))
+        .map(|grade| (etag(&grade), View::new(GRADE, grade)))
         .map_err(|error| View::new(GRADE_EDIT, error.with_input(req)))
 }
 
+fn if_match(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(IF_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim_start_matches("W/").trim_matches('"').to_owned())
+}
+
+fn etag(grade: &GradeRecord) -> ETagHeader {
+    let version: i64 = grade.version.into();
+    [(ETAG, format!("\"{version}\""))]
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct GradeEntryQuery {
+    subject: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GradeEntrySheet {
+    subjects: Vec<Subject>,
+    selected_subject: Option<String>,
+    rows: Vec<GradeEntrySheetRow>,
+    saved_count: Option<usize>,
+    invalid_count: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GradeEntrySheetRow {
+    student_id: UserId,
+    student_name: Name,
+    grade: String,
+    status: String,
+    version: Option<GradeVersion>,
+    errors: ValidationErrors,
+}
+
+async fn grade_entry(
+    _: Teacher,
+    State(ctx): State<AppContext>,
+    Query(query): Query<GradeEntryQuery>,
+) -> Result<View<GradeEntrySheet>, View<Error>> {
+    grade_entry_sheet(&ctx, query.subject, None)
+        .await
+        .map(|sheet| View::new(GRADE_ENTRY, sheet))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
+async fn handle_entry(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Query(query): Query<GradeEntryQuery>,
+    Form(fields): Form<Vec<(String, String)>>,
+) -> Result<View<GradeEntrySheet>, View<Error<GradeEntryError>>> {
+    let outcome = enter_grades(&ctx, user, entry_rows(fields))
+        .await
+        .map_err(|error| View::new(TemplateName::error(), error))?;
+    grade_entry_sheet(&ctx, query.subject, Some(outcome))
+        .await
+        .map(|sheet| View::new(GRADE_ENTRY, sheet))
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))
+}
+
+fn entry_rows(fields: Vec<(String, String)>) -> Vec<GradeEntryRow> {
+    let mut rows = Vec::<GradeEntryRow>::new();
+    for (name, value) in fields {
+        if name == "student" {
+            rows.push(GradeEntryRow {
+                student: value,
+                status: GradeStatus::Graded.as_str().to_owned(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(row) = rows.last_mut() else {
+            continue;
+        };
+        match name.as_str() {
+            "subject" => row.subject = value,
+            "grade" => row.grade = value,
+            "status" => row.status = value,
+            "version" => row.version = value,
+            _ => {}
+        }
+    }
+    rows
+}
+
+async fn grade_entry_sheet(
+    ctx: &AppContext,
+    subject: Option<String>,
+    outcome: Option<GradeEntryOutcome>,
+) -> crate::Result<GradeEntrySheet> {
+    let subjects = ctx.get_subjects().await?;
+    let roster = match &subject {
+        Some(subject) => get_roster_grades(ctx, Some(subject.clone()), None).await?,
+        None => Vec::new(),
+    };
+    let (mut failed, saved_count, invalid_count) = match outcome {
+        Some(outcome) => {
+            let failed = outcome
+                .rows
+                .into_iter()
+                .filter(|result| {
+                    !result.errors.is_empty() && Some(&result.row.subject) == subject.as_ref()
+                })
+                .filter_map(|result| {
+                    UserId::try_from(result.row.student.clone())
+                        .ok()
+                        .map(|student| (student, result))
+                })
+                .collect::<HashMap<_, _>>();
+            (
+                failed,
+                Some(outcome.saved_count),
+                Some(outcome.invalid_count),
+            )
+        }
+        None => (HashMap::new(), None, None),
+    };
+    let rows = roster
+        .into_iter()
+        .map(|grade| match failed.remove(&grade.student_id) {
+            Some(GradeEntryResult { row, errors }) => GradeEntrySheetRow {
+                student_id: grade.student_id,
+                student_name: grade.student_name,
+                grade: row.grade,
+                status: row.status,
+                version: grade.version,
+                errors,
+            },
+            None => GradeEntrySheetRow {
+                student_id: grade.student_id,
+                student_name: grade.student_name,
+                grade: grade.scaled_value.unwrap_or_default(),
+                status: grade.status.as_str().to_owned(),
+                version: grade.version,
+                errors: ValidationErrors::default(),
+            },
+        })
+        .collect();
+    Ok(GradeEntrySheet {
+        subjects,
+        selected_subject: subject,
+        rows,
+        saved_count,
+        invalid_count,
+    })
+}
+
+async fn handle_import(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    multipart: Multipart,
+) -> Result<View<GradeImportOutcome>, View<Error<GradeImportError>>> {
+    let (content, confirm) = read_import_upload(multipart)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), error))?;
+    import_grades(&ctx, user, content, confirm)
+        .await
+        .map(|outcome| View::new(GRADE_IMPORT, outcome))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
+async fn read_import_upload(
+    mut multipart: Multipart,
+) -> crate::Result<(String, bool), GradeImportError> {
+    let invalid_file = |_| crate::Error::expected(GradeImportError::InvalidFile);
+    let mut content = None;
+    let mut confirm = false;
+    while let Some(field) = multipart.next_field().await.map_err(invalid_file)? {
+        match field.name() {
+            Some("file") => {
+                let bytes = field.bytes().await.map_err(invalid_file)?;
+                let text = String::from_utf8(bytes.to_vec())
+                    .map_err(|_| crate::Error::expected(GradeImportError::InvalidFile))?;
+                content = Some(text.trim_start_matches(UTF8_BOM).to_owned());
+            }
+            Some("content") => content = Some(field.text().await.map_err(invalid_file)?),
+            Some("confirm") => confirm = field.text().await.map_err(invalid_file)? == "true",
+            _ => {}
+        }
+    }
+    content
+        .map(|content| (content, confirm))
+        .ok_or(crate::Error::expected(GradeImportError::InvalidFile))
+}
+
+async fn handle_export(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Locale(locale): Locale,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, View<Error<GradeExportError>>> {
+    let export = export_grades(&ctx, user, query.subject, query.term, query.format, locale)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), error))?;
+    let file_name = match export.subject {
+        Some(subject) => format!("grades-{}", file_name_part(subject.into())),
+        None => "grades".to_owned(),
+    };
+    let disposition = format!(
+        "attachment; filename=\"{file_name}.{}\"",
+        export.format.extension()
+    );
+    let headers = [
+        (CONTENT_TYPE, export.format.content_type().to_owned()),
+        (CONTENT_DISPOSITION, disposition),
+    ];
+    Ok((headers, export.content).into_response())
+}
+
+fn file_name_part(value: String) -> String {
+    value
+        .chars()
+        .map(
+            |char| match char.is_ascii_alphanumeric() || char == '-' || char == '_' {
+                true => char,
+                false => '_',
+            },
+        )
+        .collect()
+}
+
+async fn grade_stats(
+    _: Teacher,
+    State(ctx): State<AppContext>,
+    Query(query): Query<TermQuery>,
+) -> Result<View<GradeStats>, View<Error>> {
+    grade_stats_view(&ctx, query.term)
+        .await
+        .map(|stats| View::new(GRADE_STATS, stats))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
+async fn grade_stats_view(ctx: &AppContext, term: Option<String>) -> crate::Result<GradeStats> {
+    let term = select_term(ctx, term).await?;
+    let subjects = get_grade_statistics(ctx, term.selected_id()).await?;
+    Ok(GradeStats { subjects, term })
+}
+
+async fn student_dashboard(
+    user: User,
+    State(ctx): State<AppContext>,
+    Query(query): Query<TermQuery>,
+) -> Result<View<DashboardData>, View<Error<DashboardError>>> {
+    let term = select_term(&ctx, query.term)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))?;
+    get_student_dashboard(&ctx, user, term.selected_id())
+        .await
+        .map(|dashboard| View::new(STUDENT_DASHBOARD, DashboardData { dashboard, term }))
+        .map_err(|error| View::new(TemplateName::error(), error))
+}
+
 async fn grades_page(
     user: User,
     State(ctx): State<AppContext>,
+    Locale(locale): Locale,
+    headers: HeaderMap,
     Query(query): Query<GradesQuery>,
 ) -> Result<Response, Template<Error>> {
     match user.role {
-        Role::Teacher => {
-            let grades = get_grades(&ctx, query.subject).await;
-            let subjects = ctx.get_subjects().await;
-            grades
-                .and_then(|grades| subjects.map(|subjects| TeacherGrades { grades, subjects }))
-                .map(|grades| Template::new(TEACHER_GRADES, grades).into_response())
-        }
-        Role::Student => ctx
-            .get_student_grades(user.id)
-            .await
-            .map(|grades| Template::new(STUDENT_GRADES, grades).into_response()),
+        Role::Teacher => teacher_grades(&ctx, query, accepts_csv(&headers), &locale).await,
+        Role::Student => student_grades(&ctx, user, query).await,
+        Role::Admin => Ok(Template::new(ADMIN_HOME, ()).into_response()),
     }
     .map_err(|error| Template::new(TemplateName::error(), error))
 }
 
+async fn teacher_grades(
+    ctx: &AppContext,
+    query: GradesQuery,
+    is_csv: bool,
+    locale: &LanguageIdentifier,
+) -> crate::Result<Response> {
+    let term = select_term(ctx, query.term).await?;
+    let is_first_page = query.after_student.is_none();
+    let req = GradePageRequest {
+        subject: query.subject.clone(),
+        search: query.search.clone(),
+        sort: query.sort.clone(),
+        order: query.order.clone(),
+        after_grade: query.after_grade,
+        after_primary: query.after_primary,
+        after_secondary: query.after_secondary,
+        after_student: query.after_student,
+        after_subject: query.after_subject,
+    };
+    if is_csv {
+        let grades = get_filtered_grades(ctx, term.selected_id(), req).await?;
+        let csv = grades_csv(ctx, grades, locale)?;
+        return Ok(([(CONTENT_TYPE, TEXT_CSV_UTF_8.as_ref())], csv).into_response());
+    }
+    let page = get_grade_page(ctx, term.selected_id(), req).await?;
+    let subjects = ctx.get_subjects().await?;
+    let data = TeacherGrades {
+        grades: page.grades,
+        next: page.next,
+        is_first_page,
+        subjects,
+        selected_subject: query.subject,
+        search: query.search,
+        sort: query.sort,
+        order: query.order,
+        term,
+    };
+    Ok(View::new(TEACHER_GRADES, data).into_response())
+}
+
+async fn student_grades(
+    ctx: &AppContext,
+    user: User,
+    query: GradesQuery,
+) -> crate::Result<Response> {
+    let term = select_term(ctx, query.term).await?;
+    let grades = ctx.get_student_grades(user.id, term.selected_id()).await?;
+    let data = StudentGrades { grades, term };
+    Ok(Template::new(STUDENT_GRADES, data).into_response())
+}
+
 impl HttpError for GetGradeError {
     fn status_code(&self) -> StatusCode {
         match self {
@@ -211,6 +703,63 @@ impl HttpError for GetGradeError {
     }
 }
 
+impl HttpError for GradeExportError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::UnsupportedFormat => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl HttpError for RemoveGradeError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
+        }
+    }
+}
+
+impl HttpError for DashboardError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+impl HttpError for GradeEntryError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+impl HttpError for GradeImportError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::InvalidFile => StatusCode::BAD_REQUEST,
+            Self::MissingColumns => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+}
+
+impl HttpError for UpdateGradeError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::NotEnrolled => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Computed => StatusCode::CONFLICT,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
+        }
+    }
+}
+
 impl TryFrom<GradeThing> for UpdateGradeRequest {
     type Error = ValidationErrors;
 
@@ -218,7 +767,23 @@ impl TryFrom<GradeThing> for UpdateGradeRequest {
         try_convert!(GradeThing value => UpdateGradeRequest {
             student,
             subject,
-            grade
+            grade,
+            status,
+            version,
+            reason
+        })
+    }
+}
+
+impl TryFrom<GradeRetraction> for RemoveGradeRequest {
+    type Error = ValidationErrors;
+
+    fn try_from(value: GradeRetraction) -> Result<Self, Self::Error> {
+        try_convert!(GradeRetraction value => RemoveGradeRequest {
+            student,
+            subject,
+            version,
+            reason
         })
     }
 }
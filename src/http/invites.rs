@@ -0,0 +1,72 @@
+use axum::{extract::State, http::StatusCode, routing::get, Form, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        invites::invite_teacher,
+        validation::{try_convert, ValidationErrors},
+        AppContext, AppError,
+    },
+    domain::{
+        auth::User,
+        invites::{InviteData, InviteError},
+    },
+    error::Error,
+};
+
+use super::{
+    error::HttpError,
+    middleware::{
+        template::{Locale, Template},
+        view::View,
+    },
+};
+
+const INVITE_PAGE: &str = "invite.html";
+
+const INVITE_SENT_PAGE: &str = "invite-sent.html";
+
+pub fn invites_routes() -> Router<AppContext> {
+    Router::new().route("/", get(invite_page).post(handle_invite))
+}
+
+type HttpInviteError = Error<AppError<InviteError>, InviteForm>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InviteForm {
+    email: String,
+}
+
+async fn invite_page(_: User) -> Template<()> {
+    Template::new(INVITE_PAGE, ())
+}
+
+async fn handle_invite(
+    user: User,
+    State(ctx): State<AppContext>,
+    Locale(locale): Locale,
+    Form(form): Form<InviteForm>,
+) -> Result<View<()>, View<HttpInviteError>> {
+    let form_copy = form.clone();
+    invite_teacher(&ctx, user, form, locale)
+        .await
+        .map(|_| View::new(INVITE_SENT_PAGE, ()))
+        .map_err(|error| View::new(INVITE_PAGE, error.with_input(form_copy)))
+}
+
+impl HttpError for InviteError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::EmailTaken => StatusCode::CONFLICT,
+        }
+    }
+}
+
+impl TryFrom<InviteForm> for InviteData {
+    type Error = ValidationErrors;
+
+    fn try_from(value: InviteForm) -> Result<Self, Self::Error> {
+        try_convert!(InviteForm value => InviteData { email })
+    }
+}
@@ -1,54 +1,134 @@
 use axum::{
     extract::FromRequestParts,
     http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
 };
+use axum_extra::extract::CookieJar;
 
 use crate::{
     app::{auth::authenticate, localization::LocalizedError, AppContext},
-    domain::{auth::User, token::AuthToken},
+    domain::{auth::User, role::Role, token::AuthToken},
     error::Error,
     http::{error::HttpError, middleware::template::TemplateName},
 };
 
-use super::view::View;
+use super::{session::ACCESS_TOKEN_COOKIE, view::View};
 
 #[derive(Debug)]
 pub struct Unauthorized;
 
-impl FromRequestParts<AppContext> for User {
+#[derive(Debug)]
+pub struct Forbidden;
+
+#[derive(Debug, Clone)]
+pub struct Teacher(pub User);
+
+#[derive(Debug, Clone)]
+pub struct Admin(pub User);
+
+impl<S> FromRequestParts<S> for AuthToken
+where
+    S: Send + Sync,
+{
     type Rejection = View<Error<Unauthorized>>;
 
-    async fn from_request_parts(
-        parts: &mut Parts,
-        ctx: &AppContext,
-    ) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
         let parse_bearer: fn(&str) -> Option<&str> = |auth: &str| match auth.split_once(' ') {
             Some(("Bearer", token)) => Some(token),
             _ => None,
         };
-        let token = parts
+        let cookie_token = || {
+            CookieJar::from_headers(&parts.headers)
+                .get(ACCESS_TOKEN_COOKIE)
+                .map(|cookie| cookie.value().to_owned())
+        };
+        parts
             .headers
             .get(AUTHORIZATION)
             .and_then(|header| header.to_str().ok().and_then(parse_bearer))
-            .map(|value| AuthToken::new(value.to_owned()));
-        let user = match token {
-            Some(token) => authenticate(ctx, token).await.ok(),
-            None => None,
-        };
-        user.ok_or(Error::expected(Unauthorized))
+            .map(ToOwned::to_owned)
+            .or_else(cookie_token)
+            .map(AuthToken::new)
+            .ok_or(Error::expected(Unauthorized))
+            .inspect_err(|error| tracing::info!(?error))
+            .map_err(|error| View::new(TemplateName::error(), error))
+    }
+}
+
+impl FromRequestParts<AppContext> for User {
+    type Rejection = View<Error<Unauthorized>>;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        ctx: &AppContext,
+    ) -> Result<Self, Self::Rejection> {
+        let token = AuthToken::from_request_parts(parts, ctx).await?;
+        authenticate(ctx, token)
+            .await
+            .map_err(|_| Error::expected(Unauthorized))
             .inspect_err(|error| tracing::info!(?error))
             .map_err(|error| View::new(TemplateName::error(), error))
     }
 }
 
+impl FromRequestParts<AppContext> for Teacher {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        ctx: &AppContext,
+    ) -> Result<Self, Self::Rejection> {
+        let user = User::from_request_parts(parts, ctx)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        match user.role {
+            Role::Teacher => Ok(Self(user)),
+            Role::Student | Role::Admin => Err(Error::expected(Forbidden))
+                .inspect_err(|error| tracing::info!(?error))
+                .map_err(|error| View::new(TemplateName::error(), error).into_response()),
+        }
+    }
+}
+
+impl FromRequestParts<AppContext> for Admin {
+    type Rejection = Response;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        ctx: &AppContext,
+    ) -> Result<Self, Self::Rejection> {
+        let user = User::from_request_parts(parts, ctx)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        match user.role {
+            Role::Admin => Ok(Self(user)),
+            Role::Teacher | Role::Student => Err(Error::expected(Forbidden))
+                .inspect_err(|error| tracing::info!(?error))
+                .map_err(|error| View::new(TemplateName::error(), error).into_response()),
+        }
+    }
+}
+
 impl HttpError for Unauthorized {
     fn status_code(&self) -> StatusCode {
         StatusCode::FORBIDDEN
     }
 }
 
+impl HttpError for Forbidden {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::FORBIDDEN
+    }
+}
+
 impl From<Unauthorized> for LocalizedError {
     fn from(_: Unauthorized) -> Self {
         Self::new("UNAUTHORIZED")
     }
 }
+
+impl From<Forbidden> for LocalizedError {
+    fn from(_: Forbidden) -> Self {
+        Self::new("FORBIDDEN")
+    }
+}
@@ -0,0 +1,107 @@
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, HeaderName, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    app::{localization::LocalizedError, AppContext},
+    http::error::HttpError,
+};
+
+use super::{template::TemplateName, view::View};
+
+const CSRF_COOKIE: &str = "csrf_token";
+
+static CSRF_HEADER: HeaderName = HeaderName::from_static("x-csrf-token");
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(transparent)]
+pub struct CsrfToken(String);
+
+#[derive(Debug)]
+pub struct CsrfFailed;
+
+impl CsrfToken {
+    fn new() -> Self {
+        Self(Uuid::new_v4().simple().to_string())
+    }
+
+    fn matches(&self, submitted: &str) -> bool {
+        let expected = self.0.as_bytes();
+        let submitted = submitted.as_bytes();
+        expected.len() == submitted.len()
+            && expected
+                .iter()
+                .zip(submitted)
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+}
+
+pub(super) async fn verify_csrf(
+    State(ctx): State<AppContext>,
+    jar: CookieJar,
+    req: Request,
+    next: Next,
+) -> Response {
+    let (jar, token, is_issued) = match jar.get(CSRF_COOKIE) {
+        Some(cookie) => {
+            let token = CsrfToken(cookie.value().to_owned());
+            (jar, token, false)
+        }
+        None => {
+            let token = CsrfToken::new();
+            (with_csrf_cookie(&ctx, jar, &token), token, true)
+        }
+    };
+    let is_safe = matches!(
+        *req.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE
+    );
+    let is_bearer = req.headers().contains_key(AUTHORIZATION);
+    let is_submitted = !is_issued
+        && req
+            .headers()
+            .get(&CSRF_HEADER)
+            .and_then(|header| header.to_str().ok())
+            .is_some_and(|submitted| token.matches(submitted));
+    let mut response = if is_safe || is_bearer || is_submitted {
+        next.run(req).await
+    } else {
+        let error = crate::Error::expected(CsrfFailed);
+        tracing::info!(?error);
+        View::new(TemplateName::error(), error).into_response()
+    };
+    response.extensions_mut().insert(token);
+    (jar, response).into_response()
+}
+
+fn with_csrf_cookie(ctx: &AppContext, jar: CookieJar, token: &CsrfToken) -> CookieJar {
+    let secure = ctx
+        .token_issuer
+        .cookie_config()
+        .is_some_and(|config| config.secure);
+    let cookie = Cookie::build((CSRF_COOKIE, token.0.clone()))
+        .path("/")
+        .http_only(true)
+        .secure(secure)
+        .same_site(SameSite::Lax);
+    jar.add(cookie)
+}
+
+impl HttpError for CsrfFailed {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::FORBIDDEN
+    }
+}
+
+impl From<CsrfFailed> for LocalizedError {
+    fn from(_: CsrfFailed) -> Self {
+        Self::new("CSRF_FAILED")
+    }
+}
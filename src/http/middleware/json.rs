@@ -30,7 +30,8 @@ where
 {
     fn into_response(self) -> Response {
         let status = self.0.kind.status_code();
+        let headers = self.0.kind.headers();
         let response = HttpResponse::error(self.0);
-        (status, Json(response)).into_response()
+        (status, headers, Json(response)).into_response()
     }
 }
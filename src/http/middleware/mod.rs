@@ -1,13 +1,17 @@
 pub mod auth;
+pub mod csrf;
 pub mod json;
 pub mod not_found;
 pub mod panic;
+pub mod session;
 pub mod template;
 pub mod view;
 
 mod response;
 
 use axum::{middleware, Router};
+use csrf::verify_csrf;
+use session::refresh_session;
 use template::render_template;
 use view::render_view;
 
@@ -15,6 +19,10 @@ use crate::app::AppContext;
 
 pub trait RouterExt {
     fn with_renderers(self, ctx: AppContext) -> Self;
+
+    fn with_sessions(self, ctx: AppContext) -> Self;
+
+    fn with_csrf(self, ctx: AppContext) -> Self;
 }
 
 impl RouterExt for Router<AppContext> {
@@ -22,4 +30,12 @@ impl RouterExt for Router<AppContext> {
         self.layer(middleware::from_fn(render_view))
             .layer(middleware::from_fn_with_state(ctx.clone(), render_template))
     }
+
+    fn with_sessions(self, ctx: AppContext) -> Self {
+        self.layer(middleware::from_fn_with_state(ctx, refresh_session))
+    }
+
+    fn with_csrf(self, ctx: AppContext) -> Self {
+        self.layer(middleware::from_fn_with_state(ctx, verify_csrf))
+    }
 }
@@ -0,0 +1,102 @@
+use axum::{
+    extract::{Request, State},
+    http::{header::AUTHORIZATION, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+
+use crate::{
+    app::{auth::refresh, AppContext},
+    domain::{
+        auth::{AuthTokens, ParseToken, RefreshError},
+        token::AuthToken,
+    },
+    error::ErrorKind,
+    services::token_issuer::{self, CookieConfig},
+};
+
+pub const ACCESS_TOKEN_COOKIE: &str = "access_token";
+
+pub const REFRESH_TOKEN_COOKIE: &str = "refresh_token";
+
+pub fn deliver_tokens(
+    ctx: &AppContext,
+    jar: CookieJar,
+    tokens: AuthTokens,
+) -> (CookieJar, Option<AuthTokens>) {
+    match ctx.token_issuer.cookie_config() {
+        Some(config) => (with_session_cookies(ctx, config, jar, tokens), None),
+        None => (jar, Some(tokens)),
+    }
+}
+
+pub fn end_session(jar: CookieJar) -> CookieJar {
+    jar.remove(Cookie::build(ACCESS_TOKEN_COOKIE).path("/"))
+        .remove(Cookie::build(REFRESH_TOKEN_COOKIE).path("/"))
+}
+
+pub(super) async fn refresh_session(
+    State(ctx): State<AppContext>,
+    jar: CookieJar,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let Some(config) = ctx.token_issuer.cookie_config() else {
+        return next.run(req).await;
+    };
+    if req.headers().contains_key(AUTHORIZATION) {
+        return next.run(req).await;
+    }
+    let has_valid_access_token = jar.get(ACCESS_TOKEN_COOKIE).is_some_and(|cookie| {
+        ctx.parse_token(AuthToken::new(cookie.value().to_owned()))
+            .is_ok()
+    });
+    let refresh_token = jar
+        .get(REFRESH_TOKEN_COOKIE)
+        .map(|cookie| cookie.value().to_owned());
+    let (false, Some(refresh_token)) = (has_valid_access_token, refresh_token) else {
+        return next.run(req).await;
+    };
+    match refresh(&ctx, refresh_token).await {
+        Ok(tokens) => {
+            let access_token = Into::<String>::into(tokens.access_token.clone());
+            if let Ok(header) = HeaderValue::try_from(format!("Bearer {access_token}")) {
+                req.headers_mut().insert(AUTHORIZATION, header);
+            }
+            let jar = with_session_cookies(&ctx, config, jar, tokens);
+            (jar, next.run(req).await).into_response()
+        }
+        Err(error) => match error.kind {
+            ErrorKind::Expected(RefreshError::SessionExpired) => {
+                (end_session(jar), next.run(req).await).into_response()
+            }
+            ErrorKind::Internal(_) => next.run(req).await,
+        },
+    }
+}
+
+fn with_session_cookies(
+    ctx: &AppContext,
+    config: &CookieConfig,
+    jar: CookieJar,
+    tokens: AuthTokens,
+) -> CookieJar {
+    let max_age =
+        time::Duration::try_from(ctx.token_issuer.session_ttl()).unwrap_or(time::Duration::MAX);
+    let same_site = match config.same_site {
+        token_issuer::SameSite::Strict => SameSite::Strict,
+        token_issuer::SameSite::Lax => SameSite::Lax,
+        token_issuer::SameSite::None => SameSite::None,
+    };
+    let cookie = |name: &'static str, value: String| {
+        Cookie::build((name, value))
+            .path("/")
+            .http_only(true)
+            .secure(config.secure)
+            .same_site(same_site)
+            .max_age(max_age)
+    };
+    jar.add(cookie(ACCESS_TOKEN_COOKIE, tokens.access_token.into()))
+        .add(cookie(REFRESH_TOKEN_COOKIE, tokens.refresh_token.into()))
+}
@@ -1,8 +1,8 @@
-use std::{borrow::Cow, ops::Deref};
+use std::{borrow::Cow, convert::Infallible, ops::Deref};
 
 use axum::{
-    extract::{Request, State},
-    http::header::ACCEPT_LANGUAGE,
+    extract::{FromRequestParts, Request, State},
+    http::{header::ACCEPT_LANGUAGE, request::Parts, HeaderValue},
     middleware::Next,
     response::{Html, IntoResponse, Response},
     Extension,
@@ -12,7 +12,10 @@ use unic_langid::LanguageIdentifier;
 
 use crate::{app::AppContext, http::error::HttpError};
 
-use super::response::{ErrorType, HttpResponse};
+use super::{
+    csrf::CsrfToken,
+    response::{ErrorType, HttpResponse},
+};
 
 pub trait TemplateRenderer {
     fn render_template<T>(
@@ -20,6 +23,7 @@ pub trait TemplateRenderer {
         template_name: &str,
         data: T,
         locale: &LanguageIdentifier,
+        csrf_token: Option<&CsrfToken>,
     ) -> crate::Result<String>
     where
         T: Serialize;
@@ -38,6 +42,9 @@ pub struct Template<T> {
 #[derive(Clone, Debug)]
 pub struct TemplateName(Cow<'static, str>);
 
+#[derive(Clone, Debug)]
+pub struct Locale(pub LanguageIdentifier);
+
 impl<T> Template<T> {
     pub fn new(template_name: impl Into<TemplateName>, data: T) -> Self {
         Self {
@@ -63,30 +70,59 @@ pub(super) async fn render_template(
     let Some(template) = response.extensions_mut().remove::<Template<HttpResponse>>() else {
         return response;
     };
+    let locale = negotiate_locale(&ctx, accept_language_header.as_ref());
     let renderer = ctx.templating_engine;
+    let csrf_token = response.extensions().get::<CsrfToken>().cloned();
+    let html = match renderer.render_template(
+        &template.template_name,
+        template.data.message,
+        &locale,
+        csrf_token.as_ref(),
+    ) {
+        Ok(html) => html,
+        Err(error) => {
+            response = Template::new(TemplateName::error(), error).into_response();
+            let template = response
+                .extensions_mut()
+                .remove::<Template<HttpResponse>>()
+                .unwrap();
+            renderer
+                .render_template(
+                    &template.template_name,
+                    template.data.message,
+                    &locale,
+                    csrf_token.as_ref(),
+                )
+                .expect("render error template")
+        }
+    };
+    let (parts, _) = response.into_parts();
+    (parts, Html(html)).into_response()
+}
+
+fn negotiate_locale(
+    ctx: &AppContext,
+    accept_language_header: Option<&HeaderValue>,
+) -> LanguageIdentifier {
     let language_preferences = accept_language_header
         .and_then(|header| header.to_str().ok().map(accept_language::parse))
         .unwrap_or_default()
         .into_iter()
         .filter_map(|lang| lang.parse::<LanguageIdentifier>().ok())
         .collect::<Vec<_>>();
-    let locale = ctx.localizer.negotiate_locale(language_preferences);
-    let html =
-        match renderer.render_template(&template.template_name, template.data.message, &locale) {
-            Ok(html) => html,
-            Err(error) => {
-                response = Template::new(TemplateName::error(), error).into_response();
-                let template = response
-                    .extensions_mut()
-                    .remove::<Template<HttpResponse>>()
-                    .unwrap();
-                renderer
-                    .render_template(&template.template_name, template.data.message, &locale)
-                    .expect("render error template")
-            }
-        };
-    let (parts, _) = response.into_parts();
-    (parts, Html(html)).into_response()
+    ctx.localizer.negotiate_locale(language_preferences)
+}
+
+impl FromRequestParts<AppContext> for Locale {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        ctx: &AppContext,
+    ) -> Result<Self, Self::Rejection> {
+        let accept_language_header = parts.headers.get(ACCEPT_LANGUAGE);
+        Ok(Self(negotiate_locale(ctx, accept_language_header)))
+    }
 }
 
 impl IntoResponse for Template<HttpResponse> {
@@ -112,9 +148,10 @@ where
 {
     fn into_response(self) -> Response {
         let status = self.data.kind.status_code();
+        let headers = self.data.kind.headers();
         let response = HttpResponse::error(self.data);
         let template = Template::new(self.template_name, response);
-        (status, template).into_response()
+        (status, headers, template).into_response()
     }
 }
 
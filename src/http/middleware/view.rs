@@ -1,12 +1,12 @@
 use accept_header::Accept;
 use axum::{
     extract::Request,
-    http::header::ACCEPT,
+    http::{header::ACCEPT, HeaderMap, HeaderValue},
     middleware::Next,
     response::{IntoResponse, Response},
     Extension,
 };
-use mime::{APPLICATION_JSON, TEXT_HTML};
+use mime::{Mime, APPLICATION_JSON, TEXT_CSV, TEXT_HTML};
 use serde::Serialize;
 
 use crate::http::error::HttpError;
@@ -32,21 +32,31 @@ impl<T> View<T> {
     }
 }
 
-pub(super) async fn render_view(req: Request, next: Next) -> Response {
-    let accept_header = req.headers().get(ACCEPT).cloned();
-    let mut response = next.run(req).await;
-    let Some(view) = response.extensions_mut().remove::<View<HttpResponse>>() else {
-        return response;
-    };
-    let preference = accept_header
+pub fn accepts_csv(headers: &HeaderMap) -> bool {
+    let available = [APPLICATION_JSON, TEXT_HTML, TEXT_CSV];
+    negotiate(headers.get(ACCEPT), &available) == TEXT_CSV
+}
+
+fn negotiate(accept_header: Option<&HeaderValue>, available: &[Mime]) -> Mime {
+    accept_header
         .and_then(|header| {
             header
                 .to_str()
                 .ok()
                 .and_then(|header| header.parse::<Accept>().ok())
         })
-        .and_then(|accept| accept.negotiate(&[APPLICATION_JSON, TEXT_HTML]).ok())
-        .unwrap_or(TEXT_HTML);
+        .and_then(|accept| accept.negotiate(available).ok())
+        .unwrap_or(TEXT_HTML)
+}
+
+pub(super) async fn render_view(req: Request, next: Next) -> Response {
+    let accept_header = req.headers().get(ACCEPT).cloned();
+    let mut response = next.run(req).await;
+    let Some(view) = response.extensions_mut().remove::<View<HttpResponse>>() else {
+        return response;
+    };
+    let available = [APPLICATION_JSON, TEXT_HTML];
+    let preference = negotiate(accept_header.as_ref(), &available);
     let body = match preference {
         mime if mime == APPLICATION_JSON => Json(view.data).into_response(),
         mime if mime == TEXT_HTML => Template::new(view.template_name, view.data).into_response(),
@@ -79,8 +89,9 @@ where
 {
     fn into_response(self) -> Response {
         let status = self.data.kind.status_code();
+        let headers = self.data.kind.headers();
         let response = HttpResponse::error(self.data);
         let view = View::new(self.template_name, response);
-        (status, view).into_response()
+        (status, headers, view).into_response()
     }
 }
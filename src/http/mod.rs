@@ -1,11 +1,19 @@
+use admin::admin_routes;
+use classes::classes_routes;
 use grades::grades_routes;
+use invites::invites_routes;
 use keys::keys_routes;
-pub use middleware::template::{LocaleNegotiator, TemplateRenderer};
+pub use middleware::{
+    csrf::CsrfToken,
+    template::{LocaleNegotiator, TemplateRenderer},
+};
 
 use auth::auth_routes;
 use performance::performance_routes;
 use secrecy::{zeroize::Zeroize, ExposeSecret, SecretBox};
 use static_files::static_router;
+use subjects::subjects_routes;
+use terms::terms_routes;
 use tower_http::{catch_panic::CatchPanicLayer, trace::TraceLayer};
 
 use std::net::SocketAddr;
@@ -22,10 +30,16 @@ use crate::app::AppContext;
 mod error;
 mod middleware;
 
+mod admin;
+mod assessments;
 mod auth;
+mod classes;
 mod grades;
+mod invites;
 mod keys;
 mod performance;
+mod subjects;
+mod terms;
 
 mod static_files;
 
@@ -41,24 +55,34 @@ pub async fn serve_http(config: HttpConfig, ctx: AppContext) -> anyhow::Result<(
     let listener = TcpListener::bind(addr).await?;
     let router = root_router()
         .fallback(not_found_view)
+        .with_sessions(ctx.clone())
+        .with_csrf(ctx.clone())
         .layer(CatchPanicLayer::custom(catch_panic))
         .with_renderers(ctx.clone())
         .layer(TraceLayer::new_for_http())
         .with_state(ctx)
         .merge(static_router());
-    axum::serve(listener, router.into_make_service())
-        .await
-        .context("start http server")
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .context("start http server")
 }
 
 fn root_router() -> Router<AppContext> {
     Router::new()
         .route("/", get(homepage))
         .route("/empty", get(async || Html("")))
+        .nest("/admin", admin_routes())
         .nest("/auth", auth_routes())
+        .nest("/classes", classes_routes())
         .nest("/grades", grades_routes())
+        .nest("/invites", invites_routes())
         .nest("/keys", keys_routes())
         .nest("/performance", performance_routes())
+        .nest("/subjects", subjects_routes())
+        .nest("/terms", terms_routes())
 }
 
 async fn homepage() -> Template<()> {
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     routing::get,
     Router,
@@ -28,6 +28,11 @@ struct EvaluationPath {
     key: String,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+struct EvaluationQuery {
+    term: Option<String>,
+}
+
 async fn verifying_key(State(ctx): State<AppContext>) -> Result<Json<VerifyingKey>, Json<Error>> {
     ctx.get_verifying_key().map(Json).map_err(Json)
 }
@@ -35,14 +40,19 @@ async fn verifying_key(State(ctx): State<AppContext>) -> Result<Json<VerifyingKe
 async fn student_evaluation(
     State(ctx): State<AppContext>,
     Path(path): Path<EvaluationPath>,
+    Query(query): Query<EvaluationQuery>,
 ) -> Result<Json<SignedEvaluation>, Json<Error<KeyLookupError>>> {
-    get_signature(&ctx, path.key).await.map(Json).map_err(Json)
+    get_signature(&ctx, path.key, query.term)
+        .await
+        .map(Json)
+        .map_err(Json)
 }
 
 impl HttpError for KeyLookupError {
     fn status_code(&self) -> StatusCode {
         match self {
             KeyLookupError::UnknownKey => StatusCode::NOT_FOUND,
+            KeyLookupError::UnknownTerm => StatusCode::NOT_FOUND,
         }
     }
 }
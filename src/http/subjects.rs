@@ -0,0 +1,242 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post, put},
+    Form, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        grades::{
+            create_subject, delete_subject, list_subjects, rename_subject, set_grading_scale,
+            set_subject_archived,
+        },
+        validation::{try_convert, ValidationErrors},
+        AppContext, AppError,
+    },
+    domain::grades::{ManageSubjectError, ManagedSubject, NewSubject, Subject, SubjectScale},
+    error::Error,
+};
+
+use super::{
+    assessments::assessments_routes,
+    error::HttpError,
+    middleware::{auth::Teacher, template::TemplateName, view::View},
+};
+
+const SUBJECTS_PAGE: &str = "subjects.html";
+
+const SUBJECTS_TABLE: &str = "components/subjects-table.html";
+
+const INLINE_ERROR: &str = "components/inline-error.html";
+
+pub fn subjects_routes() -> Router<AppContext> {
+    let subject_routes = Router::new()
+        .route(
+            "/",
+            put(handle_rename_subject).delete(handle_delete_subject),
+        )
+        .route("/grading-scale", put(handle_set_grading_scale))
+        .route("/archive", post(handle_archive_subject))
+        .route("/restore", post(handle_restore_subject))
+        .nest("/assessments", assessments_routes());
+    Router::new()
+        .route("/", get(subjects_page).post(handle_create_subject))
+        .nest("/{subject_id}", subject_routes)
+}
+
+type HttpNewSubjectFormError = Error<AppError<ManageSubjectError>, NewSubjectForm>;
+
+type HttpSubjectFormError = Error<AppError<ManageSubjectError>, SubjectForm>;
+
+type HttpSubjectScaleFormError = Error<AppError<ManageSubjectError>, SubjectScaleForm>;
+
+type HttpManageSubjectError = Error<ManageSubjectError>;
+
+#[derive(Clone, Debug, Deserialize)]
+struct SubjectPath {
+    subject_id: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct TitleForm {
+    title: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct GradingScaleForm {
+    grading_scale: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct NewSubjectForm {
+    id: String,
+    title: String,
+    grading_scale: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct SubjectForm {
+    id: String,
+    title: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct SubjectScaleForm {
+    id: String,
+    grading_scale: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct SubjectsData {
+    subjects: Vec<ManagedSubject>,
+}
+
+async fn subjects_page(
+    _: Teacher,
+    State(ctx): State<AppContext>,
+) -> Result<View<SubjectsData>, View<Error>> {
+    subjects_view(&ctx, SUBJECTS_PAGE)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))
+}
+
+async fn handle_create_subject(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Form(form): Form<NewSubjectForm>,
+) -> Result<View<SubjectsData>, View<HttpNewSubjectFormError>> {
+    let form_copy = form.clone();
+    create_subject(&ctx, user, form)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error.with_input(form_copy.clone())))?;
+    subjects_view(&ctx, SUBJECTS_TABLE).await.map_err(|error| {
+        View::new(
+            INLINE_ERROR,
+            Error::from_internal(error).with_input(form_copy),
+        )
+    })
+}
+
+async fn handle_rename_subject(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+    Form(form): Form<TitleForm>,
+) -> Result<View<SubjectsData>, View<HttpSubjectFormError>> {
+    let req = SubjectForm {
+        id: path.subject_id,
+        title: form.title,
+    };
+    rename_subject(&ctx, user, req.clone())
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error.with_input(req.clone())))?;
+    subjects_view(&ctx, SUBJECTS_TABLE)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, Error::from_internal(error).with_input(req)))
+}
+
+async fn handle_set_grading_scale(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+    Form(form): Form<GradingScaleForm>,
+) -> Result<View<SubjectsData>, View<HttpSubjectScaleFormError>> {
+    let req = SubjectScaleForm {
+        id: path.subject_id,
+        grading_scale: form.grading_scale,
+    };
+    set_grading_scale(&ctx, user, req.clone())
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error.with_input(req.clone())))?;
+    subjects_view(&ctx, SUBJECTS_TABLE)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, Error::from_internal(error).with_input(req)))
+}
+
+async fn handle_archive_subject(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+) -> Result<View<SubjectsData>, View<HttpManageSubjectError>> {
+    set_subject_archived(&ctx, user, path.subject_id, true)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    subjects_view(&ctx, SUBJECTS_TABLE)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, Error::from_internal(error)))
+}
+
+async fn handle_restore_subject(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+) -> Result<View<SubjectsData>, View<HttpManageSubjectError>> {
+    set_subject_archived(&ctx, user, path.subject_id, false)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    subjects_view(&ctx, SUBJECTS_TABLE)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, Error::from_internal(error)))
+}
+
+async fn handle_delete_subject(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<SubjectPath>,
+) -> Result<View<SubjectsData>, View<HttpManageSubjectError>> {
+    delete_subject(&ctx, user, path.subject_id)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    subjects_view(&ctx, SUBJECTS_TABLE)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, Error::from_internal(error)))
+}
+
+async fn subjects_view(
+    ctx: &AppContext,
+    template_name: &'static str,
+) -> crate::Result<View<SubjectsData>> {
+    list_subjects(ctx)
+        .await
+        .map(|subjects| View::new(template_name, SubjectsData { subjects }))
+}
+
+impl HttpError for ManageSubjectError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::AlreadyExists => StatusCode::CONFLICT,
+            Self::HasGrades => StatusCode::CONFLICT,
+        }
+    }
+}
+
+impl TryFrom<NewSubjectForm> for NewSubject {
+    type Error = ValidationErrors;
+
+    fn try_from(value: NewSubjectForm) -> Result<Self, Self::Error> {
+        try_convert!(NewSubjectForm value => NewSubject { id, title, grading_scale })
+    }
+}
+
+impl TryFrom<SubjectForm> for Subject {
+    type Error = ValidationErrors;
+
+    fn try_from(value: SubjectForm) -> Result<Self, Self::Error> {
+        try_convert!(SubjectForm value => Subject { id, title })
+    }
+}
+
+impl TryFrom<SubjectScaleForm> for SubjectScale {
+    type Error = ValidationErrors;
+
+    fn try_from(value: SubjectScaleForm) -> Result<Self, Self::Error> {
+        try_convert!(SubjectScaleForm value => SubjectScale { id, grading_scale })
+    }
+}
@@ -0,0 +1,125 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Form, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::{
+        terms::{activate_term, create_term, list_terms},
+        validation::{try_convert, ValidationErrors},
+        AppContext, AppError,
+    },
+    domain::terms::{NewTerm, Term, TermError},
+    error::Error,
+};
+
+use super::{
+    error::HttpError,
+    middleware::{auth::Teacher, template::TemplateName, view::View},
+};
+
+const TERMS_PAGE: &str = "terms.html";
+
+const TERMS_TABLE: &str = "components/terms-table.html";
+
+const INLINE_ERROR: &str = "components/inline-error.html";
+
+pub fn terms_routes() -> Router<AppContext> {
+    Router::new()
+        .route("/", get(terms_page).post(handle_create_term))
+        .route("/{term_id}/activate", post(handle_activate_term))
+}
+
+type HttpCreateTermError = Error<AppError<TermError>, TermForm>;
+
+#[derive(Clone, Debug, Deserialize)]
+struct TermPath {
+    term_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TermForm {
+    name: String,
+    starts_on: String,
+    ends_on: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct TermsData {
+    terms: Vec<Term>,
+}
+
+async fn terms_page(
+    _: Teacher,
+    State(ctx): State<AppContext>,
+) -> Result<View<TermsData>, View<Error>> {
+    terms_view(&ctx, TERMS_PAGE)
+        .await
+        .map_err(|error| View::new(TemplateName::error(), Error::from_internal(error)))
+}
+
+async fn handle_create_term(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Form(form): Form<TermForm>,
+) -> Result<View<TermsData>, View<HttpCreateTermError>> {
+    let form_copy = form.clone();
+    create_term(&ctx, user, form)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error.with_input(form_copy.clone())))?;
+    terms_view(&ctx, TERMS_TABLE).await.map_err(|error| {
+        View::new(
+            INLINE_ERROR,
+            Error::from_internal(error).with_input(form_copy),
+        )
+    })
+}
+
+async fn handle_activate_term(
+    Teacher(user): Teacher,
+    State(ctx): State<AppContext>,
+    Path(path): Path<TermPath>,
+) -> Result<View<TermsData>, View<Error<TermError>>> {
+    activate_term(&ctx, user, path.term_id)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, error))?;
+    terms_view(&ctx, TERMS_TABLE)
+        .await
+        .map_err(|error| View::new(INLINE_ERROR, Error::from_internal(error)))
+}
+
+async fn terms_view(
+    ctx: &AppContext,
+    template_name: &'static str,
+) -> crate::Result<View<TermsData>> {
+    list_terms(ctx)
+        .await
+        .map(|terms| View::new(template_name, TermsData { terms }))
+}
+
+impl HttpError for TermError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::AlreadyExists => StatusCode::CONFLICT,
+            Self::InvalidDates => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+}
+
+impl TryFrom<TermForm> for NewTerm {
+    type Error = ValidationErrors;
+
+    fn try_from(value: TermForm) -> Result<Self, Self::Error> {
+        try_convert!(TermForm value => NewTerm {
+            name,
+            starts_on,
+            ends_on
+        })
+    }
+}
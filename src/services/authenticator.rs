@@ -0,0 +1,159 @@
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use anyhow::{anyhow, Context};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use jsonwebtoken::get_current_timestamp;
+use qrcode::{render::svg, QrCode};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Deserialize;
+use totp_rs::{Algorithm, TOTP};
+
+use crate::domain::{
+    email::Email,
+    role::Role,
+    two_factor::{
+        EncryptedTotpSecret, IssuedTotpSecret, TotpSecret, TotpStep, TwoFactorCode,
+        TwoFactorProvisioning,
+    },
+};
+
+const SECRET_BYTES: usize = 20;
+
+const NONCE_BYTES: usize = 12;
+
+const DIGITS: usize = 6;
+
+const STEP_SECONDS: u64 = 30;
+
+const ALLOWED_SKEW: u64 = 1;
+
+const QR_CODE_SIZE: u32 = 200;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TwoFactorConfig {
+    issuer: String,
+    encryption_key: SecretString,
+    mandatory_for_teachers: bool,
+}
+
+#[derive(Clone)]
+pub struct Authenticator {
+    issuer: String,
+    cipher: Aes256Gcm,
+    mandatory_for_teachers: bool,
+}
+
+impl Authenticator {
+    pub fn new(config: TwoFactorConfig) -> anyhow::Result<Self> {
+        let TwoFactorConfig {
+            issuer,
+            encryption_key,
+            mandatory_for_teachers,
+        } = config;
+        let cipher = Aes256Gcm::new_from_slice(encryption_key.expose_secret().as_bytes())
+            .context("validate totp encryption key")?;
+        if issuer.contains(':') {
+            return Err(anyhow!("totp issuer may not contain `:`"));
+        }
+        Ok(Self {
+            issuer,
+            cipher,
+            mandatory_for_teachers,
+        })
+    }
+
+    fn totp(&self, secret: &TotpSecret, skew: u8, email: &str) -> TOTP {
+        TOTP::new_unchecked(
+            Algorithm::SHA1,
+            DIGITS,
+            skew,
+            STEP_SECONDS,
+            secret.expose_secret().to_vec(),
+            Some(self.issuer.clone()),
+            email.to_owned(),
+        )
+    }
+}
+
+pub fn is_two_factor_required(authenticator: &Authenticator, role: Role) -> bool {
+    match role {
+        Role::Teacher | Role::Admin => authenticator.mandatory_for_teachers,
+        Role::Student => false,
+    }
+}
+
+#[tracing::instrument(skip(authenticator), err(Debug, level = "debug"))]
+pub fn issue_totp_secret(authenticator: &Authenticator) -> crate::Result<IssuedTotpSecret> {
+    let mut secret = vec![0u8; SECRET_BYTES];
+    OsRng
+        .try_fill_bytes(&mut secret)
+        .context("generate totp secret")?;
+    let mut nonce = [0u8; NONCE_BYTES];
+    OsRng
+        .try_fill_bytes(&mut nonce)
+        .context("generate totp secret nonce")?;
+    let ciphertext = authenticator
+        .cipher
+        .encrypt(Nonce::from_slice(&nonce), secret.as_slice())
+        .map_err(|_| anyhow!("encrypt totp secret"))?;
+    let encrypted = EncryptedTotpSecret::new(hex::encode([nonce.as_slice(), &ciphertext].concat()));
+    Ok(IssuedTotpSecret {
+        secret: TotpSecret::new(secret),
+        encrypted,
+    })
+}
+
+#[tracing::instrument(skip(authenticator), err(Debug, level = "debug"))]
+pub fn decrypt_totp_secret(
+    authenticator: &Authenticator,
+    secret: &EncryptedTotpSecret,
+) -> crate::Result<TotpSecret> {
+    let bytes = hex::decode(secret.as_ref()).context("decode stored totp secret")?;
+    if bytes.len() <= NONCE_BYTES {
+        return Err(crate::Error::internal(anyhow!(
+            "stored totp secret is too short"
+        )));
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_BYTES);
+    let secret = authenticator
+        .cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("decrypt totp secret"))?;
+    Ok(TotpSecret::new(secret))
+}
+
+#[tracing::instrument(skip(authenticator, secret), err(Debug, level = "debug"))]
+pub fn provision_totp(
+    authenticator: &Authenticator,
+    secret: &TotpSecret,
+    email: &Email,
+) -> crate::Result<TwoFactorProvisioning> {
+    let email = Into::<String>::into(email.clone());
+    let totp = authenticator.totp(secret, ALLOWED_SKEW as u8, &email);
+    let uri = totp.get_url();
+    let qr_code = QrCode::new(uri.as_bytes())
+        .context("encode totp provisioning uri")?
+        .render::<svg::Color>()
+        .min_dimensions(QR_CODE_SIZE, QR_CODE_SIZE)
+        .build();
+    Ok(TwoFactorProvisioning {
+        secret: totp.get_secret_base32(),
+        uri,
+        qr_code,
+    })
+}
+
+pub fn check_totp_code(
+    authenticator: &Authenticator,
+    secret: &TotpSecret,
+    code: &TwoFactorCode,
+) -> Option<TotpStep> {
+    let code = code.normalized();
+    let totp = authenticator.totp(secret, 0, "");
+    let current_step = get_current_timestamp() / STEP_SECONDS;
+    (current_step.saturating_sub(ALLOWED_SKEW)..=current_step + ALLOWED_SKEW)
+        .find(|step| totp.check(&code, step * STEP_SECONDS))
+        .map(|step| TotpStep::new(step as i64))
+}
@@ -0,0 +1,96 @@
+use crate::domain::{
+    admin::{DbManagedUser, UpdateUserError},
+    role::Role,
+    user_id::DbUserId,
+};
+
+use super::{sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_managed_users(
+    db: &Database,
+    search: Option<String>,
+) -> crate::Result<Vec<DbManagedUser>> {
+    sqlx::query_as(
+        "
+        select id, email, name, role,
+          verified_at is not null as is_verified,
+          disabled_at is not null as is_disabled
+        from users
+        where $1::text is null
+           or strpos(lower(email), lower($1)) > 0
+           or strpos(lower(name), lower($1)) > 0
+        order by role, name, id
+        ",
+    )
+    .bind(search)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn update_user_role(
+    db: &Database,
+    user_id: DbUserId,
+    role: Role,
+) -> crate::Result<(), UpdateUserError> {
+    sqlx::query(
+        "
+        update users
+        set role = $2
+        where id = $1
+        ",
+    )
+    .bind(user_id)
+    .bind(role)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(UpdateUserError::NotFound)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn update_user_disabled(
+    db: &Database,
+    user_id: DbUserId,
+    is_disabled: bool,
+) -> crate::Result<(), UpdateUserError> {
+    sqlx::query(
+        "
+        update users
+        set disabled_at = case when $2 then coalesce(disabled_at, now()) end
+        where id = $1
+        ",
+    )
+    .bind(user_id)
+    .bind(is_disabled)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(UpdateUserError::NotFound)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn remove_user(db: &Database, user_id: DbUserId) -> crate::Result<(), UpdateUserError> {
+    sqlx::query(
+        "
+        delete from users
+        where id = $1
+        ",
+    )
+    .bind(user_id)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(UpdateUserError::NotFound)),
+        _ => Ok(()),
+    })
+}
@@ -0,0 +1,325 @@
+use sqlx::PgConnection;
+
+use crate::domain::{
+    assessment_id::AssessmentId,
+    grade::Grade,
+    grade_status::GradeStatus,
+    grades::{
+        Assessment, AssessmentError, DbAssessmentScore, DbGradeChange, DbGradebookStudent,
+        NewAssessment, StudentAssessment, Subject,
+    },
+    grading_scale::GradingScale,
+    subject_id::SubjectId,
+    term_id::TermId,
+    user_id::DbUserId,
+};
+
+use super::{
+    grades::{lock_grade, write_grade},
+    sql_error, Database,
+};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_subject(
+    db: &Database,
+    subject_id: &SubjectId,
+) -> crate::Result<Subject, AssessmentError> {
+    sqlx::query_as(
+        "
+        select id, title
+        from subjects
+        where id = $1
+        ",
+    )
+    .bind(subject_id)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(sql_error)?
+    .ok_or(crate::Error::expected(AssessmentError::NotFound))
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_assessments(
+    db: &Database,
+    subject_id: &SubjectId,
+) -> crate::Result<Vec<Assessment>> {
+    sqlx::query_as(
+        "
+        select id, subject_id, title, kind, weight
+        from assessments
+        where subject_id = $1
+          and term_id = (select id from terms where is_active)
+        order by id
+        ",
+    )
+    .bind(subject_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_gradebook_students(
+    db: &Database,
+    subject_id: &SubjectId,
+) -> crate::Result<Vec<DbGradebookStudent>> {
+    sqlx::query_as(
+        "
+        with roster as (
+          select class_students.user_id
+          from class_students
+          join class_subjects on class_students.class_id = class_subjects.class_id
+          where class_subjects.subject_id = $1
+          union
+          select user_id
+          from grades
+          where subject_id = $1
+            and term_id = (select id from terms where is_active)
+        )
+        select
+            users.id,
+            users.name,
+            grades.value as final_grade
+        from roster
+        join users on roster.user_id = users.id
+        left join grades
+          on grades.user_id = roster.user_id
+         and grades.subject_id = $1
+         and grades.term_id = (select id from terms where is_active)
+        order by users.name
+        ",
+    )
+    .bind(subject_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_assessment_scores(
+    db: &Database,
+    subject_id: &SubjectId,
+) -> crate::Result<Vec<DbAssessmentScore>> {
+    sqlx::query_as(
+        "
+        select
+            assessments.subject_id,
+            assessment_scores.assessment_id,
+            assessment_scores.user_id as student_id,
+            assessment_scores.value as score
+        from assessment_scores
+        join assessments on assessment_scores.assessment_id = assessments.id
+        where assessments.subject_id = $1
+          and assessments.term_id = (select id from terms where is_active)
+        ",
+    )
+    .bind(subject_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_student_assessments(
+    db: &Database,
+    student_id: DbUserId,
+    term_id: Option<TermId>,
+) -> crate::Result<Vec<StudentAssessment>> {
+    sqlx::query_as(
+        "
+        select
+            assessments.subject_id,
+            assessments.title,
+            assessments.kind,
+            assessments.weight,
+            assessment_scores.value as score
+        from assessments
+        join grades
+          on grades.subject_id = assessments.subject_id
+         and grades.term_id = assessments.term_id
+         and grades.user_id = $1
+        left join assessment_scores
+          on assessment_scores.assessment_id = assessments.id
+         and assessment_scores.user_id = $1
+        where assessments.term_id = coalesce($2, (select id from terms where is_active))
+        order by assessments.id
+        ",
+    )
+    .bind(student_id)
+    .bind(term_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn insert_assessment(
+    db: &Database,
+    assessment: NewAssessment,
+) -> crate::Result<(), AssessmentError> {
+    match sqlx::query(
+        "
+        insert into assessments
+          (subject_id, term_id, title, kind, weight)
+        select $1, id, $2, $3, $4
+        from terms
+        where is_active
+        ",
+    )
+    .bind(assessment.subject_id)
+    .bind(assessment.title)
+    .bind(assessment.kind)
+    .bind(assessment.weight)
+    .execute(&db.pool)
+    .await
+    {
+        Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+            Err(crate::Error::expected(AssessmentError::NotFound))
+        }
+        result => result.map(|_| ()).map_err(sql_error),
+    }
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn remove_assessment(
+    db: &Database,
+    actor: DbUserId,
+    subject_id: SubjectId,
+    assessment_id: AssessmentId,
+) -> crate::Result<(), AssessmentError> {
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    let students = sqlx::query_as::<_, (DbUserId,)>(
+        "
+        select user_id
+        from assessment_scores
+        where assessment_id = $1
+        ",
+    )
+    .bind(assessment_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    let result = sqlx::query(
+        "
+        delete from assessments
+        where id = $1
+          and subject_id = $2
+          and term_id = (select id from terms where is_active)
+        ",
+    )
+    .bind(assessment_id)
+    .bind(&subject_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    if result.rows_affected() == 0 {
+        return Err(crate::Error::expected(AssessmentError::NotFound));
+    }
+    for (student_id,) in students {
+        update_final_grade(&mut tx, actor, subject_id.clone(), student_id)
+            .await
+            .map_err(crate::Error::from_internal)?;
+    }
+    tx.commit().await.map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_db_assessment_score(
+    db: &Database,
+    actor: DbUserId,
+    score: DbAssessmentScore,
+) -> crate::Result<(), AssessmentError> {
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    let current = lock_grade(&mut tx, score.student_id, &score.subject_id)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    if let Some((status, _, _)) = current.filter(|(status, _, _)| is_locked(*status)) {
+        return Err(crate::Error::expected(AssessmentError::GradeLocked(status)));
+    }
+    let result = sqlx::query(
+        "
+        insert into assessment_scores
+          (assessment_id, user_id, value)
+        select id, $2, $3
+        from assessments
+        where id = $1
+          and subject_id = $4
+          and term_id = (select id from terms where is_active)
+        on conflict (assessment_id, user_id) do update
+        set value = excluded.value
+        ",
+    )
+    .bind(score.assessment_id)
+    .bind(score.student_id)
+    .bind(score.score)
+    .bind(&score.subject_id)
+    .execute(&mut *tx)
+    .await;
+    match result {
+        Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+            return Err(crate::Error::expected(AssessmentError::NotFound));
+        }
+        Ok(result) if result.rows_affected() == 0 => {
+            return Err(crate::Error::expected(AssessmentError::NotFound));
+        }
+        result => result.map_err(sql_error)?,
+    };
+    update_final_grade(&mut tx, actor, score.subject_id, score.student_id)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    tx.commit().await.map_err(sql_error)
+}
+
+async fn update_final_grade(
+    conn: &mut PgConnection,
+    actor: DbUserId,
+    subject_id: SubjectId,
+    student_id: DbUserId,
+) -> crate::Result<()> {
+    let (final_grade, grading_scale) = sqlx::query_as::<_, (Option<Grade>, GradingScale)>(
+        "
+        select
+            round(
+              sum(assessments.weight * assessment_scores.value) / sum(assessments.weight),
+              2
+            ),
+            (select grading_scale from subjects where id = $2)
+        from assessment_scores
+        join assessments on assessment_scores.assessment_id = assessments.id
+        where assessment_scores.user_id = $1
+          and assessments.subject_id = $2
+          and assessments.term_id = (select id from terms where is_active)
+        ",
+    )
+    .bind(student_id)
+    .bind(&subject_id)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(sql_error)?;
+    let current = lock_grade(conn, student_id, &subject_id).await?;
+    let status = current.map(|(status, _, _)| status);
+    if status.is_some_and(is_locked) {
+        return Ok(());
+    }
+    let (grade, status) = match (final_grade, status) {
+        (Some(grade), _) => (Some(grading_scale.scale(grade)), GradeStatus::Graded),
+        (None, Some(GradeStatus::Graded)) => (None, GradeStatus::Ungraded),
+        (None, _) => return Ok(()),
+    };
+    let change = DbGradeChange {
+        subject: subject_id,
+        student: student_id,
+        grade,
+        status,
+        version: None,
+        reason: None,
+    };
+    write_grade(conn, actor, change).await
+}
+
+fn is_locked(status: GradeStatus) -> bool {
+    matches!(
+        status,
+        GradeStatus::Excused | GradeStatus::Absent | GradeStatus::Retracted
+    )
+}
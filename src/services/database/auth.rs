@@ -1,6 +1,12 @@
 use crate::domain::{
-    auth::{DbUser, FindUserError, GetUserError, NewUser, SaveNewUserError},
+    auth::{
+        DbRefreshToken, DbUser, FindUserError, GetUserError, MarkEmailVerifiedError,
+        NewRefreshToken, NewResetToken, NewUser, SaveNewUserError, UseRefreshTokenError,
+        UseResetTokenError,
+    },
     email::MaybeEmail,
+    password::PasswordHash,
+    token::{RefreshTokenHash, ResetTokenHash, SessionId, TokenId},
     user_id::DbUserId,
 };
 
@@ -37,7 +43,9 @@ pub async fn save_new_user(
 pub async fn get_user(db: &Database, db_id: &DbUserId) -> crate::Result<DbUser, GetUserError> {
     sqlx::query_as(
         "
-        select id, email, name, password_hash, role
+        select id, email, name, password_hash, role,
+          verified_at is not null as is_verified,
+          disabled_at is not null as is_disabled
         from users
         where id = $1
         ",
@@ -53,7 +61,9 @@ pub async fn get_user(db: &Database, db_id: &DbUserId) -> crate::Result<DbUser,
 pub async fn find_user(db: &Database, email: &MaybeEmail) -> crate::Result<DbUser, FindUserError> {
     sqlx::query_as(
         "
-        select id, email, name, password_hash, role
+        select id, email, name, password_hash, role,
+          verified_at is not null as is_verified,
+          disabled_at is not null as is_disabled
         from users
         where email = $1
         ",
@@ -64,3 +74,222 @@ pub async fn find_user(db: &Database, email: &MaybeEmail) -> crate::Result<DbUse
     .map_err(sql_error)?
     .ok_or(crate::Error::expected(FindUserError::NotFound))
 }
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn mark_email_verified(
+    db: &Database,
+    email: &MaybeEmail,
+) -> crate::Result<(), MarkEmailVerifiedError> {
+    sqlx::query(
+        "
+        update users
+        set verified_at = coalesce(verified_at, now())
+        where email = $1
+        ",
+    )
+    .bind(email)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(MarkEmailVerifiedError::NotFound)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_refresh_token(db: &Database, token: NewRefreshToken) -> crate::Result<()> {
+    sqlx::query(
+        "
+        insert into refresh_tokens
+          (hash, user_id, session_id, expires_at)
+        values
+          ($1, $2, $3, now() + make_interval(secs => $4))
+        on conflict (hash) do nothing
+        ",
+    )
+    .bind(token.hash)
+    .bind(token.user_id)
+    .bind(token.session_id)
+    .bind(token.ttl.as_secs_f64())
+    .execute(&db.pool)
+    .await
+    .map(|_| ())
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn use_refresh_token(
+    db: &Database,
+    hash: RefreshTokenHash,
+) -> crate::Result<DbRefreshToken, UseRefreshTokenError> {
+    let token = sqlx::query_as(
+        "
+        update refresh_tokens
+        set used_at = coalesce(used_at, now())
+        where hash = $1
+          and (used_at is null or used_at > now() - interval '10 seconds')
+          and revoked_at is null
+          and expires_at > now()
+        returning user_id, session_id
+        ",
+    )
+    .bind(&hash)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(sql_error)?;
+    if let Some(token) = token {
+        return Ok(token);
+    }
+    let reused = sqlx::query_as::<_, (SessionId,)>(
+        "
+        select session_id
+        from refresh_tokens
+        where hash = $1
+          and used_at is not null
+        ",
+    )
+    .bind(&hash)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(sql_error)?;
+    let error = match reused {
+        Some((session_id,)) => UseRefreshTokenError::Reused(session_id),
+        None => UseRefreshTokenError::NotFound,
+    };
+    Err(crate::Error::expected(error))
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn revoke_session(db: &Database, session_id: SessionId) -> crate::Result<()> {
+    sqlx::query(
+        "
+        update refresh_tokens
+        set revoked_at = now()
+        where session_id = $1
+          and revoked_at is null
+        ",
+    )
+    .bind(session_id)
+    .execute(&db.pool)
+    .await
+    .map(|_| ())
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn revoke_token(db: &Database, token_id: TokenId, expires_at: u64) -> crate::Result<()> {
+    sqlx::query(
+        "
+        insert into revoked_tokens
+          (id, expires_at)
+        values
+          ($1, to_timestamp($2))
+        on conflict (id) do nothing
+        ",
+    )
+    .bind(token_id)
+    .bind(expires_at as f64)
+    .execute(&db.pool)
+    .await
+    .map(|_| ())
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn is_token_revoked(db: &Database, token_id: TokenId) -> crate::Result<bool> {
+    sqlx::query_as::<_, (bool,)>(
+        "
+        select exists (
+          select 1
+          from revoked_tokens
+          where id = $1
+        )
+        ",
+    )
+    .bind(token_id)
+    .fetch_one(&db.pool)
+    .await
+    .map(|(revoked,)| revoked)
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn revoke_user_sessions(db: &Database, user_id: DbUserId) -> crate::Result<()> {
+    sqlx::query(
+        "
+        update refresh_tokens
+        set revoked_at = now()
+        where user_id = $1
+          and revoked_at is null
+        ",
+    )
+    .bind(user_id)
+    .execute(&db.pool)
+    .await
+    .map(|_| ())
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_reset_token(db: &Database, token: NewResetToken) -> crate::Result<()> {
+    sqlx::query(
+        "
+        insert into password_reset_tokens
+          (hash, user_id, expires_at)
+        values
+          ($1, $2, now() + make_interval(secs => $3))
+        ",
+    )
+    .bind(token.hash)
+    .bind(token.user_id)
+    .bind(token.ttl.as_secs_f64())
+    .execute(&db.pool)
+    .await
+    .map(|_| ())
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn use_reset_token(
+    db: &Database,
+    hash: ResetTokenHash,
+) -> crate::Result<DbUserId, UseResetTokenError> {
+    sqlx::query_as::<_, (DbUserId,)>(
+        "
+        update password_reset_tokens
+        set used_at = now()
+        where hash = $1
+          and used_at is null
+          and expires_at > now()
+        returning user_id
+        ",
+    )
+    .bind(hash)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(sql_error)?
+    .map(|(user_id,)| user_id)
+    .ok_or(crate::Error::expected(UseResetTokenError::NotFound))
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn update_password(
+    db: &Database,
+    user_id: DbUserId,
+    password_hash: PasswordHash,
+) -> crate::Result<()> {
+    sqlx::query(
+        "
+        update users
+        set password_hash = $2
+        where id = $1
+        ",
+    )
+    .bind(user_id)
+    .bind(password_hash)
+    .execute(&db.pool)
+    .await
+    .map(|_| ())
+    .map_err(sql_error)
+}
@@ -0,0 +1,28 @@
+use crate::domain::{dashboard::TermAverage, user_id::DbUserId};
+
+use super::{sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_term_averages(
+    db: &Database,
+    student_id: DbUserId,
+) -> crate::Result<Vec<TermAverage>> {
+    sqlx::query_as(
+        "
+        select
+            terms.id as term_id,
+            terms.name as term_name,
+            round(avg(grades.value), 2) as average
+        from grades
+        join terms on grades.term_id = terms.id
+        where grades.user_id = $1
+          and grades.value is not null
+        group by terms.id
+        order by terms.starts_on
+        ",
+    )
+    .bind(student_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
@@ -0,0 +1,350 @@
+use crate::domain::{
+    class_id::ClassId,
+    enrollment::{
+        Class, ClassSubject, ClassSummary, DbClassStudent, DbEnrollment, DbRosterGrade,
+        EnrollmentError,
+    },
+    subject_id::SubjectId,
+    term_id::TermId,
+    user_id::DbUserId,
+};
+
+use super::{sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_classes(db: &Database) -> crate::Result<Vec<ClassSummary>> {
+    sqlx::query_as(
+        "
+        select
+            classes.id,
+            classes.name,
+            (
+              select count(*)
+              from class_students
+              where class_students.class_id = classes.id
+            ) as student_count,
+            (
+              select count(*)
+              from class_subjects
+              where class_subjects.class_id = classes.id
+            ) as subject_count
+        from classes
+        order by classes.name
+        ",
+    )
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn insert_class(db: &Database, class: Class) -> crate::Result<(), EnrollmentError> {
+    match sqlx::query(
+        "
+        insert into classes
+          (id, name)
+        values
+          ($1, $2)
+        ",
+    )
+    .bind(class.id)
+    .bind(class.name)
+    .execute(&db.pool)
+    .await
+    {
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            Err(crate::Error::expected(EnrollmentError::AlreadyExists))
+        }
+        result => result.map(|_| ()).map_err(sql_error),
+    }
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn remove_class(db: &Database, class_id: ClassId) -> crate::Result<(), EnrollmentError> {
+    sqlx::query(
+        "
+        delete from classes
+        where id = $1
+        ",
+    )
+    .bind(class_id)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(EnrollmentError::NotFound)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_class(
+    db: &Database,
+    class_id: &ClassId,
+) -> crate::Result<Class, EnrollmentError> {
+    sqlx::query_as(
+        "
+        select id, name
+        from classes
+        where id = $1
+        ",
+    )
+    .bind(class_id)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(sql_error)?
+    .ok_or(crate::Error::expected(EnrollmentError::NotFound))
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_class_students(
+    db: &Database,
+    class_id: &ClassId,
+) -> crate::Result<Vec<DbClassStudent>> {
+    sqlx::query_as(
+        "
+        select
+            users.id,
+            users.name,
+            users.email,
+            class_students.user_id is not null as is_enrolled
+        from users
+        left join class_students
+          on class_students.user_id = users.id
+         and class_students.class_id = $1
+        where users.role = 'student'
+          and users.disabled_at is null
+        order by is_enrolled desc, users.name
+        ",
+    )
+    .bind(class_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_class_subjects(
+    db: &Database,
+    class_id: &ClassId,
+) -> crate::Result<Vec<ClassSubject>> {
+    sqlx::query_as(
+        "
+        select
+            subjects.id,
+            subjects.title,
+            class_subjects.subject_id is not null as is_assigned
+        from subjects
+        left join class_subjects
+          on class_subjects.subject_id = subjects.id
+         and class_subjects.class_id = $1
+        where subjects.archived_at is null
+           or class_subjects.subject_id is not null
+        order by is_assigned desc, subjects.title
+        ",
+    )
+    .bind(class_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_class_student(
+    db: &Database,
+    class_id: ClassId,
+    student_id: DbUserId,
+) -> crate::Result<(), EnrollmentError> {
+    match sqlx::query(
+        "
+        insert into class_students
+          (class_id, user_id)
+        select $1, id
+        from users
+        where id = $2
+          and role = 'student'
+        on conflict (class_id, user_id) do nothing
+        ",
+    )
+    .bind(class_id)
+    .bind(student_id)
+    .execute(&db.pool)
+    .await
+    {
+        Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+            Err(crate::Error::expected(EnrollmentError::NotFound))
+        }
+        result => result.map(|_| ()).map_err(sql_error),
+    }
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn remove_class_student(
+    db: &Database,
+    class_id: ClassId,
+    student_id: DbUserId,
+) -> crate::Result<(), EnrollmentError> {
+    sqlx::query(
+        "
+        delete from class_students
+        where class_id = $1
+          and user_id = $2
+        ",
+    )
+    .bind(class_id)
+    .bind(student_id)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(EnrollmentError::NotFound)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_class_subject(
+    db: &Database,
+    class_id: ClassId,
+    subject_id: SubjectId,
+) -> crate::Result<(), EnrollmentError> {
+    match sqlx::query(
+        "
+        insert into class_subjects
+          (class_id, subject_id)
+        values
+          ($1, $2)
+        on conflict (class_id, subject_id) do nothing
+        ",
+    )
+    .bind(class_id)
+    .bind(subject_id)
+    .execute(&db.pool)
+    .await
+    {
+        Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+            Err(crate::Error::expected(EnrollmentError::NotFound))
+        }
+        result => result.map(|_| ()).map_err(sql_error),
+    }
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn remove_class_subject(
+    db: &Database,
+    class_id: ClassId,
+    subject_id: SubjectId,
+) -> crate::Result<(), EnrollmentError> {
+    sqlx::query(
+        "
+        delete from class_subjects
+        where class_id = $1
+          and subject_id = $2
+        ",
+    )
+    .bind(class_id)
+    .bind(subject_id)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(EnrollmentError::NotFound)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_enrollments(
+    db: &Database,
+    students: Vec<DbUserId>,
+) -> crate::Result<Vec<DbEnrollment>> {
+    sqlx::query_as(
+        "
+        select distinct
+            class_students.user_id as student_id,
+            class_subjects.subject_id
+        from class_students
+        join class_subjects on class_students.class_id = class_subjects.class_id
+        join users on class_students.user_id = users.id
+        where class_students.user_id = any($1)
+          and users.role = 'student'
+        ",
+    )
+    .bind(students)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_classmates(
+    db: &Database,
+    student: DbUserId,
+) -> crate::Result<Vec<DbEnrollment>> {
+    sqlx::query_as(
+        "
+        select distinct
+            classmates.user_id as student_id,
+            class_subjects.subject_id
+        from class_students
+        join class_students classmates on class_students.class_id = classmates.class_id
+        join class_subjects on class_students.class_id = class_subjects.class_id
+        where class_students.user_id = $1
+        ",
+    )
+    .bind(student)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_roster_grades(
+    db: &Database,
+    subject_id: Option<SubjectId>,
+    term_id: Option<TermId>,
+) -> crate::Result<Vec<DbRosterGrade>> {
+    sqlx::query_as(
+        "
+        with term as (
+          select id
+          from terms
+          where id = coalesce($2, (select id from terms where is_active))
+        ),
+        roster as (
+          select class_students.user_id, class_subjects.subject_id
+          from class_students
+          join class_subjects on class_students.class_id = class_subjects.class_id
+          union
+          select user_id, subject_id
+          from grades
+          where term_id = (select id from term)
+        )
+        select
+            users.id as student_id,
+            users.name as student_name,
+            grades.value as grade,
+            grades.scaled_value,
+            grades.gpa,
+            coalesce(grades.status, 'ungraded') as status,
+            grades.version,
+            subjects.id as subject_id,
+            subjects.title as subject_title
+        from roster
+        join users on roster.user_id = users.id
+        join subjects on roster.subject_id = subjects.id
+        left join grades
+          on grades.user_id = roster.user_id
+         and grades.subject_id = roster.subject_id
+         and grades.term_id = (select id from term)
+        where $1::text is null
+           or subjects.id = $1
+        order by subjects.title, users.name
+        ",
+    )
+    .bind(subject_id)
+    .bind(term_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
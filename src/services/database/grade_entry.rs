@@ -0,0 +1,67 @@
+use rust_decimal::Decimal;
+
+use crate::domain::{
+    grade::Grade, grade_entry::SavedGrade, grade_status::GradeStatus, grade_version::GradeVersion,
+    grades::DbGradeChange, user_id::DbUserId,
+};
+
+use super::{grades::write_grade, sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_db_grades(
+    db: &Database,
+    actor: DbUserId,
+    changes: Vec<DbGradeChange>,
+) -> crate::Result<Vec<SavedGrade>> {
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    let mut saved = Vec::with_capacity(changes.len());
+    for change in changes {
+        let current =
+            sqlx::query_as::<_, (Option<Grade>, Option<String>, GradeStatus, GradeVersion)>(
+                "
+            select value, scaled_value, status, version
+            from grades
+            where user_id = $1
+              and subject_id = $2
+              and term_id = (select id from terms where is_active)
+            for update
+            ",
+            )
+            .bind(change.student)
+            .bind(&change.subject)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(sql_error)?;
+        let value = change.grade.as_ref().map(|grade| grade.grade);
+        let is_unchanged = match &current {
+            Some((current_value, _, status, _)) => {
+                *status == change.status
+                    && current_value.map(Decimal::from) == value.map(Decimal::from)
+            }
+            None => change.status == GradeStatus::Ungraded,
+        };
+        if is_unchanged {
+            saved.push(SavedGrade::Unchanged);
+            continue;
+        }
+        match (current, change.version) {
+            (None, None) => {}
+            (Some((_, _, _, current_version)), Some(version)) if current_version == version => {}
+            (Some((_, current, status, _)), _) => {
+                saved.push(SavedGrade::Conflict { status, current });
+                continue;
+            }
+            (None, Some(_)) => {
+                saved.push(SavedGrade::Conflict {
+                    status: GradeStatus::Ungraded,
+                    current: None,
+                });
+                continue;
+            }
+        }
+        write_grade(&mut tx, actor, change).await?;
+        saved.push(SavedGrade::Saved);
+    }
+    tx.commit().await.map_err(sql_error)?;
+    Ok(saved)
+}
@@ -0,0 +1,24 @@
+use crate::domain::{grade_import::ImportStudent, user_id::DbUserId};
+
+use super::{sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_import_students(
+    db: &Database,
+    emails: Vec<String>,
+    ids: Vec<DbUserId>,
+) -> crate::Result<Vec<ImportStudent>> {
+    sqlx::query_as(
+        "
+        select id, email
+        from users
+        where role = 'student'
+          and (lower(email) = any($1) or id = any($2))
+        ",
+    )
+    .bind(emails)
+    .bind(ids)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
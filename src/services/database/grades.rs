@@ -1,7 +1,17 @@
+use sqlx::PgConnection;
+
 use crate::domain::{
     grade::Grade,
-    grades::{DbGradeRecord, GetGradeError, StudentGrade, Subject},
+    grade_status::GradeStatus,
+    grade_version::GradeVersion,
+    grades::{
+        DbGradeChange, DbGradePageRow, DbGradeQuery, DbGradeRecord, DbGradeRemoval, GetGradeError,
+        GradeEvent, ManageSubjectError, ManagedSubject, NewSubject, RemoveGradeError, StudentGrade,
+        Subject, SubjectScale, UpdateGradeError,
+    },
+    grading_scale::GradingScale,
     subject_id::SubjectId,
+    term_id::TermId,
     user_id::DbUserId,
 };
 
@@ -13,6 +23,29 @@ pub async fn get_subjects(db: &Database) -> crate::Result<Vec<Subject>> {
         "
         select id, title
         from subjects
+        where archived_at is null
+        order by title
+        ",
+    )
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_managed_subjects(db: &Database) -> crate::Result<Vec<ManagedSubject>> {
+    sqlx::query_as(
+        "
+        select
+            subjects.id,
+            subjects.title,
+            subjects.grading_scale,
+            subjects.archived_at is not null as is_archived,
+            count(grades.user_id) as grade_count
+        from subjects
+        left join grades on subjects.id = grades.subject_id
+        group by subjects.id
+        order by is_archived, subjects.title
         ",
     )
     .fetch_all(&db.pool)
@@ -20,6 +53,212 @@ pub async fn get_subjects(db: &Database) -> crate::Result<Vec<Subject>> {
     .map_err(sql_error)
 }
 
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn insert_subject(
+    db: &Database,
+    subject: NewSubject,
+) -> crate::Result<(), ManageSubjectError> {
+    match sqlx::query(
+        "
+        insert into subjects
+          (id, title, grading_scale)
+        values
+          ($1, $2, $3)
+        ",
+    )
+    .bind(subject.id)
+    .bind(subject.title)
+    .bind(subject.grading_scale)
+    .execute(&db.pool)
+    .await
+    {
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            Err(crate::Error::expected(ManageSubjectError::AlreadyExists))
+        }
+        result => result.map(|_| ()).map_err(sql_error),
+    }
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn update_subject_title(
+    db: &Database,
+    subject: Subject,
+) -> crate::Result<(), ManageSubjectError> {
+    sqlx::query(
+        "
+        update subjects
+        set title = $2
+        where id = $1
+        ",
+    )
+    .bind(subject.id)
+    .bind(subject.title)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(ManageSubjectError::NotFound)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn update_subject_archived(
+    db: &Database,
+    subject_id: SubjectId,
+    is_archived: bool,
+) -> crate::Result<(), ManageSubjectError> {
+    sqlx::query(
+        "
+        update subjects
+        set archived_at = case when $2 then coalesce(archived_at, now()) end
+        where id = $1
+        ",
+    )
+    .bind(subject_id)
+    .bind(is_archived)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(ManageSubjectError::NotFound)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn update_subject_grading_scale(
+    db: &Database,
+    subject: SubjectScale,
+) -> crate::Result<(), ManageSubjectError> {
+    let SubjectScale {
+        id: subject_id,
+        grading_scale,
+    } = subject;
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    let result = sqlx::query(
+        "
+        update subjects
+        set grading_scale = $2
+        where id = $1
+        ",
+    )
+    .bind(&subject_id)
+    .bind(grading_scale)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    if result.rows_affected() == 0 {
+        return Err(crate::Error::expected(ManageSubjectError::NotFound));
+    }
+    let grades = sqlx::query_as::<_, (DbUserId, TermId, Grade)>(
+        "
+        select user_id, term_id, value
+        from grades
+        where subject_id = $1
+          and value is not null
+        for update
+        ",
+    )
+    .bind(&subject_id)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    let mut students = Vec::with_capacity(grades.len());
+    let mut terms = Vec::with_capacity(grades.len());
+    let mut scaled_values = Vec::with_capacity(grades.len());
+    let mut gpas = Vec::with_capacity(grades.len());
+    for (student, term, grade) in grades {
+        let scaled = grading_scale.scale(grade);
+        students.push(student);
+        terms.push(term);
+        scaled_values.push(scaled.scaled_value);
+        gpas.push(scaled.gpa);
+    }
+    sqlx::query(
+        "
+        update grades
+        set scaled_value = incoming.scaled_value,
+            gpa = incoming.gpa,
+            version = grades.version + 1
+        from unnest($2::bigint[], $3::bigint[], $4::text[], $5::numeric[])
+          as incoming (user_id, term_id, scaled_value, gpa)
+        where grades.subject_id = $1
+          and grades.user_id = incoming.user_id
+          and grades.term_id = incoming.term_id
+        ",
+    )
+    .bind(subject_id)
+    .bind(students)
+    .bind(terms)
+    .bind(scaled_values)
+    .bind(gpas)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    tx.commit().await.map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_subject_grading_scale(
+    db: &Database,
+    subject_id: &SubjectId,
+) -> crate::Result<Option<GradingScale>> {
+    sqlx::query_as::<_, (GradingScale,)>(
+        "
+        select grading_scale
+        from subjects
+        where id = $1 and archived_at is null
+        ",
+    )
+    .bind(subject_id)
+    .fetch_optional(&db.pool)
+    .await
+    .map(|row| row.map(|(grading_scale,)| grading_scale))
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_grading_scales(db: &Database) -> crate::Result<Vec<SubjectScale>> {
+    sqlx::query_as(
+        "
+        select id, grading_scale
+        from subjects
+        where archived_at is null
+        ",
+    )
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn remove_subject(
+    db: &Database,
+    subject_id: SubjectId,
+) -> crate::Result<(), ManageSubjectError> {
+    match sqlx::query(
+        "
+        delete from subjects
+        where id = $1
+        ",
+    )
+    .bind(subject_id)
+    .execute(&db.pool)
+    .await
+    {
+        Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+            Err(crate::Error::expected(ManageSubjectError::HasGrades))
+        }
+        result => result
+            .map_err(sql_error)
+            .and_then(|result| match result.rows_affected() {
+                0 => Err(crate::Error::expected(ManageSubjectError::NotFound)),
+                _ => Ok(()),
+            }),
+    }
+}
+
 #[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
 pub async fn get_db_grade(
     db: &Database,
@@ -32,6 +271,10 @@ pub async fn get_db_grade(
             users.id as student_id,
             users.name as student_name,
             grades.value as grade,
+            grades.scaled_value,
+            grades.gpa,
+            grades.status,
+            grades.version,
             subjects.id as subject_id,
             subjects.title as subject_title
         from users
@@ -39,6 +282,7 @@ pub async fn get_db_grade(
         join subjects on grades.subject_id = subjects.id
         where subjects.id = $1
           and users.id = $2
+          and grades.term_id = (select id from terms where is_active)
         limit 1
         ",
     )
@@ -54,32 +298,129 @@ pub async fn get_db_grade(
 pub async fn get_db_grades(
     db: &Database,
     subject_id: Option<SubjectId>,
+    term_id: Option<TermId>,
 ) -> crate::Result<Vec<DbGradeRecord>> {
-    let base_query = "
+    sqlx::query_as(
+        "
         select
             users.id as student_id,
             users.name as student_name,
             grades.value as grade,
+            grades.scaled_value,
+            grades.gpa,
+            grades.status,
+            grades.version,
             subjects.id as subject_id,
             subjects.title as subject_title
         from users
         join grades on users.id = grades.user_id
         join subjects on grades.subject_id = subjects.id
-        ";
-    match subject_id {
-        Some(subject_id) => {
-            sqlx::query_as(&format!("{base_query} where subjects.id = $1"))
-                .bind(&subject_id)
-                .fetch_all(&db.pool)
-                .await
-        }
-        None => {
-            sqlx::query_as(base_query)
-                .bind(&subject_id)
-                .fetch_all(&db.pool)
-                .await
-        }
-    }
+        where grades.term_id = coalesce($2, (select id from terms where is_active))
+          and ($1::text is null or subjects.id = $1)
+        ",
+    )
+    .bind(subject_id)
+    .bind(term_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_grade_page(
+    db: &Database,
+    query: DbGradeQuery,
+    limit: Option<i64>,
+) -> crate::Result<Vec<DbGradePageRow>> {
+    let after = query.after;
+    sqlx::query_as(
+        "
+        with term as (
+          select id
+          from terms
+          where id = coalesce($2, (select id from terms where is_active))
+        ),
+        roster as (
+          select class_students.user_id, class_subjects.subject_id
+          from class_students
+          join class_subjects on class_students.class_id = class_subjects.class_id
+          union
+          select user_id, subject_id
+          from grades
+          where term_id = (select id from term)
+        ),
+        rows as (
+          select
+              users.id as student_id,
+              users.name as student_name,
+              grades.value as grade,
+              grades.scaled_value,
+              grades.gpa,
+              coalesce(grades.status, 'ungraded') as status,
+              grades.version,
+              subjects.id as subject_id,
+              subjects.title as subject_title,
+              case when $4 = 'grade' then coalesce(grades.value, -1) else 0 end as grade_key,
+              lower(case when $4 = 'subject' then subjects.title else users.name end)
+                as primary_key,
+              lower(case when $4 = 'subject' then users.name else subjects.title end)
+                as secondary_key
+          from roster
+          join users on roster.user_id = users.id
+          join subjects on roster.subject_id = subjects.id
+          left join grades
+            on grades.user_id = roster.user_id
+           and grades.subject_id = roster.subject_id
+           and grades.term_id = (select id from term)
+          where ($1::text is null or subjects.id = $1)
+            and ($3::text is null or strpos(lower(users.name), lower($3)) > 0)
+        )
+        select
+            student_id,
+            student_name,
+            grade,
+            scaled_value,
+            gpa,
+            status,
+            version,
+            subject_id,
+            subject_title,
+            grade_key,
+            primary_key,
+            secondary_key
+        from rows
+        where $6::bigint is null
+           or ($5 and (grade_key, primary_key, secondary_key, student_id, subject_id)
+                    < ($8::numeric, $9::text, $10::text, $6, $7))
+           or (not $5 and (grade_key, primary_key, secondary_key, student_id, subject_id)
+                        > ($8::numeric, $9::text, $10::text, $6, $7))
+        order by
+            case when $5 then grade_key end desc,
+            case when $5 then primary_key end desc,
+            case when $5 then secondary_key end desc,
+            case when $5 then student_id end desc,
+            case when $5 then subject_id end desc,
+            grade_key,
+            primary_key,
+            secondary_key,
+            student_id,
+            subject_id
+        limit $11
+        ",
+    )
+    .bind(query.subject)
+    .bind(query.term)
+    .bind(query.search)
+    .bind(query.sort.as_str())
+    .bind(query.order.is_desc())
+    .bind(after.as_ref().map(|cursor| cursor.student))
+    .bind(after.as_ref().map(|cursor| cursor.subject.clone()))
+    .bind(after.as_ref().map(|cursor| cursor.grade_key))
+    .bind(after.as_ref().map(|cursor| cursor.primary_key.clone()))
+    .bind(after.as_ref().map(|cursor| cursor.secondary_key.clone()))
+    .bind(limit)
+    .fetch_all(&db.pool)
+    .await
     .map_err(sql_error)
 }
 
@@ -87,19 +428,25 @@ pub async fn get_db_grades(
 pub async fn get_db_student_grades(
     db: &Database,
     student_id: DbUserId,
+    term_id: Option<TermId>,
 ) -> crate::Result<Vec<StudentGrade>> {
     sqlx::query_as(
         "
         select
             grades.value as grade,
+            grades.scaled_value,
+            grades.gpa,
+            grades.status,
             subjects.id as subject_id,
             subjects.title as subject_title
         from grades
         join subjects on grades.subject_id = subjects.id
         where grades.user_id = $1
+          and grades.term_id = coalesce($2, (select id from terms where is_active))
         ",
     )
     .bind(student_id)
+    .bind(term_id)
     .fetch_all(&db.pool)
     .await
     .map_err(sql_error)
@@ -108,25 +455,227 @@ pub async fn get_db_student_grades(
 #[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
 pub async fn update_db_grade(
     db: &Database,
-    subject: SubjectId,
+    actor: DbUserId,
+    change: DbGradeChange,
+) -> crate::Result<(), UpdateGradeError> {
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    check_grade_version(&mut tx, &change).await?;
+    if change.status == GradeStatus::Graded
+        && has_assessments(&mut tx, &change.subject)
+            .await
+            .map_err(crate::Error::from_internal)?
+    {
+        return Err(crate::Error::expected(UpdateGradeError::Computed));
+    }
+    write_grade(&mut tx, actor, change)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    tx.commit().await.map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn remove_db_grade(
+    db: &Database,
+    actor: DbUserId,
+    removal: DbGradeRemoval,
+) -> crate::Result<(), RemoveGradeError> {
+    let DbGradeRemoval {
+        subject,
+        student,
+        version,
+        reason,
+    } = removal;
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    let current = lock_grade(&mut tx, student, &subject)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    match current {
+        None | Some((GradeStatus::Ungraded | GradeStatus::Retracted, _, _)) => {
+            return Err(crate::Error::expected(RemoveGradeError::NotFound));
+        }
+        Some((status, current, current_version))
+            if version.is_some_and(|version| version != current_version) =>
+        {
+            return Err(crate::Error::expected(RemoveGradeError::Conflict {
+                status,
+                current,
+            }));
+        }
+        Some(_) => {}
+    }
+    let change = DbGradeChange {
+        subject,
+        student,
+        grade: None,
+        status: GradeStatus::Retracted,
+        version: None,
+        reason: Some(reason),
+    };
+    write_grade(&mut tx, actor, change)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    tx.commit().await.map_err(sql_error)
+}
+
+pub(super) async fn lock_grade(
+    conn: &mut PgConnection,
     student: DbUserId,
-    grade: Grade,
+    subject: &SubjectId,
+) -> crate::Result<Option<(GradeStatus, Option<String>, GradeVersion)>> {
+    sqlx::query_as(
+        "
+        select status, scaled_value, version
+        from grades
+        where user_id = $1
+          and subject_id = $2
+          and term_id = (select id from terms where is_active)
+        for update
+        ",
+    )
+    .bind(student)
+    .bind(subject)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(sql_error)
+}
+
+pub(super) async fn has_assessments(
+    conn: &mut PgConnection,
+    subject: &SubjectId,
+) -> crate::Result<bool> {
+    sqlx::query_as::<_, (bool,)>(
+        "
+        select exists (
+          select 1
+          from assessments
+          where subject_id = $1
+            and term_id = (select id from terms where is_active)
+        )
+        ",
+    )
+    .bind(subject)
+    .fetch_one(&mut *conn)
+    .await
+    .map(|(exists,)| exists)
+    .map_err(sql_error)
+}
+
+async fn check_grade_version(
+    conn: &mut PgConnection,
+    change: &DbGradeChange,
+) -> crate::Result<(), UpdateGradeError> {
+    let current = lock_grade(conn, change.student, &change.subject)
+        .await
+        .map_err(crate::Error::from_internal)?;
+    match (current, change.version) {
+        (None, None) => Ok(()),
+        (Some((_, _, current_version)), Some(version)) if current_version == version => Ok(()),
+        (Some((status, current, _)), _) => {
+            Err(crate::Error::expected(UpdateGradeError::Conflict {
+                status,
+                current,
+            }))
+        }
+        (None, Some(_)) => Err(crate::Error::expected(UpdateGradeError::Conflict {
+            status: GradeStatus::Ungraded,
+            current: None,
+        })),
+    }
+}
+
+pub(super) async fn write_grade(
+    conn: &mut PgConnection,
+    actor: DbUserId,
+    change: DbGradeChange,
 ) -> crate::Result<()> {
+    let old_value = sqlx::query_as::<_, (Option<Grade>,)>(
+        "
+        select value
+        from grades
+        where user_id = $1
+          and subject_id = $2
+          and term_id = (select id from terms where is_active)
+        for update
+        ",
+    )
+    .bind(change.student)
+    .bind(&change.subject)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(sql_error)?
+    .and_then(|(value,)| value);
     sqlx::query(
         "
         insert into grades
-          (user_id, subject_id, value)
-        values
-          ($1, $2, $3)
-        on conflict (user_id, subject_id) do update
-        set value = $3
+          (user_id, subject_id, term_id, value, scaled_value, gpa, status)
+        select $1, $2, id, $3, $4, $5, $6
+        from terms
+        where is_active
+        on conflict (user_id, subject_id, term_id) do update
+        set value = $3,
+            scaled_value = $4,
+            gpa = $5,
+            status = $6,
+            version = grades.version + 1
         ",
     )
-    .bind(student)
-    .bind(subject)
-    .bind(grade)
-    .execute(&db.pool)
+    .bind(change.student)
+    .bind(&change.subject)
+    .bind(change.grade.as_ref().map(|grade| grade.grade))
+    .bind(change.grade.as_ref().map(|grade| &grade.scaled_value))
+    .bind(change.grade.as_ref().map(|grade| grade.gpa))
+    .bind(change.status)
+    .execute(&mut *conn)
+    .await
+    .map_err(sql_error)?;
+    sqlx::query(
+        "
+        insert into grade_events
+          (student_id, subject_id, term_id, actor_id, old_value, new_value, status, reason)
+        select $1, $2, id, $3, $4, $5, $6, $7
+        from terms
+        where is_active
+        ",
+    )
+    .bind(change.student)
+    .bind(change.subject)
+    .bind(actor)
+    .bind(old_value)
+    .bind(change.grade.map(|grade| grade.grade))
+    .bind(change.status)
+    .bind(change.reason)
+    .execute(&mut *conn)
+    .await
+    .map_err(sql_error)?;
+    Ok(())
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_db_grade_events(
+    db: &Database,
+    subject_id: SubjectId,
+    student_id: DbUserId,
+) -> crate::Result<Vec<GradeEvent>> {
+    sqlx::query_as(
+        "
+        select
+            users.name as actor_name,
+            grade_events.old_value,
+            grade_events.new_value,
+            grade_events.status,
+            grade_events.reason,
+            extract(epoch from grade_events.created_at)::bigint as created_at
+        from grade_events
+        left join users on grade_events.actor_id = users.id
+        where grade_events.subject_id = $1
+          and grade_events.student_id = $2
+          and grade_events.term_id = (select id from terms where is_active)
+        order by grade_events.created_at desc, grade_events.id desc
+        ",
+    )
+    .bind(subject_id)
+    .bind(student_id)
+    .fetch_all(&db.pool)
     .await
-    .map(|_| ())
     .map_err(sql_error)
 }
@@ -0,0 +1,88 @@
+use crate::domain::{
+    auth::NewUser,
+    invites::{InvitedSignupError, NewInvite},
+    token::InviteTokenHash,
+};
+
+use super::{sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_invite(db: &Database, invite: NewInvite) -> crate::Result<()> {
+    sqlx::query(
+        "
+        insert into invites
+          (hash, email, invited_by, expires_at)
+        values
+          ($1, $2, $3, now() + make_interval(secs => $4))
+        ",
+    )
+    .bind(invite.hash)
+    .bind(invite.email)
+    .bind(invite.invited_by)
+    .bind(invite.ttl.as_secs_f64())
+    .execute(&db.pool)
+    .await
+    .map(|_| ())
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_invited_user(
+    db: &Database,
+    new_user: NewUser,
+    invite: InviteTokenHash,
+) -> crate::Result<(), InvitedSignupError> {
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    let email_matches = sqlx::query_as::<_, (bool,)>(
+        "
+        select lower(email) = lower($2)
+        from invites
+        where hash = $1
+          and used_at is null
+          and expires_at > now()
+        for update
+        ",
+    )
+    .bind(&invite)
+    .bind(&new_user.email)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(sql_error)?
+    .map(|(email_matches,)| email_matches)
+    .ok_or(crate::Error::expected(InvitedSignupError::InvalidInvite))?;
+    if !email_matches {
+        return Err(crate::Error::expected(InvitedSignupError::EmailMismatch));
+    }
+    match sqlx::query(
+        "
+        insert into users
+          (email, name, password_hash, role, verified_at)
+        values
+          ($1, $2, $3, $4, now())
+        ",
+    )
+    .bind(new_user.email)
+    .bind(new_user.name)
+    .bind(new_user.password_hash)
+    .bind(new_user.role)
+    .execute(&mut *tx)
+    .await
+    {
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            return Err(crate::Error::expected(InvitedSignupError::EmailTaken));
+        }
+        result => result.map_err(sql_error)?,
+    };
+    sqlx::query(
+        "
+        update invites
+        set used_at = now()
+        where hash = $1
+        ",
+    )
+    .bind(invite)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    tx.commit().await.map_err(sql_error)
+}
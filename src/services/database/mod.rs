@@ -2,10 +2,20 @@ use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
 
+pub mod admin;
+pub mod assessments;
 pub mod auth;
+pub mod dashboard;
+pub mod enrollment;
+pub mod grade_entry;
+pub mod grade_import;
 pub mod grades;
+pub mod invites;
 pub mod keys;
 pub mod performance;
+pub mod terms;
+pub mod throttle;
+pub mod two_factor;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct DatabaseConfig {
@@ -0,0 +1,100 @@
+use crate::domain::{
+    term_id::TermId,
+    terms::{NewTerm, Term, TermError},
+};
+
+use super::{sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_terms(db: &Database) -> crate::Result<Vec<Term>> {
+    sqlx::query_as(
+        "
+        select
+            id,
+            name,
+            to_char(starts_on, 'YYYY-MM-DD') as starts_on,
+            to_char(ends_on, 'YYYY-MM-DD') as ends_on,
+            is_active
+        from terms
+        order by starts_on desc, name
+        ",
+    )
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_term(db: &Database, term_id: Option<TermId>) -> crate::Result<Option<Term>> {
+    sqlx::query_as(
+        "
+        select
+            id,
+            name,
+            to_char(starts_on, 'YYYY-MM-DD') as starts_on,
+            to_char(ends_on, 'YYYY-MM-DD') as ends_on,
+            is_active
+        from terms
+        where id = coalesce($1, (select id from terms where is_active))
+        ",
+    )
+    .bind(term_id)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn insert_term(db: &Database, term: NewTerm) -> crate::Result<(), TermError> {
+    match sqlx::query(
+        "
+        insert into terms
+          (name, starts_on, ends_on)
+        values
+          ($1, $2::date, $3::date)
+        ",
+    )
+    .bind(term.name)
+    .bind(term.starts_on)
+    .bind(term.ends_on)
+    .execute(&db.pool)
+    .await
+    {
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            Err(crate::Error::expected(TermError::AlreadyExists))
+        }
+        result => result.map(|_| ()).map_err(sql_error),
+    }
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn update_active_term(db: &Database, term_id: TermId) -> crate::Result<(), TermError> {
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    sqlx::query(
+        "
+        update terms
+        set is_active = false
+        where is_active
+          and id <> $1
+        ",
+    )
+    .bind(term_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    let result = sqlx::query(
+        "
+        update terms
+        set is_active = true
+        where id = $1
+        ",
+    )
+    .bind(term_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    match result.rows_affected() {
+        0 => Err(crate::Error::expected(TermError::NotFound)),
+        _ => tx.commit().await.map_err(sql_error),
+    }
+}
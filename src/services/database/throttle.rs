@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use crate::domain::throttle::{LoginAttempt, LoginFailures, LoginSource};
+
+use super::{sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_login_failures(
+    db: &Database,
+    source: &LoginSource,
+    window: Duration,
+) -> crate::Result<LoginFailures> {
+    sqlx::query_as(
+        "
+        with last_success as (
+            select coalesce(max(attempted_at), '-infinity') as success_at
+            from login_attempts
+            where email = $1
+              and outcome = 'succeeded'
+        )
+        select
+            count(*) filter (
+              where email = $1 and login_attempts.attempted_at > last_success.success_at
+            ) as email_failures,
+            extract(epoch from max(login_attempts.attempted_at) filter (
+              where email = $1 and login_attempts.attempted_at > last_success.success_at
+            ))::bigint as email_last_failure_at,
+            count(*) filter (where ip = $2) as ip_failures,
+            extract(epoch from max(login_attempts.attempted_at) filter (where ip = $2))::bigint
+              as ip_last_failure_at
+        from login_attempts, last_success
+        where outcome = 'failed'
+          and login_attempts.attempted_at > now() - make_interval(secs => $3)
+          and (email = $1 or ip = $2)
+        ",
+    )
+    .bind(&source.email)
+    .bind(source.ip.to_string())
+    .bind(window.as_secs_f64())
+    .fetch_one(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn record_login_attempt(db: &Database, attempt: LoginAttempt) -> crate::Result<()> {
+    let LoginAttempt { source, outcome } = attempt;
+    sqlx::query(
+        "
+        insert into login_attempts
+          (email, ip, outcome)
+        values
+          ($1, $2, $3)
+        ",
+    )
+    .bind(source.email)
+    .bind(source.ip.to_string())
+    .bind(outcome)
+    .execute(&db.pool)
+    .await
+    .map(|_| ())
+    .map_err(sql_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv6Addr};
+
+    use uuid::Uuid;
+
+    use crate::{config::Config, domain::throttle::LoginAttemptOutcome};
+
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "requires a migrated database, run with ENVIRONMENT set"]
+    async fn login_failures_reset_on_success_for_email_only() {
+        let config = Config::init().expect("load config");
+        let db = Database::new(config.app.database);
+        let id = Uuid::new_v4();
+        let source = LoginSource {
+            email: format!("{id}@throttle.test").into(),
+            ip: IpAddr::V6(Ipv6Addr::from(id.as_u128())),
+        };
+        let outcomes = [
+            LoginAttemptOutcome::Failed,
+            LoginAttemptOutcome::Succeeded,
+            LoginAttemptOutcome::Failed,
+            LoginAttemptOutcome::Failed,
+        ];
+        for outcome in outcomes {
+            let source = source.clone();
+            record_login_attempt(&db, LoginAttempt { source, outcome })
+                .await
+                .expect("record login attempt");
+        }
+        let failures = get_login_failures(&db, &source, Duration::from_secs(900))
+            .await
+            .expect("get login failures");
+        assert_eq!(failures.email_failures, 2);
+        assert_eq!(failures.ip_failures, 3);
+        assert!(failures.email_last_failure_at.is_some());
+        assert!(failures.ip_last_failure_at.is_some());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::domain::{
+    token::TokenId,
+    two_factor::{
+        DbRecoveryCode, DbTotpCredential, EncryptedTotpSecret, RecoveryCodeHash, RecoveryCodeId,
+        TotpStep, TwoFactorError,
+    },
+    user_id::DbUserId,
+};
+
+use super::{sql_error, Database};
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn save_totp_secret(
+    db: &Database,
+    user_id: DbUserId,
+    secret: EncryptedTotpSecret,
+) -> crate::Result<(), TwoFactorError> {
+    sqlx::query(
+        "
+        insert into totp_credentials
+          (user_id, secret)
+        values
+          ($1, $2)
+        on conflict (user_id) do update
+        set secret = excluded.secret,
+            last_used_step = null
+        where totp_credentials.enabled_at is null
+        ",
+    )
+    .bind(user_id)
+    .bind(secret)
+    .execute(&db.pool)
+    .await
+    .map_err(sql_error)
+    .and_then(|result| match result.rows_affected() {
+        0 => Err(crate::Error::expected(TwoFactorError::AlreadyEnabled)),
+        _ => Ok(()),
+    })
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_totp_credential(
+    db: &Database,
+    user_id: DbUserId,
+) -> crate::Result<Option<DbTotpCredential>> {
+    sqlx::query_as(
+        "
+        select secret, enabled_at is not null as is_enabled
+        from totp_credentials
+        where user_id = $1
+        ",
+    )
+    .bind(user_id)
+    .fetch_optional(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn activate_totp(
+    db: &Database,
+    user_id: DbUserId,
+    step: TotpStep,
+    recovery_codes: Vec<RecoveryCodeHash>,
+) -> crate::Result<()> {
+    let mut tx = db.pool.begin().await.map_err(sql_error)?;
+    sqlx::query(
+        "
+        update totp_credentials
+        set enabled_at = now(),
+            last_used_step = $2
+        where user_id = $1
+        ",
+    )
+    .bind(user_id)
+    .bind(step)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    sqlx::query(
+        "
+        delete from recovery_codes
+        where user_id = $1
+        ",
+    )
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    sqlx::query(
+        "
+        insert into recovery_codes
+          (user_id, hash)
+        select $1, unnest($2::text[])
+        ",
+    )
+    .bind(user_id)
+    .bind(recovery_codes)
+    .execute(&mut *tx)
+    .await
+    .map_err(sql_error)?;
+    tx.commit().await.map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn use_totp_step(
+    db: &Database,
+    user_id: DbUserId,
+    step: TotpStep,
+) -> crate::Result<bool> {
+    sqlx::query(
+        "
+        update totp_credentials
+        set last_used_step = $2
+        where user_id = $1
+          and enabled_at is not null
+          and (last_used_step is null or last_used_step < $2)
+        ",
+    )
+    .bind(user_id)
+    .bind(step)
+    .execute(&db.pool)
+    .await
+    .map(|result| result.rows_affected() > 0)
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn record_challenge_attempt(
+    db: &Database,
+    challenge_id: TokenId,
+    expires_at: u64,
+) -> crate::Result<i32> {
+    sqlx::query_as::<_, (i32,)>(
+        "
+        with expired as (
+          delete from two_factor_challenges
+          where expires_at < now()
+        )
+        insert into two_factor_challenges
+          (id, expires_at)
+        values
+          ($1, to_timestamp($2))
+        on conflict (id) do update
+        set attempts = two_factor_challenges.attempts + 1
+        returning attempts
+        ",
+    )
+    .bind(challenge_id)
+    .bind(expires_at as f64)
+    .fetch_one(&db.pool)
+    .await
+    .map(|(attempts,)| attempts)
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn get_recovery_codes(
+    db: &Database,
+    user_id: DbUserId,
+) -> crate::Result<Vec<DbRecoveryCode>> {
+    sqlx::query_as(
+        "
+        select id, hash
+        from recovery_codes
+        where user_id = $1
+          and used_at is null
+        ",
+    )
+    .bind(user_id)
+    .fetch_all(&db.pool)
+    .await
+    .map_err(sql_error)
+}
+
+#[tracing::instrument(skip(db), ret(level = "debug") err(Debug, level = "debug"))]
+pub async fn use_recovery_code(db: &Database, id: RecoveryCodeId) -> crate::Result<bool> {
+    sqlx::query(
+        "
+        update recovery_codes
+        set used_at = now()
+        where id = $1
+          and used_at is null
+        ",
+    )
+    .bind(id)
+    .execute(&db.pool)
+    .await
+    .map(|result| result.rows_affected() > 0)
+    .map_err(sql_error)
+}
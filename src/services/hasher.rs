@@ -1,6 +1,9 @@
 use anyhow::Context;
 use argon2::{
-    password_hash::{rand_core::OsRng, PasswordHasher, SaltString},
+    password_hash::{
+        rand_core::{OsRng, RngCore},
+        PasswordHasher, SaltString,
+    },
     Algorithm, Argon2, Params, PasswordVerifier, Version,
 };
 use secrecy::{ExposeSecret, SecretString};
@@ -9,8 +12,15 @@ use serde::Deserialize;
 use crate::domain::{
     auth::VerifyPasswordError,
     password::{MaybePassword, Password, PasswordHash},
+    two_factor::{IssuedRecoveryCode, RecoveryCode, RecoveryCodeHash, TwoFactorCode},
 };
 
+const RECOVERY_CODE_COUNT: usize = 10;
+
+const RECOVERY_CODE_LENGTH: usize = 10;
+
+const RECOVERY_CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct HasherConfig {
     secret: SecretString,
@@ -91,3 +101,46 @@ pub fn verify_password(
         .verify_password(password.expose_secret().as_bytes(), &password_hash)
         .map_err(|_| crate::Error::expected(VerifyPasswordError::InvalidPassword))
 }
+
+#[tracing::instrument(skip(hasher), err(Debug, level = "debug"))]
+pub fn issue_recovery_codes(hasher: &Hasher) -> crate::Result<Vec<IssuedRecoveryCode>> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let code = (0..RECOVERY_CODE_LENGTH)
+                .map(|_| {
+                    let index = OsRng.next_u32() as usize % RECOVERY_CODE_ALPHABET.len();
+                    RECOVERY_CODE_ALPHABET[index] as char
+                })
+                .collect::<String>();
+            let hash = hasher
+                .expect_argon()
+                .hash_password(code.as_bytes(), &SaltString::generate(&mut OsRng))
+                .map(|hash| RecoveryCodeHash::new(hash.to_string()))
+                .context("hash recovery code")?;
+            let (head, tail) = code.split_at(RECOVERY_CODE_LENGTH / 2);
+            let code = RecoveryCode::new(SecretString::from(format!("{head}-{tail}")));
+            Ok(IssuedRecoveryCode { code, hash })
+        })
+        .collect()
+}
+
+pub fn is_recovery_code(code: &TwoFactorCode) -> bool {
+    let code = code.normalized();
+    code.len() == RECOVERY_CODE_LENGTH
+        && code
+            .bytes()
+            .all(|byte| RECOVERY_CODE_ALPHABET.contains(&byte))
+}
+
+pub fn verify_recovery_code(
+    hasher: &Hasher,
+    code: &TwoFactorCode,
+    hash: &RecoveryCodeHash,
+) -> bool {
+    argon2::PasswordHash::new(hash.as_ref()).is_ok_and(|hash| {
+        hasher
+            .expect_argon()
+            .verify_password(code.normalized().as_bytes(), &hash)
+            .is_ok()
+    })
+}
@@ -66,6 +66,11 @@ impl Localizer {
         Ok(bundles)
     }
 
+    pub fn localize(&self, locale: &LanguageIdentifier, text_id: &str) -> Option<String> {
+        self.lookup(locale, text_id, None)
+            .or_else(|| self.lookup(&self.fallback, text_id, None))
+    }
+
     fn lookup(
         &self,
         locale: &LanguageIdentifier,
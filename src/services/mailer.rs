@@ -0,0 +1,213 @@
+use std::{fs, io::Write, path::PathBuf};
+
+use anyhow::Context;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    domain::mail::{Mail, MailContent, MailTemplate},
+    http::TemplateRenderer,
+};
+
+use super::localizer::Localizer;
+
+pub trait Mailer {
+    async fn deliver(&self, message: Message) -> anyhow::Result<()>;
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MailerConfig {
+    pub from: String,
+    pub base_url: String,
+    pub transport: TransportConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportConfig {
+    Smtp(SmtpConfig),
+    Outbox(OutboxConfig),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub user: String,
+    pub password: SecretString,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct OutboxConfig {
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Clone)]
+pub struct MailService {
+    from: Mailbox,
+    base_url: String,
+    transport: Transport,
+}
+
+#[derive(Clone)]
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+#[derive(Clone)]
+pub struct OutboxMailer {
+    transport: Option<AsyncFileTransport<Tokio1Executor>>,
+}
+
+#[derive(Clone)]
+enum Transport {
+    Smtp(SmtpMailer),
+    Outbox(OutboxMailer),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MailContext<'a, T> {
+    base_url: &'a str,
+    data: T,
+}
+
+impl MailService {
+    pub fn new(config: MailerConfig) -> anyhow::Result<Self> {
+        let MailerConfig {
+            from,
+            base_url,
+            transport,
+        } = config;
+        let from = from.parse().context("parse sender mailbox")?;
+        let transport = match transport {
+            TransportConfig::Smtp(config) => Transport::Smtp(SmtpMailer::new(config)?),
+            TransportConfig::Outbox(config) => Transport::Outbox(OutboxMailer::new(config)?),
+        };
+        Ok(Self {
+            from,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            transport,
+        })
+    }
+}
+
+impl SmtpMailer {
+    pub fn new(config: SmtpConfig) -> anyhow::Result<Self> {
+        let SmtpConfig {
+            host,
+            port,
+            user,
+            password,
+        } = config;
+        let credentials = Credentials::new(user, password.expose_secret().to_owned());
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)
+            .context("configure smtp relay")?
+            .port(port)
+            .credentials(credentials)
+            .build();
+        Ok(Self { transport })
+    }
+}
+
+impl OutboxMailer {
+    pub fn new(config: OutboxConfig) -> anyhow::Result<Self> {
+        let transport = match config.path {
+            Some(path) => {
+                fs::create_dir_all(&path).context("create outbox directory")?;
+                Some(AsyncFileTransport::new(path))
+            }
+            None => None,
+        };
+        Ok(Self { transport })
+    }
+}
+
+impl Mailer for SmtpMailer {
+    async fn deliver(&self, message: Message) -> anyhow::Result<()> {
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .context("send mail over smtp")
+    }
+}
+
+impl Mailer for OutboxMailer {
+    async fn deliver(&self, message: Message) -> anyhow::Result<()> {
+        match &self.transport {
+            Some(transport) => transport
+                .send(message)
+                .await
+                .map(|_| ())
+                .context("write mail to outbox"),
+            None => std::io::stdout()
+                .lock()
+                .write_all(&message.formatted())
+                .context("write mail to stdout"),
+        }
+    }
+}
+
+impl Mailer for Transport {
+    async fn deliver(&self, message: Message) -> anyhow::Result<()> {
+        match self {
+            Self::Smtp(mailer) => mailer.deliver(message).await,
+            Self::Outbox(mailer) => mailer.deliver(message).await,
+        }
+    }
+}
+
+#[tracing::instrument(skip(mailer, mail), err(Debug))]
+pub async fn send_mail(mailer: &MailService, mail: Mail) -> crate::Result<()> {
+    let Mail { to, content } = mail;
+    let to = Into::<String>::into(to)
+        .parse::<Mailbox>()
+        .context("parse recipient mailbox")?;
+    let message = Message::builder()
+        .from(mailer.from.clone())
+        .to(to)
+        .subject(content.subject)
+        .header(ContentType::TEXT_HTML)
+        .body(content.body)
+        .context("build mail message")?;
+    mailer.transport.deliver(message).await?;
+    Ok(())
+}
+
+#[tracing::instrument(skip(mailer, renderer, localizer, data), err(Debug))]
+pub fn render_mail<T>(
+    mailer: &MailService,
+    renderer: &impl TemplateRenderer,
+    localizer: &Localizer,
+    template: MailTemplate,
+    data: T,
+    locale: &LanguageIdentifier,
+) -> crate::Result<MailContent>
+where
+    T: Serialize,
+{
+    let (template_name, subject_key) = match template {
+        MailTemplate::PasswordReset => ("emails/password-reset.html", "password-reset-subject"),
+        MailTemplate::EmailVerification => (
+            "emails/email-verification.html",
+            "email-verification-subject",
+        ),
+        MailTemplate::TeacherInvite => ("emails/teacher-invite.html", "teacher-invite-subject"),
+    };
+    let context = MailContext {
+        base_url: &mailer.base_url,
+        data,
+    };
+    let body = renderer.render_template(template_name, context, locale, None)?;
+    let subject = localizer
+        .localize(locale, subject_key)
+        .with_context(|| format!("localize mail subject `{subject_key}`"))?;
+    Ok(MailContent { subject, body })
+}
@@ -1,7 +1,11 @@
+pub mod authenticator;
 pub mod database;
 pub mod hasher;
 pub mod id_encoder;
 pub mod localizer;
+pub mod mailer;
 pub mod signer;
+pub mod spreadsheet;
 pub mod templating_engine;
+pub mod throttler;
 pub mod token_issuer;
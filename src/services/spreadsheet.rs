@@ -0,0 +1,70 @@
+use anyhow::Context;
+use rust_xlsxwriter::Workbook;
+use squint::{tag, Id};
+
+use crate::domain::grade_export::{GradeSheet, GradeSheetRow};
+
+const GRADE_CELL: usize = 4;
+const STATUS_CELL: usize = 5;
+
+#[tracing::instrument(skip(sheet), err(Debug))]
+pub fn write_csv(sheet: &GradeSheet) -> crate::Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record(&sheet.columns)
+        .context("write csv header")?;
+    for row in &sheet.rows {
+        writer
+            .write_record(row_cells(row))
+            .context("write csv record")?;
+    }
+    writer
+        .into_inner()
+        .map_err(|error| error.into_error())
+        .context("flush csv writer")
+        .map_err(crate::Error::internal)
+}
+
+#[tracing::instrument(skip(sheet), err(Debug))]
+pub fn write_xlsx(sheet: &GradeSheet) -> crate::Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    for (col, column) in (0u16..).zip(&sheet.columns) {
+        worksheet
+            .write_string(0, col, column)
+            .context("write xlsx header")?;
+    }
+    for (row_index, row) in (1u32..).zip(&sheet.rows) {
+        let cells = row_cells(row);
+        for (col, cell) in (0u16..).zip(&cells[..GRADE_CELL]) {
+            worksheet
+                .write_string(row_index, col, cell)
+                .context("write xlsx cell")?;
+        }
+        if let Some(grade) = &row.grade {
+            match grade.parse::<f64>() {
+                Ok(number) => worksheet.write_number(row_index, GRADE_CELL as u16, number),
+                Err(_) => worksheet.write_string(row_index, GRADE_CELL as u16, grade),
+            }
+            .context("write xlsx grade")?;
+        }
+        worksheet
+            .write_string(row_index, STATUS_CELL as u16, &cells[STATUS_CELL])
+            .context("write xlsx status")?;
+    }
+    worksheet.autofit();
+    let buffer = workbook.save_to_buffer().context("save xlsx workbook")?;
+    Ok(buffer)
+}
+
+fn row_cells(row: &GradeSheetRow) -> [String; 6] {
+    let student_id: Id<{ tag("user") }> = row.student_id.clone().into();
+    [
+        student_id.to_string(),
+        row.student_name.clone().into(),
+        row.subject_id.clone().into(),
+        row.subject_title.clone().into(),
+        row.grade.clone().unwrap_or_default(),
+        row.status.as_str().to_owned(),
+    ]
+}
@@ -5,7 +5,10 @@ use serde::{Deserialize, Serialize};
 use tera::Tera;
 use unic_langid::LanguageIdentifier;
 
-use crate::{app::localization::LocalizedError, http::TemplateRenderer};
+use crate::{
+    app::localization::LocalizedError,
+    http::{CsrfToken, TemplateRenderer},
+};
 
 static LOCALIZE_FUNCTION: &str = "localize";
 
@@ -55,17 +58,18 @@ impl<L> TemplateRenderer for TemplatingEngine<L>
 where
     L: TemplateLocalizer + 'static,
 {
-    #[tracing::instrument(skip(self, data), err(Debug))]
+    #[tracing::instrument(skip(self, data, csrf_token), err(Debug))]
     fn render_template<T>(
         &self,
         template_name: &str,
         data: T,
         locale: &LanguageIdentifier,
+        csrf_token: Option<&CsrfToken>,
     ) -> crate::Result<String>
     where
         T: Serialize,
     {
-        render_template(self, template_name, data, locale)
+        render_template(self, template_name, data, locale, csrf_token)
     }
 }
 
@@ -74,6 +78,7 @@ fn render_template<T, L>(
     template_name: &str,
     data: T,
     locale: &LanguageIdentifier,
+    csrf_token: Option<&CsrfToken>,
 ) -> crate::Result<String>
 where
     T: Serialize,
@@ -85,6 +90,7 @@ where
     let mut tera_context = tera::Context::new();
     tera_context.insert("context", &context);
     tera_context.insert("locale", locale);
+    tera_context.insert("csrf_token", &csrf_token);
     let html = templating_engine
         .tera
         .render(template_name, &tera_context)
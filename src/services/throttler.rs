@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use jsonwebtoken::get_current_timestamp;
+use serde::Deserialize;
+use serde_with::{serde_as, DurationSeconds};
+
+use crate::domain::throttle::LoginFailures;
+
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+pub struct ThrottleConfig {
+    #[serde_as(as = "DurationSeconds<u64>")]
+    window: Duration,
+    #[serde_as(as = "DurationSeconds<u64>")]
+    backoff: Duration,
+    #[serde_as(as = "DurationSeconds<u64>")]
+    lockout: Duration,
+    email: ThrottleLimits,
+    ip: ThrottleLimits,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThrottleLimits {
+    free_attempts: u32,
+    lockout_after: u32,
+}
+
+#[derive(Clone)]
+pub struct Throttler {
+    config: ThrottleConfig,
+}
+
+impl Throttler {
+    pub fn new(config: ThrottleConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.config.window
+    }
+
+    fn delay(&self, failures: i64, limits: ThrottleLimits) -> Option<Duration> {
+        let failures = u32::try_from(failures).unwrap_or(u32::MAX);
+        if failures >= limits.lockout_after {
+            return Some(self.config.lockout);
+        }
+        let excess = failures.checked_sub(limits.free_attempts)?;
+        let delay = self
+            .config
+            .backoff
+            .saturating_mul(2u32.saturating_pow(excess));
+        Some(delay.min(self.config.lockout))
+    }
+}
+
+pub fn compute_login_delay(throttler: &Throttler, failures: &LoginFailures) -> Option<Duration> {
+    let now = get_current_timestamp();
+    [
+        (
+            failures.email_failures,
+            failures.email_last_failure_at,
+            throttler.config.email,
+        ),
+        (
+            failures.ip_failures,
+            failures.ip_last_failure_at,
+            throttler.config.ip,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(count, last_failure_at, limits)| {
+        let last_failure_at = u64::try_from(last_failure_at?).ok()?;
+        let delay = throttler.delay(count, limits)?;
+        last_failure_at
+            .saturating_add(delay.as_secs())
+            .checked_sub(now)
+            .filter(|remaining| *remaining > 0)
+            .map(Duration::from_secs)
+    })
+    .max()
+}
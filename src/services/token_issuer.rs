@@ -1,19 +1,68 @@
 use std::time::Duration;
 
 use anyhow::Context;
+use argon2::password_hash::rand_core::{OsRng, RngCore};
 use jsonwebtoken::{get_current_timestamp, DecodingKey, EncodingKey, Header, Validation};
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DurationSeconds};
+use sha2::{Digest, Sha256};
 
-use crate::domain::{auth::ParseTokenError, token::AuthToken, user_id::UserId};
+use crate::domain::{
+    auth::{AuthClaims, IssuedRefreshToken, IssuedResetToken, ParseTokenError},
+    email::{Email, MaybeEmail},
+    invites::IssuedInviteToken,
+    token::{
+        AuthToken, InviteToken, InviteTokenHash, RefreshToken, RefreshTokenHash, ResetToken,
+        ResetTokenHash, SessionId, TokenId, VerificationToken,
+    },
+    two_factor::{ChallengeClaims, TwoFactorChallenge, TwoFactorError},
+    user_id::UserId,
+};
+
+const REFRESH_TOKEN_BYTES: usize = 32;
+
+const RESET_TOKEN_BYTES: usize = 32;
+
+const INVITE_TOKEN_BYTES: usize = 32;
+
+const VERIFICATION_AUDIENCE: &str = "email-verification";
+
+const CHALLENGE_AUDIENCE: &str = "two-factor";
+
+const REFRESH_ROTATION_CONTEXT: &[u8] = b"refresh-rotation";
 
 #[serde_as]
 #[derive(Clone, Debug, Deserialize)]
 pub struct JwtConfig {
     #[serde_as(as = "DurationSeconds<u64>")]
     ttl: Duration,
+    #[serde_as(as = "DurationSeconds<u64>")]
+    refresh_ttl: Duration,
+    #[serde_as(as = "DurationSeconds<u64>")]
+    reset_ttl: Duration,
+    #[serde_as(as = "DurationSeconds<u64>")]
+    verification_ttl: Duration,
+    #[serde_as(as = "DurationSeconds<u64>")]
+    challenge_ttl: Duration,
+    #[serde_as(as = "DurationSeconds<u64>")]
+    invite_ttl: Duration,
     secret: SecretString,
+    cookie: Option<CookieConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CookieConfig {
+    pub secure: bool,
+    pub same_site: SameSite,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
 }
 
 #[derive(Clone)]
@@ -24,21 +73,56 @@ pub struct TokenIssuer {
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct TokenClaims {
+    jti: TokenId,
+    sid: SessionId,
     iat: u64,
     exp: u64,
     user_id: UserId,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+struct VerificationClaims {
+    aud: String,
+    iat: u64,
+    exp: u64,
+    email: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TwoFactorClaims {
+    jti: TokenId,
+    aud: String,
+    iat: u64,
+    exp: u64,
+    user_id: UserId,
+    email: String,
+}
+
 impl TokenIssuer {
     pub fn new(config: JwtConfig) -> Self {
         Self { config }
     }
+
+    pub fn cookie_config(&self) -> Option<&CookieConfig> {
+        self.config.cookie.as_ref()
+    }
+
+    pub fn session_ttl(&self) -> Duration {
+        self.config.refresh_ttl
+    }
 }
 
 #[tracing::instrument(skip(issuer), ret(level = "debug") err(Debug, level = "debug"))]
-pub fn issue_token(issuer: &TokenIssuer, user_id: UserId) -> crate::Result<AuthToken> {
+pub fn issue_token(
+    issuer: &TokenIssuer,
+    user_id: UserId,
+    session_id: SessionId,
+) -> crate::Result<AuthToken> {
     let now = get_current_timestamp();
     let claims = TokenClaims {
+        jti: TokenId::new(),
+        sid: session_id,
         iat: now,
         exp: now + issuer.config.ttl.as_secs(),
         user_id,
@@ -57,7 +141,7 @@ pub fn issue_token(issuer: &TokenIssuer, user_id: UserId) -> crate::Result<AuthT
 pub fn parse_token(
     issuer: &TokenIssuer,
     token: AuthToken,
-) -> crate::Result<UserId, ParseTokenError> {
+) -> crate::Result<AuthClaims, ParseTokenError> {
     let claims = jsonwebtoken::decode::<TokenClaims>(
         &Into::<String>::into(token),
         &DecodingKey::from_secret(issuer.config.secret.expose_secret().as_bytes()),
@@ -65,5 +149,171 @@ pub fn parse_token(
     )
     .map(|token| token.claims)
     .map_err(|_| crate::Error::expected(ParseTokenError::Invalid))?;
-    Ok(claims.user_id)
+    Ok(AuthClaims {
+        token_id: claims.jti,
+        session_id: claims.sid,
+        user_id: claims.user_id,
+        expires_at: claims.exp,
+    })
+}
+
+#[tracing::instrument(skip(issuer), ret(level = "debug") err(Debug, level = "debug"))]
+pub fn issue_refresh_token(issuer: &TokenIssuer) -> crate::Result<IssuedRefreshToken> {
+    let mut bytes = [0u8; REFRESH_TOKEN_BYTES];
+    OsRng
+        .try_fill_bytes(&mut bytes)
+        .context("generate refresh token")?;
+    let token = RefreshToken::new(hex::encode(bytes));
+    let hash = hash_refresh_token(&token);
+    Ok(IssuedRefreshToken {
+        token,
+        hash,
+        ttl: issuer.config.refresh_ttl,
+    })
+}
+
+pub fn rotate_refresh_token(issuer: &TokenIssuer, token: &RefreshToken) -> IssuedRefreshToken {
+    let digest = Sha256::new()
+        .chain_update(REFRESH_ROTATION_CONTEXT)
+        .chain_update(issuer.config.secret.expose_secret().as_bytes())
+        .chain_update(token.as_ref().as_bytes())
+        .finalize();
+    let token = RefreshToken::new(hex::encode(digest));
+    let hash = hash_refresh_token(&token);
+    IssuedRefreshToken {
+        token,
+        hash,
+        ttl: issuer.config.refresh_ttl,
+    }
+}
+
+pub fn hash_refresh_token(token: &RefreshToken) -> RefreshTokenHash {
+    let digest = Sha256::digest(token.as_ref().as_bytes());
+    RefreshTokenHash::new(hex::encode(digest))
+}
+
+#[tracing::instrument(skip(issuer), ret(level = "debug") err(Debug, level = "debug"))]
+pub fn issue_reset_token(issuer: &TokenIssuer) -> crate::Result<IssuedResetToken> {
+    let mut bytes = [0u8; RESET_TOKEN_BYTES];
+    OsRng
+        .try_fill_bytes(&mut bytes)
+        .context("generate reset token")?;
+    let token = ResetToken::new(hex::encode(bytes));
+    let hash = hash_reset_token(&token);
+    Ok(IssuedResetToken {
+        token,
+        hash,
+        ttl: issuer.config.reset_ttl,
+    })
+}
+
+pub fn hash_reset_token(token: &ResetToken) -> ResetTokenHash {
+    let digest = Sha256::digest(token.as_ref().as_bytes());
+    ResetTokenHash::new(hex::encode(digest))
+}
+
+#[tracing::instrument(skip(issuer), ret(level = "debug") err(Debug, level = "debug"))]
+pub fn issue_invite_token(issuer: &TokenIssuer) -> crate::Result<IssuedInviteToken> {
+    let mut bytes = [0u8; INVITE_TOKEN_BYTES];
+    OsRng
+        .try_fill_bytes(&mut bytes)
+        .context("generate invite token")?;
+    let token = InviteToken::new(hex::encode(bytes));
+    let hash = hash_invite_token(&token);
+    Ok(IssuedInviteToken {
+        token,
+        hash,
+        ttl: issuer.config.invite_ttl,
+    })
+}
+
+pub fn hash_invite_token(token: &InviteToken) -> InviteTokenHash {
+    let digest = Sha256::digest(token.as_ref().as_bytes());
+    InviteTokenHash::new(hex::encode(digest))
+}
+
+#[tracing::instrument(skip(issuer), ret(level = "debug") err(Debug, level = "debug"))]
+pub fn issue_verification_token(
+    issuer: &TokenIssuer,
+    email: &Email,
+) -> crate::Result<VerificationToken> {
+    let now = get_current_timestamp();
+    let claims = VerificationClaims {
+        aud: VERIFICATION_AUDIENCE.to_owned(),
+        iat: now,
+        exp: now + issuer.config.verification_ttl.as_secs(),
+        email: email.clone().into(),
+    };
+    let token = jsonwebtoken::encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(issuer.config.secret.expose_secret().as_bytes()),
+    )
+    .map(VerificationToken::new)
+    .context("encode verification jwt")?;
+    Ok(token)
+}
+
+#[tracing::instrument(skip(issuer), ret(level = "debug") err(Debug, level = "debug"))]
+pub fn parse_verification_token(
+    issuer: &TokenIssuer,
+    token: VerificationToken,
+) -> crate::Result<MaybeEmail, ParseTokenError> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[VERIFICATION_AUDIENCE]);
+    let claims = jsonwebtoken::decode::<VerificationClaims>(
+        &Into::<String>::into(token),
+        &DecodingKey::from_secret(issuer.config.secret.expose_secret().as_bytes()),
+        &validation,
+    )
+    .map(|token| token.claims)
+    .map_err(|_| crate::Error::expected(ParseTokenError::Invalid))?;
+    Ok(MaybeEmail::new(claims.email))
+}
+
+#[tracing::instrument(skip(issuer), err(Debug, level = "debug"))]
+pub fn issue_two_factor_challenge(
+    issuer: &TokenIssuer,
+    user_id: UserId,
+    email: MaybeEmail,
+) -> crate::Result<TwoFactorChallenge> {
+    let now = get_current_timestamp();
+    let claims = TwoFactorClaims {
+        jti: TokenId::new(),
+        aud: CHALLENGE_AUDIENCE.to_owned(),
+        iat: now,
+        exp: now + issuer.config.challenge_ttl.as_secs(),
+        user_id,
+        email: email.into(),
+    };
+    let challenge = jsonwebtoken::encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(issuer.config.secret.expose_secret().as_bytes()),
+    )
+    .map(TwoFactorChallenge::new)
+    .context("encode two-factor challenge")?;
+    Ok(challenge)
+}
+
+#[tracing::instrument(skip(issuer), ret(level = "debug") err(Debug, level = "debug"))]
+pub fn parse_two_factor_challenge(
+    issuer: &TokenIssuer,
+    challenge: TwoFactorChallenge,
+) -> crate::Result<ChallengeClaims, TwoFactorError> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[CHALLENGE_AUDIENCE]);
+    let claims = jsonwebtoken::decode::<TwoFactorClaims>(
+        &Into::<String>::into(challenge),
+        &DecodingKey::from_secret(issuer.config.secret.expose_secret().as_bytes()),
+        &validation,
+    )
+    .map(|token| token.claims)
+    .map_err(|_| crate::Error::expected(TwoFactorError::ChallengeExpired))?;
+    Ok(ChallengeClaims {
+        challenge_id: claims.jti,
+        user_id: claims.user_id,
+        email: MaybeEmail::new(claims.email),
+        expires_at: claims.exp,
+    })
 }